@@ -0,0 +1,43 @@
+use std::str::FromStr;
+use anyhow::Result;
+
+use solpredict::solana as anchor_client_;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use prediction_market_program::constants::{PAUSE_ALL, PAUSE_BETTING, PAUSE_CLAIMS, PAUSE_CREATION};
+
+fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let ctx = anchor_client_::connect_devnet()?;
+    let signer = &*ctx.payer;
+
+    let action = std::env::args().nth(1).unwrap_or_default();
+    let arg = std::env::args().nth(2);
+
+    let sig = match action.as_str() {
+        // run as the current admin
+        "propose" => {
+            let new_admin = Pubkey::from_str(&arg.expect("usage: admin_controls propose <pubkey>"))?;
+            anchor_client_::propose_admin(&ctx, signer, new_admin)?
+        }
+        // run with the proposed admin as payer
+        "accept" => anchor_client_::accept_admin(&ctx, signer)?,
+        "pause" => {
+            let flags = match arg.as_deref().unwrap_or("all") {
+                "all" => PAUSE_ALL,
+                list => list.split(',').try_fold(0u8, |acc, f| match f.trim() {
+                    "betting" => Ok(acc | PAUSE_BETTING),
+                    "creation" => Ok(acc | PAUSE_CREATION),
+                    "claims" => Ok(acc | PAUSE_CLAIMS),
+                    other => Err(anyhow::anyhow!("unknown pause flag {other}")),
+                })?,
+            };
+            anchor_client_::set_paused(&ctx, signer, flags)?
+        }
+        "unpause" => anchor_client_::set_paused(&ctx, signer, 0)?,
+        other => anyhow::bail!("unknown action {other:?}, expected propose, accept, pause or unpause"),
+    };
+    println!("{} OK: {}", action, sig);
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use anyhow::Result;
 use solpredict::solana as anchor_client_;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use prediction_market_program::constants::{PAUSE_BETTING, PAUSE_CLAIMS, PAUSE_CREATION, TIP_CURVE_LINEAR};
 
 fn main() -> Result<()> {
     dotenvy::dotenv().ok();
@@ -10,11 +12,38 @@ fn main() -> Result<()> {
         Ok(config) => {
             println!("Config account exists and is valid!");
             println!("   Admin: {}", config.admin);
+            if config.pending_admin != Pubkey::default() {
+                println!("   Pending Admin: {}", config.pending_admin);
+            }
             println!("   Treasury: {}", config.treasury_wallet);
             println!("   Fee BPS: {}", config.fee_bps);
             println!("   Resolver BPS: {}", config.resolver_bps);
             println!("   Creator BPS: {}", config.creator_bps);
             println!("   Tip Cap: {}", config.resolver_tip_cap);
+            println!("   Tip Curve: {}", if config.tip_curve == TIP_CURVE_LINEAR { "linear" } else { "flat" });
+            println!("   Dispute Window (s): {}", config.dispute_window_secs);
+            println!("   Dispute Bond: {}", config.dispute_bond);
+            println!("   Dispute Reward BPS: {}", config.dispute_reward_bps);
+            println!("   Dispute Authority: {}", config.dispute_authority);
+            println!("   Exit Fee BPS: {}", config.exit_fee_bps);
+            println!("   Exit Lock (s): {}", config.exit_lock_secs);
+            println!("   Max Bet Cap: {}", config.max_bet_cap);
+            println!("   Max Per Wallet Cap: {}", config.max_per_wallet_cap);
+            println!("   Crank Fee BPS: {}", config.crank_fee_bps);
+            println!("   Crank Fee Cap: {}", config.crank_fee_cap);
+            println!("   Crank Delay (s): {}", config.crank_delay_secs);
+            println!("   Referral BPS: {}", config.referral_bps);
+            println!("   LP Fee Share BPS: {}", config.lp_fee_share_bps);
+            println!("   Layout Version: {}", config.version);
+            for mint in config.allowed_mints() {
+                println!("   Collateral Mint: {}", mint);
+            }
+            println!(
+                "   Paused: betting={} creation={} claims={}",
+                config.is_paused(PAUSE_BETTING),
+                config.is_paused(PAUSE_CREATION),
+                config.is_paused(PAUSE_CLAIMS),
+            );
             Ok(())
         }
         Err(e) => {
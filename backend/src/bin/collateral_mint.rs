@@ -0,0 +1,25 @@
+use std::str::FromStr;
+use anyhow::Result;
+
+use solpredict::solana as anchor_client_;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let ctx = anchor_client_::connect_devnet()?;
+    let admin = &*ctx.payer;
+
+    let action = std::env::args().nth(1).unwrap_or_else(|| "add".to_string());
+    let mint = Pubkey::from_str(&std::env::var("COLLATERAL_MINT")
+        .expect("set COLLATERAL_MINT=<pubkey>"))?;
+
+    let sig = match action.as_str() {
+        "add" => anchor_client_::add_collateral_mint(&ctx, admin, mint)?,
+        "remove" => anchor_client_::remove_collateral_mint(&ctx, admin, mint)?,
+        other => anyhow::bail!("unknown action {other}, expected add or remove"),
+    };
+    println!("{}_collateral_mint OK: {}", action, sig);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use solpredict::solana as anchor_client_;
+use prediction_market_program::constants::AIRDROP_AMOUNT;
+
+const DECIMALS: u8 = 6;
+
+fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let ctx = anchor_client_::connect_devnet()?;
+    let admin = &*ctx.payer;
+
+    let action = std::env::args().nth(1).unwrap_or_else(|| "show".to_string());
+    let arg = std::env::args().nth(2);
+
+    let sig = match action.as_str() {
+        "init" => {
+            let amount = match std::env::var("FAUCET_AMOUNT_UI") {
+                Ok(v) => anchor_client_::ui_to_base_units(v.parse()?, DECIMALS),
+                Err(_) => AIRDROP_AMOUNT,
+            };
+            let cooldown_secs: i64 = std::env::var("FAUCET_COOLDOWN_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()?;
+            anchor_client_::init_faucet(&ctx, admin, amount, cooldown_secs)?
+        }
+        // 0 disables the faucet
+        "amount" => {
+            let ui: f64 = arg.expect("usage: faucet amount <usdc>").parse()?;
+            let amount = anchor_client_::ui_to_base_units(ui, DECIMALS);
+            anchor_client_::update_faucet(&ctx, admin, Some(amount), None)?
+        }
+        "cooldown" => {
+            let secs: i64 = arg.expect("usage: faucet cooldown <secs>").parse()?;
+            anchor_client_::update_faucet(&ctx, admin, None, Some(secs))?
+        }
+        "show" => {
+            let faucet = anchor_client_::get_faucet_account(&ctx)?;
+            println!("Faucet:");
+            println!("   Amount: {}", faucet.amount);
+            println!("   Cooldown (s): {}", faucet.cooldown_secs);
+            return Ok(());
+        }
+        other => anyhow::bail!("unknown action {other:?}, expected init, amount, cooldown or show"),
+    };
+    println!("faucet {} OK: {}", action, sig);
+
+    Ok(())
+}
@@ -2,7 +2,8 @@ use std::str::FromStr;
 use anyhow::Result;
 
 use solpredict::solana as anchor_client_;
-use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signer};
+use prediction_market_program::instructions::InitConfigParams;
 
 fn main() -> Result<()> {
     dotenvy::dotenv().ok();
@@ -17,9 +18,64 @@ fn main() -> Result<()> {
     let resolver_bps: u16 = std::env::var("RESOLVER_BPS").expect("set RESOLVER_BPS").parse()?;
     let creator_bps: u16 = std::env::var("RESOLVER_BPS").expect("set RESOLVER_BPS").parse()?;
     let tip_cap: u64 = std::env::var("TIP_CAP").expect("set TIP_CAP").parse()?;
+    let dispute_window: i64 = std::env::var("DISPUTE_WINDOW_SECS").expect("set DISPUTE_WINDOW_SECS").parse()?;
+    let dispute_bond: u64 = std::env::var("DISPUTE_BOND").expect("set DISPUTE_BOND").parse()?;
+    let dispute_reward_bps: u16 = std::env::var("DISPUTE_REWARD_BPS").expect("set DISPUTE_REWARD_BPS").parse()?;
+    // Secondary oracle allowed to rule disputes; defaults to the admin
+    let dispute_authority = match std::env::var("DISPUTE_AUTHORITY") {
+        Ok(v) => Pubkey::from_str(&v)?,
+        Err(_) => admin.pubkey(),
+    };
 
-    let sig = anchor_client_::init_config(&ctx, admin, treasury, fee_bps, resolver_bps, creator_bps, tip_cap)?;
+    let exit_fee_bps: u16 = std::env::var("EXIT_FEE_BPS").expect("set EXIT_FEE_BPS").parse()?;
+    let exit_lock_secs: i64 = std::env::var("EXIT_LOCK_SECS").expect("set EXIT_LOCK_SECS").parse()?;
+
+    // Ceilings for creator stake limits, base units; 0 = uncapped
+    let max_bet_cap: u64 = std::env::var("MAX_BET_CAP").map_or(Ok(0), |v| v.parse())?;
+    let max_per_wallet_cap: u64 = std::env::var("MAX_PER_WALLET_CAP").map_or(Ok(0), |v| v.parse())?;
+
+    // Resolver tip curve: 0 = flat, 1 = decays to zero at the resolve horizon
+    let tip_curve: u8 = std::env::var("TIP_CURVE").map_or(Ok(0), |v| v.parse())?;
+
+    // Payout crank: fee bps (max 100), fee cap in base units (0 = uncapped), delay after end_ts
+    let crank_fee_bps: u16 = std::env::var("CRANK_FEE_BPS").map_or(Ok(0), |v| v.parse())?;
+    let crank_fee_cap: u64 = std::env::var("CRANK_FEE_CAP").map_or(Ok(0), |v| v.parse())?;
+    let crank_delay_secs: i64 = std::env::var("CRANK_DELAY_SECS").map_or(Ok(7 * 86_400), |v| v.parse())?;
+
+    // Cut of referred volume paid to referrers at settlement
+    let referral_bps: u16 = std::env::var("REFERRAL_BPS").map_or(Ok(0), |v| v.parse())?;
+
+    // Share of the protocol fee paid to liquidity providers
+    let lp_fee_share_bps: u16 = std::env::var("LP_FEE_SHARE_BPS").map_or(Ok(0), |v| v.parse())?;
+
+    let params = InitConfigParams {
+        fee_bps,
+        resolver_bps,
+        creator_bps,
+        resolver_tip_cap: tip_cap,
+        dispute_window_secs: dispute_window,
+        dispute_bond,
+        dispute_reward_bps,
+        dispute_authority,
+        exit_fee_bps,
+        exit_lock_secs,
+        max_bet_cap,
+        max_per_wallet_cap,
+        tip_curve,
+        crank_fee_bps,
+        crank_fee_cap,
+        crank_delay_secs,
+        referral_bps,
+        lp_fee_share_bps,
+    };
+
+    let sig = anchor_client_::init_config(&ctx, admin, treasury, params)?;
     println!("init_config OK: {}", sig);
 
+    // Markets can only be created with allowlisted collateral; start with USDC
+    let usdc_mint = Pubkey::from_str(&std::env::var("USDC_MINT").expect("set USDC_MINT"))?;
+    let sig = anchor_client_::add_collateral_mint(&ctx, admin, usdc_mint)?;
+    println!("add_collateral_mint OK: {}", sig);
+
     Ok(())
 }
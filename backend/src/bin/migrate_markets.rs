@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+use anyhow::Result;
+
+use solpredict::{config, db, repo::position as position_repo, solana as anchor_client_, telemetry};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+/// Positions per migrate_market tx, besides the market itself
+const POSITIONS_PER_TX: usize = 20;
+
+/// One-shot sweep bringing the config, then every indexed market and its positions, up to the
+/// current account layout. Run right after a program upgrade: nothing that loads the config
+/// works until it is migrated. Accounts already current are skipped; the backend payer covers
+/// the rent.
+#[tokio::main]
+async fn main() -> Result<()> {
+    telemetry::init();
+
+    let settings = config::load()?;
+    let db = db::init_pool(&settings.database.url).await?;
+    let ctx = Arc::new(anchor_client_::connect_devnet()?);
+
+    let c = ctx.clone();
+    match tokio::task::spawn_blocking(move || anchor_client_::ensure_config_migrated(&c)).await?? {
+        Some(sig) => tracing::info!(%sig, "config migrated"),
+        None => tracing::info!("config already current"),
+    }
+
+    let rows = position_repo::fetch_migration_rows(db.pool()).await?;
+
+    let mut by_market: BTreeMap<String, Vec<Pubkey>> = BTreeMap::new();
+    for r in rows {
+        let market = Pubkey::from_str(&r.market_pda)?;
+        let positions = by_market.entry(r.market_pda).or_default();
+        let Some(user) = r.user_pubkey else { continue };
+        let user = Pubkey::from_str(&user)?;
+        positions.push(match (r.lp, r.outcome_idx) {
+            (true, _) => anchor_client_::pda_lp_position(&market, &user).0,
+            (false, Some(idx)) => anchor_client_::pda_position_multi(&market, &user, idx as u8).0,
+            (false, None) => anchor_client_::pda_position(&market, &user).0,
+        });
+    }
+
+    let (mut sent, mut failed) = (0usize, 0usize);
+    for (market_str, positions) in by_market {
+        let market = Pubkey::from_str(&market_str)?;
+        // The first batch also carries the market when it has no positions at all
+        let batches: Vec<Vec<Pubkey>> = if positions.is_empty() {
+            vec![Vec::new()]
+        } else {
+            positions.chunks(POSITIONS_PER_TX).map(<[Pubkey]>::to_vec).collect()
+        };
+
+        for batch in batches {
+            let c = ctx.clone();
+            let res = tokio::task::spawn_blocking(move || anchor_client_::ensure_migrated(&c, market, &batch)).await?;
+            match res {
+                Ok(Some(sig)) => {
+                    sent += 1;
+                    tracing::info!(market = %market_str, %sig, "migrated");
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    failed += 1;
+                    tracing::warn!(market = %market_str, "migration failed: {e:#}");
+                }
+            }
+        }
+    }
+
+    println!("migrate_markets done: {sent} txs sent, {failed} failed");
+    Ok(())
+}
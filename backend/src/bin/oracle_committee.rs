@@ -0,0 +1,31 @@
+use std::str::FromStr;
+use anyhow::Result;
+
+use solpredict::solana as anchor_client_;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let ctx = anchor_client_::connect_devnet()?;
+    let admin = &*ctx.payer;
+
+    let action = std::env::args().nth(1).unwrap_or_else(|| "create".to_string());
+    let id: u64 = std::env::var("COMMITTEE_ID").expect("set COMMITTEE_ID").parse()?;
+    let members = std::env::var("COMMITTEE_MEMBERS")
+        .expect("set COMMITTEE_MEMBERS=<pubkey>,<pubkey>,...")
+        .split(',')
+        .map(|s| Pubkey::from_str(s.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let threshold: u8 = std::env::var("COMMITTEE_THRESHOLD").expect("set COMMITTEE_THRESHOLD").parse()?;
+
+    let sig = match action.as_str() {
+        "create" => anchor_client_::create_committee(&ctx, admin, id, members, threshold)?,
+        "update" => anchor_client_::update_committee(&ctx, admin, id, members, threshold)?,
+        other => anyhow::bail!("unknown action {other}, expected create or update"),
+    };
+    println!("{}_committee OK: {}", action, sig);
+    println!("AI_ORACLE_COMMITTEE={}", anchor_client_::pda_committee(id).0);
+
+    Ok(())
+}
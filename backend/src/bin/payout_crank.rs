@@ -0,0 +1,77 @@
+use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
+use anyhow::Result;
+
+use solpredict::{config, db, repo::{bets as bets_repo, position as position_repo}, solana as anchor_client_, telemetry};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+/// Pushes winnings for recently ended markets that winners have not claimed themselves.
+/// Env: CRANK_INTERVAL_SECS (default 300), CRANK_LOOKBACK_SECS (default 30 days).
+#[tokio::main]
+async fn main() -> Result<()> {
+    telemetry::init();
+
+    let settings = config::load()?;
+    let db = db::init_pool(&settings.database.url).await?;
+    let ctx = Arc::new(anchor_client_::connect_devnet()?);
+
+    let interval: u64 = std::env::var("CRANK_INTERVAL_SECS").map_or(Ok(300), |v| v.parse())?;
+    let lookback: i64 = std::env::var("CRANK_LOOKBACK_SECS").map_or(Ok(30 * 86_400), |v| v.parse())?;
+
+    loop {
+        if let Err(e) = run_once(&db, ctx.clone(), lookback).await {
+            tracing::error!("payout crank pass failed: {e:#}");
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn run_once(db: &db::Db, ctx: Arc<anchor_client_::AnchorCtx>, lookback: i64) -> Result<()> {
+    let c = ctx.clone();
+    let cfg = tokio::task::spawn_blocking(move || anchor_client_::get_config_account(&c)).await??;
+
+    let rows = position_repo::fetch_crankable_positions(db.pool(), cfg.crank_delay_secs, lookback, 500).await?;
+
+    // One vault per (market, outcome)
+    let mut by_vault: BTreeMap<(String, Option<i16>), Vec<String>> = BTreeMap::new();
+    for r in rows {
+        by_vault.entry((r.market_pda, r.outcome_idx)).or_default().push(r.user_pubkey);
+    }
+
+    for ((market_str, outcome_idx), owners) in by_vault {
+        let market_pda = Pubkey::from_str(&market_str)?;
+        let outcome_idx = outcome_idx.map(|i| i as u8);
+
+        // Split-winner multi markets list every outcome as 255 in the DB; only pay the mask
+        let c = ctx.clone();
+        let market = tokio::task::spawn_blocking(move || anchor_client_::get_market_account(&c, market_pda)).await??;
+        if let Some(idx) = outcome_idx {
+            let is_single = market.winners_mask == 0 && market.outcome_idx != u8::MAX;
+            let winner = if is_single { market.outcome_idx == idx } else { market.winners_mask & (1 << idx) != 0 };
+            if !winner {
+                continue;
+            }
+        }
+
+        for batch in owners.chunks(anchor_client_::MAX_CRANK_BATCH) {
+            let owner_pks = batch.iter().map(|s| Pubkey::from_str(s)).collect::<Result<Vec<_>, _>>()?;
+            let c = ctx.clone();
+            let res = tokio::task::spawn_blocking(move || {
+                anchor_client_::crank_payout(&c, market_pda, outcome_idx, &owner_pks)
+            })
+            .await?;
+
+            match res {
+                Ok(sig) => {
+                    let sig = sig.to_string();
+                    for owner in batch {
+                        bets_repo::mark_claimed_in_batch(db.pool(), &market_str, owner, outcome_idx.map(i16::from), &sig).await?;
+                    }
+                    tracing::info!(market = %market_str, paid = batch.len(), %sig, "payout crank batch sent");
+                }
+                Err(e) => tracing::warn!(market = %market_str, "payout crank batch failed: {e:#}"),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+use anyhow::Result;
+use time::OffsetDateTime;
+
+use solpredict::{
+    config, db,
+    repo::{market as market_repo, series as series_repo},
+    solana as anchor_client_, telemetry,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+/// Opens the next round of every registered market series once the current one has ended,
+/// and indexes each round's market under its series.
+/// Env: SERIES_INTERVAL_SECS (default 30).
+#[tokio::main]
+async fn main() -> Result<()> {
+    telemetry::init();
+
+    let settings = config::load()?;
+    let db = db::init_pool(&settings.database.url).await?;
+    let ctx = Arc::new(anchor_client_::connect_devnet()?);
+
+    let interval: u64 = std::env::var("SERIES_INTERVAL_SECS").map_or(Ok(30), |v| v.parse())?;
+
+    loop {
+        if let Err(e) = run_once(&db, ctx.clone()).await {
+            tracing::error!("series roller pass failed: {e:#}");
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn run_once(db: &db::Db, ctx: Arc<anchor_client_::AnchorCtx>) -> Result<()> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+
+    for s in series_repo::fetch_series(db.pool()).await? {
+        let series_pda = Pubkey::from_str(&s.series_pda)?;
+
+        // Missing until the create tx confirms
+        let c = ctx.clone();
+        let Ok(mut series) = tokio::task::spawn_blocking(move || anchor_client_::get_series_account(&c, series_pda)).await? else {
+            continue;
+        };
+
+        let mut roll_sig = String::new();
+        if series.active && now >= series.current_end_ts {
+            let price_update = Pubkey::from_str(&s.price_feed_account)?;
+            let c = ctx.clone();
+            let res = tokio::task::spawn_blocking(move || {
+                anchor_client_::roll_series(&c, series_pda, price_update)
+            })
+            .await?;
+            match res {
+                Ok((sig, market)) => {
+                    tracing::info!(series = %s.series_pda, %market, %sig, "series rolled");
+                    roll_sig = sig.to_string();
+                }
+                // Someone else may have rolled first; index whatever round is current
+                Err(e) => tracing::warn!(series = %s.series_pda, "roll_series failed: {e:#}"),
+            }
+            let c = ctx.clone();
+            series = tokio::task::spawn_blocking(move || anchor_client_::get_series_account(&c, series_pda)).await??;
+        }
+
+        if series.current_market == Pubkey::default() {
+            continue;
+        }
+        let market_str = series.current_market.to_string();
+        if market_repo::find_by_address(db.pool(), &market_str).await?.is_some() {
+            continue;
+        }
+
+        let c = ctx.clone();
+        let market_pda = series.current_market;
+        let market = tokio::task::spawn_blocking(move || anchor_client_::get_market_account(&c, market_pda)).await??;
+        let market_id = series_repo::insert_series_market(
+            db.pool(),
+            &s,
+            &market_str,
+            series.next_round.saturating_sub(1) as i64,
+            market.bound_lo,
+            OffsetDateTime::from_unix_timestamp(market.end_ts)?,
+            &roll_sig,
+        )
+        .await?;
+        market_repo::upsert_initial_state(db.pool(), market_id, 0, 0, 0).await?;
+        tracing::info!(series = %s.series_pda, market = %market_str, round = series.next_round - 1, "series round indexed");
+    }
+
+    Ok(())
+}
@@ -18,6 +18,9 @@ pub enum AppError {
     #[error("unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("too many requests: {0}")]
+    TooManyRequests(String),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -31,6 +34,10 @@ impl AppError {
     pub fn unauthorized(msg: impl Into<String>) -> Self {
         AppError::Unauthorized(msg.into())
     }
+
+    pub fn too_many_requests(msg: impl Into<String>) -> Self {
+        AppError::TooManyRequests(msg.into())
+    }
 }
 
 // Auto-convert validator errors into 400 BadRequest
@@ -53,6 +60,9 @@ impl IntoResponse for AppError {
             AppError::Unauthorized(msg) =>
                 (StatusCode::UNAUTHORIZED, Json(ErrorBody { error: msg, code: 401 })).into_response(),
 
+            AppError::TooManyRequests(msg) =>
+                (StatusCode::TOO_MANY_REQUESTS, Json(ErrorBody { error: msg, code: 429 })).into_response(),
+
             AppError::Other(e) => {
                 tracing::error!(?e, "internal error");
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorBody {
@@ -0,0 +1,78 @@
+use serde::Serialize;
+use std::str::FromStr;
+
+use axum::{
+    extract::{Extension, State},
+    Json,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::AppError, middleware::auth::CurrentUser,
+    solana as anchor_client_, state::SharedState
+};
+
+// Test USDC minted by the faucet
+const FAUCET_DECIMALS: i32 = 6;
+
+#[derive(Debug, Serialize)]
+pub struct FaucetClaimResponse {
+    pub ok: bool,
+    pub tx: String,
+    pub amount_ui: f64,
+    pub total_ui: f64,       // everything this wallet has drawn so far
+    pub next_claim_at: i64,  // unix seconds
+}
+
+// ====== POST /v1/faucet/claim ======
+
+// Endpoint: refill the caller's wallet with test USDC (devnet only)
+pub async fn claim(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<Json<FaucetClaimResponse>, AppError> {
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+
+    // Check the cooldown up front so callers get a 429 instead of a failed simulation
+    let ctx = state.anchor.clone();
+    let (faucet, claim) = tokio::task::spawn_blocking(move || {
+        let faucet = anchor_client_::get_faucet_account(&ctx);
+        let claim = anchor_client_::get_airdrop_claim_account(&ctx, user_pk).ok();
+        (faucet, claim)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?;
+
+    let faucet = faucet.map_err(|_| AppError::bad_request("faucet is not initialized"))?;
+    if faucet.amount == 0 {
+        return Err(AppError::bad_request("faucet is disabled"));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let prev_total = claim.as_ref().map(|c| c.total).unwrap_or(0);
+    if let Some(c) = claim.filter(|c| c.claimed) {
+        let next = c.when.saturating_add(faucet.cooldown_secs);
+        if now < next {
+            return Err(AppError::too_many_requests(format!(
+                "faucet cooldown: next claim in {}s",
+                next - now
+            )));
+        }
+    }
+
+    let ctx = state.anchor.clone();
+    let sig = tokio::task::spawn_blocking(move || anchor_client_::airdrop_usdc(&ctx, user_pk))
+        .await
+        .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+        .map_err(AppError::Other)?;
+
+    let scale = 10f64.powi(FAUCET_DECIMALS);
+    Ok(Json(FaucetClaimResponse {
+        ok: true,
+        tx: sig.to_string(),
+        amount_ui: faucet.amount as f64 / scale,
+        total_ui: prev_total.saturating_add(faucet.amount) as f64 / scale,
+        next_claim_at: now.saturating_add(faucet.cooldown_secs),
+    }))
+}
@@ -9,7 +9,8 @@ use time::format_description::well_known::Rfc3339;
 
 use crate::{error::AppError, state::SharedState};
 use crate::usecases::ai_jobs::{AiJobValue, ProposalWithId, get_job};
-use crate::handlers::market::types::current_user_pubkey;
+use crate::usecases::market_spec::MarketSpecV1;
+use crate::handlers::market::types::{current_user_pubkey, stake_limits_from_ui};
 
 use crate::solana as anchor_client_;
 use anchor_client::solana_sdk::pubkey::Pubkey;
@@ -18,6 +19,13 @@ use anchor_client::solana_sdk::pubkey::Pubkey;
 pub struct AiValidateSelectReq {
     pub hash: String,
     pub id: String,
+    // Optional stake limits in collateral UI units
+    #[serde(default)]
+    pub min_bet: Option<f64>,
+    #[serde(default)]
+    pub max_bet: Option<f64>,
+    #[serde(default)]
+    pub max_per_wallet: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -64,6 +72,34 @@ pub async fn post_ai_validate_select(
     let ai_oracle_pubkey: Pubkey = state.ai_oracle_pubkey
         .parse()
         .map_err(|_| AppError::Other(anyhow::anyhow!("AI_ORACLE_PUBKEY is invalid base58")))?;
+    let oracle_committee: Option<Pubkey> = state.ai_oracle_committee
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| AppError::Other(anyhow::anyhow!("AI_ORACLE_COMMITTEE is invalid base58")))?;
+    let mint: Pubkey = state.usdc_mint
+        .parse()
+        .map_err(|_| AppError::Other(anyhow::anyhow!("USDC_MINT is invalid base58")))?;
+
+    let (config, mint_info) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || -> anyhow::Result<_> {
+            Ok((anchor_client_::get_config_account(&ctx)?, anchor_client_::get_mint_info(&ctx, &mint)?))
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+    let limits = stake_limits_from_ui(
+        req.min_bet,
+        req.max_bet,
+        req.max_per_wallet,
+        mint_info.decimals,
+        &config,
+    )?;
+
+    // The oracle signs over this hash, tying resolution to the exact question shown here
+    let question_hash = MarketSpecV1::from_proposal(&p, end_ts).hash();
+    let spec_base_url = std::env::var("MARKET_SPEC_BASE_URL").ok();
 
 	let memo_json = format!(r#"{{"k":"ai1","h":"{}","p":"{}"}}"#, req.hash, req.id);
     let ctx = state.anchor.clone();
@@ -71,8 +107,13 @@ pub async fn post_ai_validate_select(
         anchor_client_::build_create_market_ai_binary_unsigned(
             &ctx,
             user_pubkey,
+            mint,
             end_ts,
             ai_oracle_pubkey,
+            oracle_committee,
+            limits,
+            question_hash,
+            spec_base_url.as_deref(),
 			Some(&memo_json),
         )
     })
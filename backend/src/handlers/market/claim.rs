@@ -8,22 +8,41 @@ use anyhow::anyhow;
 
 use crate::{
 	error::AppError, middleware::auth::CurrentUser, 
-	repo::market as market_repo, solana as anchor_client_, 
+	repo::{market as market_repo, position as position_repo}, solana as anchor_client_, 
 	state::SharedState
 };
 
+/// Upper bound on positions packed per request; the rest come back on the next call
+const MAX_BATCH_CLAIMS: i64 = 60;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct PrepareClaimRequest {
     #[validate(length(min = 32, max = 64))]
     pub market_pda: String,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct PrepareRefundMultiRequest {
+    #[validate(length(min = 32, max = 64))]
+    pub market_pda: String,
+    pub outcome_idx: u8,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PrepareClaimResponse {
     pub ok: bool,
     pub tx_base64: String, // unsigned Transaction
 }
 
+#[derive(Debug, Serialize)]
+pub struct PrepareClaimManyResponse {
+    pub ok: bool,
+    pub txs_base64: Vec<String>, // unsigned txs, legacy or v0
+    pub lookup_table: Option<String>,
+    pub markets: Vec<String>,
+    pub skipped: Vec<String>, // need a single `claim`, e.g. void with stakes on both sides
+}
+
 // ====== POST /v1/markets/claim/tx ======
 
 pub async fn prepare_claim_tx(
@@ -87,6 +106,11 @@ pub async fn prepare_claim_tx(
                 return Err(AppError::bad_request("payout pool is zero"));
             }
         }
+        4 => {
+            if on_pos.yes_bet == 0 && on_pos.no_bet == 0 {
+                return Err(AppError::bad_request("no LONG or SHORT stake to claim"));
+            }
+        }
         _ => return Err(AppError::bad_request("winning side is undefined")),
     }
 
@@ -95,3 +119,106 @@ pub async fn prepare_claim_tx(
         tx_base64: tx_b64,
     }))
 }
+
+// ====== POST /v1/markets/ai/refund/tx ======
+
+pub async fn prepare_refund_multi_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareRefundMultiRequest>,
+) -> Result<Json<PrepareClaimResponse>, AppError> {
+    req.validate()?;
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let ctx = state.anchor.clone();
+    let outcome_idx = req.outcome_idx;
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_refund_multi_unsigned(&ctx, user_pk, market_pk, outcome_idx)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(PrepareClaimResponse {
+        ok: true,
+        tx_base64: tx_b64,
+    }))
+}
+
+// ====== POST /v1/markets/referral/claim/tx ======
+
+pub async fn prepare_claim_referral_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareClaimRequest>,
+) -> Result<Json<PrepareClaimResponse>, AppError> {
+    req.validate()?;
+    let referrer_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_claim_referral_unsigned(&ctx, referrer_pk, market_pk)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(PrepareClaimResponse {
+        ok: true,
+        tx_base64: tx_b64,
+    }))
+}
+
+// ====== POST /v1/markets/claim/many/tx ======
+
+pub async fn prepare_claim_many_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<Json<PrepareClaimManyResponse>, AppError> {
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+
+    let rows = position_repo::fetch_claimable_positions(state.db.pool(), &user.wallet, MAX_BATCH_CLAIMS)
+        .await
+        .map_err(AppError::Other)?;
+    if rows.is_empty() {
+        return Err(AppError::bad_request("nothing to claim"));
+    }
+
+    let candidates = rows
+        .iter()
+        .map(|r| {
+            Ok(anchor_client_::ClaimCandidate {
+                market: Pubkey::from_str(&r.market_pda)?,
+                outcome_idx: r.outcome_idx.map(|i| i as u8),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(AppError::Other)?;
+
+    let ctx = state.anchor.clone();
+    let bundle = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_claim_many_unsigned(&ctx, user_pk, &candidates)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::Other(anyhow!(e)))?;
+
+    if bundle.txs_base64.is_empty() {
+        return Err(AppError::bad_request("nothing to claim"));
+    }
+
+    Ok(Json(PrepareClaimManyResponse {
+        ok: true,
+        txs_base64: bundle.txs_base64,
+        lookup_table: bundle.lookup_table.map(|k| k.to_string()),
+        markets: bundle.claimed.iter().map(|k| k.to_string()).collect(),
+        skipped: bundle.skipped.iter().map(|k| k.to_string()).collect(),
+    }))
+}
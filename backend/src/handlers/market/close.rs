@@ -0,0 +1,82 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use axum::extract::{Extension, Json, State};
+use serde::Deserialize;
+use validator::Validate;
+use std::str::FromStr;
+use anyhow::anyhow;
+
+use crate::{
+    error::AppError, middleware::auth::CurrentUser,
+    handlers::market::claim::PrepareClaimResponse,
+    solana as anchor_client_, state::SharedState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PrepareClosePositionRequest {
+    #[validate(length(min = 32, max = 64))]
+    pub market_pda: String,
+    // multi-outcome markets only
+    #[serde(default)]
+    pub outcome_idx: u8,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PrepareCloseMarketRequest {
+    #[validate(length(min = 32, max = 64))]
+    pub market_pda: String,
+}
+
+// ====== POST /v1/markets/positions/close/tx ======
+
+pub async fn prepare_close_position_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareClosePositionRequest>,
+) -> Result<Json<PrepareClaimResponse>, AppError> {
+    req.validate()?;
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let ctx = state.anchor.clone();
+    let outcome_idx = req.outcome_idx;
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_close_position_unsigned(&ctx, user_pk, market_pk, outcome_idx)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(PrepareClaimResponse {
+        ok: true,
+        tx_base64: tx_b64,
+    }))
+}
+
+// ====== POST /v1/markets/close/tx ======
+
+pub async fn prepare_close_market_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareCloseMarketRequest>,
+) -> Result<Json<PrepareClaimResponse>, AppError> {
+    req.validate()?;
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_close_market_unsigned(&ctx, user_pk, market_pk)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(PrepareClaimResponse {
+        ok: true,
+        tx_base64: tx_b64,
+    }))
+}
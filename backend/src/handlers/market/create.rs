@@ -4,10 +4,13 @@ use time::{Duration, OffsetDateTime};
 use serde::{Serialize};
 use validator::Validate;
 use anyhow::anyhow;
+use std::str::FromStr;
 use crate::{
-    handlers::market::types::{Comparator, CreateMarketRequest, MarketType, 
-        SeedSide, current_user_pubkey, cat_str, cmp_str, feed_id_hex_to_bytes32,
-        usd_to_1e6},
+    handlers::market::types::{Comparator, CreateLadderRequest, CreateMarketRequest, CreateSeriesRequest,
+        MarketType, PricingKind, SeedSide, StrikeRule, current_user_pubkey, cat_str, cmp_str,
+        feed_id_hex_to_bytes32, check_stake_limits, resolve_price_feed_account_from_hex,
+        stake_limits_from_ui, usd_to_1e6},
+    repo::series as series_repo,
     solana as anchor_client_,
     state::SharedState,
     error::AppError,
@@ -61,6 +64,8 @@ pub async fn create_market(
     let market_type_onchain = match req.market_type {
         MarketType::PriceThreshold => onchain::types::MarketType::PriceThreshold,
         MarketType::PriceRange => onchain::types::MarketType::PriceRange,
+        MarketType::PriceBuckets => onchain::types::MarketType::PriceBuckets,
+        MarketType::PriceScalar => onchain::types::MarketType::PriceScalar,
     };
     let comparator_u8 = map_comparator(req.comparator);
 
@@ -71,7 +76,7 @@ pub async fn create_market(
                 .ok_or_else(|| AppError::bad_request("threshold is required"))?;
             (usd_to_1e6(t), 0)
         }
-        MarketType::PriceRange => {
+        MarketType::PriceRange | MarketType::PriceScalar => {
             let lo = req
                 .lower_bound
                 .ok_or_else(|| AppError::bad_request("lowerBound is required"))?;
@@ -80,20 +85,80 @@ pub async fn create_market(
                 .ok_or_else(|| AppError::bad_request("upperBound is required"))?;
             (usd_to_1e6(lo), usd_to_1e6(hi))
         }
+        MarketType::PriceBuckets => (0, 0),
     };
+    let bucket_bounds_usd_6: Vec<i64> = req
+        .buckets
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|&x| usd_to_1e6(x))
+        .collect();
+    let bucket_bounds_str = req
+        .buckets
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
 
     // Resolve Pyth price account
     let feed_id_bytes = feed_id_hex_to_bytes32(&req.feed_id)
         .map_err(|e| AppError::bad_request(&format!("bad feedId hex: {e}")))?;
 
+    // Collateral mint must be on the config allowlist; its decimals scale the seed amount
+    let mint = Pubkey::from_str(req.collateral_mint.as_deref().unwrap_or(&state.usdc_mint))
+        .map_err(|_| AppError::bad_request("bad collateralMint"))?;
+    let (config, mint_info) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || -> anyhow::Result<_> {
+            Ok((anchor_client_::get_config_account(&ctx)?, anchor_client_::get_mint_info(&ctx, &mint)?))
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+    if !config.is_mint_allowed(&mint) {
+        return Err(AppError::bad_request("collateral mint is not allowed"));
+    }
+    let limits = stake_limits_from_ui(
+        req.min_bet,
+        req.max_bet,
+        req.max_per_wallet,
+        mint_info.decimals,
+        &config,
+    )?;
+
     // Build transaction (create + seed market)
     let ctx = state.anchor.clone();
     let end_ts = req.end_date.unix_timestamp();
-    let amount_tokens: u64 = (req.initial_liquidity * 1_000_000.0).round() as u64;
+    let amount_tokens: u64 = anchor_client_::ui_to_base_units(req.initial_liquidity, mint_info.decimals);
+    let rules = onchain::types::ResolutionRules {
+        max_conf_bps: req.max_conf_bps.unwrap_or(0),
+        void_on_wide_conf: req.void_on_wide_conf,
+        use_ema: req.use_ema,
+    };
+
+    // Scalar payouts split the pot between both sides, so there is no CPMM pool to seed
+    if matches!(req.market_type, MarketType::PriceScalar) && req.pricing == PricingKind::Cpmm {
+        return Err(AppError::bad_request("scalar markets are parimutuel only"));
+    }
+
+    // Two-sided seeds become LP positions, which only parimutuel yes/no markets take
+    let two_sided = matches!(req.initial_side, SeedSide::Both) && req.pricing != PricingKind::Cpmm;
+    if two_sided && !matches!(req.market_type, MarketType::PriceThreshold | MarketType::PriceRange) {
+        return Err(AppError::bad_request("two-sided liquidity needs a threshold or range market"));
+    }
+
+    // The seed bet goes through place_bet, so it has to fit the limits too
+    if amount_tokens > 0 && req.pricing != PricingKind::Cpmm && !two_sided {
+        check_stake_limits(&limits, amount_tokens, amount_tokens)?;
+    }
 
     let side_onchain = match req.initial_side {
-        SeedSide::Yes => onchain::types::Side::Yes,
-        SeedSide::No => onchain::types::Side::No,
+        SeedSide::Yes => Some(onchain::types::Side::Yes),
+        SeedSide::No => Some(onchain::types::Side::No),
+        SeedSide::Both => None,
     };
 
     let memo_str = serde_urlencoded::to_string([
@@ -104,12 +169,24 @@ pub async fn create_market(
         ("eD", &req.end_date.unix_timestamp().to_string()),
         ("f",  &req.feed_id),
         ("iL", &req.initial_liquidity.to_string()),
-        ("iS", if matches!(req.initial_side, SeedSide::Yes) { "yes" } else { "no" }),
-        ("mt", match req.market_type { MarketType::PriceThreshold => "threshold", MarketType::PriceRange => "range" }),
+        ("iS", match req.initial_side {
+            SeedSide::Yes => "yes",
+            SeedSide::No => "no",
+            SeedSide::Both => "both",
+        }),
+        ("pk", if req.pricing == PricingKind::Cpmm { "cpmm" } else { "pari" }),
+        ("mt", match req.market_type {
+            MarketType::PriceThreshold => "threshold",
+            MarketType::PriceRange => "range",
+            MarketType::PriceBuckets => "buckets",
+            MarketType::PriceScalar => "scalar",
+        }),
         ("s",  &req.symbol),
         ("lB", &req.lower_bound.map_or(String::new(), |x| x.to_string())),
         ("uB", &req.upper_bound.map_or(String::new(), |x| x.to_string())),
         ("th", &req.threshold.map_or(String::new(), |x| x.to_string())),
+        ("bk", &bucket_bounds_str),
+        ("iB", &req.initial_bucket.unwrap_or(0).to_string()),
     ]).map_err(|e| AppError::Other(anyhow!(e)))?;
 
     tracing::info!("Create market memo: {}", memo_str.len());
@@ -123,6 +200,44 @@ pub async fn create_market(
         .await
         .map_err(|e| AppError::Other(anyhow!("Failed to get blockhash: {e}")))?;
 
+    // Bucket markets use a salted PDA, so the builder hands it back
+    if matches!(req.market_type, MarketType::PriceBuckets) {
+        let seed_bucket = req.initial_bucket.unwrap_or(0);
+        let (create_tx_b64, market_pda) = tokio::task::spawn_blocking({
+            let ctx = ctx.clone();
+            let memo_owned = memo_str.clone();
+            move || {
+                anchor_client_::build_create_buckets_and_seed(
+                    &ctx,
+                    user_pubkey,
+                    mint,
+                    feed_id_bytes,
+                    end_ts,
+                    bucket_bounds_usd_6,
+                    limits,
+                    rules,
+                    seed_bucket,
+                    amount_tokens,
+                    Some(memo_owned.as_bytes()),
+                    recent_blockhash,
+                )
+            }
+        })
+        .await
+        .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+
+        return Ok((
+            StatusCode::CREATED,
+            Json(CreateMarketResponse {
+                ok: true,
+                market_id: market_pda.to_string(),
+                create_tx: create_tx_b64,
+                place_bet_tx: None,
+                message: "Bucket market created; optional initial liquidity included".into(),
+            }),
+        ));
+    }
+
     let create_tx_b64 = tokio::task::spawn_blocking({
         let ctx = ctx.clone();
         let memo_owned = memo_str.clone();
@@ -130,14 +245,18 @@ pub async fn create_market(
             anchor_client_::build_create_and_seed(
                 &ctx,
                 user_pubkey,
+                mint,
                 feed_id_bytes,
                 market_type_onchain,
                 comparator_u8,
                 bound_lo_usd_6,
                 bound_hi_usd_6,
                 end_ts,
+                limits,
+                rules,
                 side_onchain,
                 amount_tokens,
+                req.pricing == PricingKind::Cpmm,
                 Some(memo_owned.as_bytes()),
                 recent_blockhash,
             )
@@ -168,4 +287,213 @@ pub async fn create_market(
             message: "Market created; optional initial liquidity included".into(),
         }),
     ))
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLadderResponse {
+    pub ok: bool,
+    pub market_ids: Vec<String>,
+    pub create_tx: String,
+    pub message: String,
+}
+
+// ====== POST /v1/markets/ladder ======
+
+pub async fn create_ladder(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<CreateLadderRequest>,
+) -> Result<(StatusCode, Json<CreateLadderResponse>), AppError> {
+    let user_pubkey = current_user_pubkey(&headers, &state.jwt_secret)?;
+
+    req.validate()?;
+    if req.end_date <= OffsetDateTime::now_utc() + Duration::seconds(1) {
+        return Err(AppError::bad_request(
+            "End date must be at least 10 minutes in the future",
+        ));
+    }
+
+    let feed_id_bytes = feed_id_hex_to_bytes32(&req.feed_id)
+        .map_err(|e| AppError::bad_request(&format!("bad feedId hex: {e}")))?;
+    let strikes_usd_6: Vec<i64> = req.strikes.iter().map(|&x| usd_to_1e6(x)).collect();
+
+    let mint = Pubkey::from_str(req.collateral_mint.as_deref().unwrap_or(&state.usdc_mint))
+        .map_err(|_| AppError::bad_request("bad collateralMint"))?;
+    let (config, mint_info) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || -> anyhow::Result<_> {
+            Ok((anchor_client_::get_config_account(&ctx)?, anchor_client_::get_mint_info(&ctx, &mint)?))
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+    if !config.is_mint_allowed(&mint) {
+        return Err(AppError::bad_request("collateral mint is not allowed"));
+    }
+    let limits = stake_limits_from_ui(
+        req.min_bet,
+        req.max_bet,
+        req.max_per_wallet,
+        mint_info.decimals,
+        &config,
+    )?;
+    let rules = onchain::types::ResolutionRules {
+        max_conf_bps: req.max_conf_bps.unwrap_or(0),
+        void_on_wide_conf: req.void_on_wide_conf,
+        use_ema: req.use_ema,
+    };
+
+    // Feed, strikes and end date are read back from the instruction data by the webhook
+    let memo_str = serde_urlencoded::to_string([
+        ("v", "1"),
+        ("t", "create_ladder"),
+        ("ca", cat_str(req.category)),
+        ("s", &req.symbol),
+    ]).map_err(|e| AppError::Other(anyhow!(e)))?;
+
+    let recent_blockhash = state
+        .rpc
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| AppError::Other(anyhow!("Failed to get blockhash: {e}")))?;
+
+    let comparator_u8 = map_comparator(req.comparator);
+    let end_ts = req.end_date.unix_timestamp();
+    let (create_tx_b64, market_pdas) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || {
+            anchor_client_::build_create_ladder_unsigned(
+                &ctx,
+                user_pubkey,
+                mint,
+                feed_id_bytes,
+                comparator_u8,
+                end_ts,
+                strikes_usd_6,
+                limits,
+                rules,
+                Some(memo_str.as_bytes()),
+                recent_blockhash,
+            )
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateLadderResponse {
+            ok: true,
+            market_ids: market_pdas.iter().map(|p| p.to_string()).collect(),
+            create_tx: create_tx_b64,
+            message: "Ladder created; markets are indexed once the transaction confirms".into(),
+        }),
+    ))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSeriesResponse {
+    pub ok: bool,
+    pub series_id: String,
+    pub create_tx: String,
+    pub message: String,
+}
+
+// ====== POST /v1/markets/series ======
+
+pub async fn create_series(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<CreateSeriesRequest>,
+) -> Result<(StatusCode, Json<CreateSeriesResponse>), AppError> {
+    let user_pubkey = current_user_pubkey(&headers, &state.jwt_secret)?;
+
+    req.validate()?;
+
+    let feed_id_bytes = feed_id_hex_to_bytes32(&req.feed_id)
+        .map_err(|e| AppError::bad_request(&format!("bad feedId hex: {e}")))?;
+    let price_feed_account = resolve_price_feed_account_from_hex(&req.feed_id)
+        .map_err(|e| AppError::bad_request(&format!("Cannot resolve price account: {e}")))?;
+
+    let mint = Pubkey::from_str(req.collateral_mint.as_deref().unwrap_or(&state.usdc_mint))
+        .map_err(|_| AppError::bad_request("bad collateralMint"))?;
+    let (config, mint_info) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || -> anyhow::Result<_> {
+            Ok((anchor_client_::get_config_account(&ctx)?, anchor_client_::get_mint_info(&ctx, &mint)?))
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+    if !config.is_mint_allowed(&mint) {
+        return Err(AppError::bad_request("collateral mint is not allowed"));
+    }
+    let limits = stake_limits_from_ui(
+        req.min_bet,
+        req.max_bet,
+        req.max_per_wallet,
+        mint_info.decimals,
+        &config,
+    )?;
+
+    let p = onchain::instructions::series::CreateSeriesParams {
+        id: rand::random::<u64>(),
+        feed_id: feed_id_bytes,
+        comparator: map_comparator(req.comparator),
+        cadence_secs: req.cadence_secs,
+        strike_rule: match req.strike_rule {
+            StrikeRule::Nearest => onchain::constants::STRIKE_NEAREST,
+            StrikeRule::Floor => onchain::constants::STRIKE_FLOOR,
+            StrikeRule::Ceil => onchain::constants::STRIKE_CEIL,
+        },
+        strike_step: usd_to_1e6(req.strike_step),
+        limits,
+        rules: onchain::types::ResolutionRules {
+            max_conf_bps: req.max_conf_bps.unwrap_or(0),
+            void_on_wide_conf: req.void_on_wide_conf,
+            use_ema: req.use_ema,
+        },
+    };
+
+    let recent_blockhash = state
+        .rpc
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| AppError::Other(anyhow!("Failed to get blockhash: {e}")))?;
+
+    let (create_tx_b64, series_pda) = tokio::task::spawn_blocking({
+        let ctx = state.anchor.clone();
+        move || anchor_client_::build_create_series_unsigned(&ctx, user_pubkey, mint, p, None, recent_blockhash)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("Join error: {e}")))??;
+
+    // The roller skips series whose create tx never confirmed
+    series_repo::insert_series(
+        state.db.pool(),
+        &series_repo::SeriesRow {
+            series_pda: series_pda.to_string(),
+            authority_pubkey: user_pubkey.to_string(),
+            symbol: req.symbol.clone(),
+            feed_id: req.feed_id.clone(),
+            price_feed_account: price_feed_account.to_string(),
+            mint: mint.to_string(),
+            comparator: cmp_str(req.comparator).to_string(),
+            cadence_secs: req.cadence_secs,
+        },
+    )
+    .await
+    .map_err(AppError::Other)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateSeriesResponse {
+            ok: true,
+            series_id: series_pda.to_string(),
+            create_tx: create_tx_b64,
+            message: "Series created; rounds are opened by the roller once the transaction confirms".into(),
+        }),
+    ))
+}
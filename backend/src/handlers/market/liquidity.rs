@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use std::str::FromStr;
+
+use axum::{
+    extract::{Extension, State},
+    Json,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::AppError, middleware::auth::CurrentUser,
+    solana as anchor_client_, state::SharedState
+};
+use prediction_market_program as onchain;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PrepareLiquidityRequest {
+    #[validate(length(min = 32, max = 64))]
+    pub market_pda: String,
+    // add: deposit split across both sides; remove: LP shares to burn (1 share = 1 unit deposited)
+    #[serde(default)]
+    pub amount_ui: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrepareLiquidityResponse {
+    pub ok: bool,
+    pub tx_base64: String, // unsigned Transaction
+}
+
+async fn load_market(
+    state: &SharedState,
+    user: &CurrentUser,
+    market_pda: &str,
+) -> Result<(Pubkey, Pubkey, onchain::state::market::Market), AppError> {
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let market = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
+        .await
+        .map_err(AppError::Other)?;
+    Ok((user_pk, market_pk, market))
+}
+
+fn amount_base(req: &PrepareLiquidityRequest, decimals: u8) -> Result<u64, AppError> {
+    match req.amount_ui {
+        Some(x) if x.is_finite() && x > 0.0 => Ok(anchor_client_::ui_to_base_units(x, decimals)),
+        _ => Err(AppError::bad_request("amount_ui must be > 0")),
+    }
+}
+
+fn check_open(market: &onchain::state::market::Market) -> Result<(), AppError> {
+    if !market.accepts_liquidity() {
+        return Err(AppError::bad_request("liquidity is only supported on parimutuel yes/no price markets"));
+    }
+    if market.settled {
+        return Err(AppError::bad_request("market already settled"));
+    }
+    if chrono::Utc::now().timestamp() >= market.exit_lock_ts {
+        return Err(AppError::bad_request("liquidity is locked for this market"));
+    }
+    Ok(())
+}
+
+
+// ====== POST /v1/markets/liquidity/add/tx ======
+
+pub async fn prepare_add_liquidity_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareLiquidityRequest>,
+) -> Result<Json<PrepareLiquidityResponse>, AppError> {
+    req.validate()?;
+    let (user_pk, market_pk, market) = load_market(&state, &user, &req.market_pda).await?;
+    check_open(&market)?;
+
+    let amount = amount_base(&req, market.mint_decimals)?;
+    if amount < 2 {
+        return Err(AppError::bad_request("amount is too small to split across both sides"));
+    }
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_add_liquidity_unsigned(&ctx, user_pk, market_pk, amount)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
+
+    Ok(Json(PrepareLiquidityResponse { ok: true, tx_base64: tx_b64 }))
+}
+
+
+// ====== POST /v1/markets/liquidity/remove/tx ======
+
+pub async fn prepare_remove_liquidity_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareLiquidityRequest>,
+) -> Result<Json<PrepareLiquidityResponse>, AppError> {
+    req.validate()?;
+    let (user_pk, market_pk, market) = load_market(&state, &user, &req.market_pda).await?;
+    check_open(&market)?;
+
+    let ctx = state.anchor.clone();
+    let lp = tokio::task::spawn_blocking(move || {
+        anchor_client_::get_lp_position_account(&ctx, market_pk, user_pk)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+    .map_err(|_| AppError::bad_request("no liquidity position in this market"))?;
+
+    // Omitted amount withdraws everything
+    let shares = match req.amount_ui {
+        Some(_) => amount_base(&req, market.mint_decimals)?,
+        None => lp.shares,
+    };
+    if shares == 0 || shares > lp.shares {
+        return Err(AppError::bad_request(format!("shares must be between 1 and {}", lp.shares)));
+    }
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_remove_liquidity_unsigned(&ctx, user_pk, market_pk, shares)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
+
+    Ok(Json(PrepareLiquidityResponse { ok: true, tx_base64: tx_b64 }))
+}
+
+
+// ====== POST /v1/markets/liquidity/claim/tx ======
+
+pub async fn prepare_claim_lp_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PrepareLiquidityRequest>,
+) -> Result<Json<PrepareLiquidityResponse>, AppError> {
+    req.validate()?;
+    let (user_pk, market_pk, market) = load_market(&state, &user, &req.market_pda).await?;
+    if !market.settled {
+        return Err(AppError::bad_request("market is not settled yet"));
+    }
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_claim_lp_unsigned(&ctx, user_pk, market_pk)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
+
+    Ok(Json(PrepareLiquidityResponse { ok: true, tx_base64: tx_b64 }))
+}
@@ -6,8 +6,10 @@ use uuid::Uuid;
 use crate::{
     handlers::market::types::{generate_title, MarketDto, TitleSpec}, 
     repo::{bets as bets_repo, market as market_repo}, 
+    solana as anchor_client_,
     state::SharedState,
     error::AppError, 
+    usecases::market_spec::MarketSpecV1,
 };
 
 
@@ -37,6 +39,8 @@ pub struct MarketDtoV2 {
     no_price: f64,
     end_date: String,
     status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    series_pda: Option<String>,  // rounds of one series share this, for grouping
 }
 
 #[derive(Serialize)]
@@ -52,6 +56,7 @@ pub struct MarketsQuery {
     limit: Option<u32>,          // default 15
     cursor: Option<String>,      // cursor for keyset
     category: Option<String>,    // filter by category
+    series: Option<String>,      // filter by series PDA
     sort: Option<String>,        // sort
     status: Option<String>,
 }
@@ -104,6 +109,7 @@ pub async fn list(
         limit,
         q.cursor.as_deref(),
         q.category.as_deref(),
+        q.series.as_deref(),
         q.sort.as_deref(),
         Some(&wanted_refs),
     )
@@ -128,6 +134,7 @@ pub async fn list(
             no_price: 1.0 - (m.price_yes_bp.unwrap_or(0) as f64) / 10_000.0,
             end_date: m.end_date_utc.to_rfc3339(),
             status: m.status,
+            series_pda: m.series_pda,
         }
     }).collect();
 
@@ -150,6 +157,55 @@ pub async fn handle(
     Ok(Json(MarketDto::from(row)))
 }
 
+// ====== GET /v1/markets/{market_address}/spec ======
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketSpecDto {
+    spec: MarketSpecV1,
+    canonical_json: String, // exact bytes hashed into question_hash
+    question_hash: String,  // hex, as stored on-chain
+    matches_onchain: bool,
+}
+
+pub async fn spec(
+    State(state): State<SharedState>,
+    Path(market_address): Path<String>,
+) -> Result<Json<MarketSpecDto>, AppError> {
+    use std::str::FromStr;
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+
+    let market_pk = Pubkey::from_str(&market_address)
+        .map_err(|_| AppError::bad_request("bad market address"))?;
+    let Some(input) = market_repo::fetch_ai_resolver_input_by_pda(state.db.pool(), &market_address)
+        .await
+        .map_err(AppError::Other)?
+    else {
+        return Err(AppError::NotFound);
+    };
+
+    let market = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
+        .await
+        .map_err(AppError::Other)?;
+
+    // end_ts comes from the chain so the spec is rebuilt exactly as it was hashed at creation
+    let spec = MarketSpecV1::new(
+        &input.ai_topic,
+        &input.ai_description,
+        &input.ai_criteria_md,
+        &input.ai_accepted_sources,
+        market.end_ts,
+    );
+    let hash = spec.hash();
+
+    Ok(Json(MarketSpecDto {
+        canonical_json: spec.canonical_json(),
+        question_hash: hex::encode(market.question_hash),
+        matches_onchain: hash == market.question_hash,
+        spec,
+    }))
+}
+
 // ====== GET /v1/markets/bets ======
 
 #[derive(Serialize)]
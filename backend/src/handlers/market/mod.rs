@@ -8,22 +8,39 @@ pub mod types;
 pub mod claim;
 mod place_bet;
 mod resolve;
+mod close;
+mod liquidity;
 
 pub fn public_routes() -> Router<SharedState> {
     Router::new()
     .route("/markets", get(markets::list))
     .route("/markets/bets", get(markets::recent_bets))
     .route("/markets/{market_address}", get(markets::handle))
+    .route("/markets/{market_address}/spec", get(markets::spec))
 }
 
 pub fn protected_routes() -> Router<SharedState> {
     Router::new()
         .route("/markets", post(create::create_market))
+        .route("/markets/ladder", post(create::create_ladder))
+        .route("/markets/series", post(create::create_series))
         .route("/markets/bets/tx", post(place_bet::prepare_place_tx))
         .route("/markets/ai/bets/tx", post(place_bet::build_place_bet_multi_tx))
+        .route("/markets/bets/exit/tx", post(place_bet::prepare_exit_tx))
         .route("/markets/resolve/ix", post(resolve::build_resolve_ix))
         .route("/markets/resolve/multi/tx", post(resolve::prepare_multi_tx))
+        .route("/markets/ai/dispute/tx", post(resolve::prepare_dispute_tx))
+        .route("/markets/ai/dispute/expire/tx", post(resolve::prepare_expire_dispute_tx))
+        .route("/markets/ai/void/tx", post(resolve::prepare_void_tx))
         .route("/markets/claim/tx", post(claim::prepare_claim_tx))
+        .route("/markets/claim/many/tx", post(claim::prepare_claim_many_tx))
+        .route("/markets/ai/refund/tx", post(claim::prepare_refund_multi_tx))
+        .route("/markets/referral/claim/tx", post(claim::prepare_claim_referral_tx))
+        .route("/markets/liquidity/add/tx", post(liquidity::prepare_add_liquidity_tx))
+        .route("/markets/liquidity/remove/tx", post(liquidity::prepare_remove_liquidity_tx))
+        .route("/markets/liquidity/claim/tx", post(liquidity::prepare_claim_lp_tx))
+        .route("/markets/positions/close/tx", post(close::prepare_close_position_tx))
+        .route("/markets/close/tx", post(close::prepare_close_market_tx))
         // 
         .merge(ai::protected_routes())
 }
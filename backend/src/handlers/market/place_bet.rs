@@ -15,6 +15,8 @@ use anchor_client::solana_sdk::{
 
 use crate::{
     error::AppError, middleware::auth::CurrentUser,
+    handlers::market::types::check_stake_limits,
+    repo::users,
     solana as anchor_client_, state::SharedState
 };
 
@@ -32,11 +34,20 @@ fn side_yes_from_outcome_idx(idx: u8) -> Result<bool, AppError> {
     }
 }
 
-fn amount_ui_to_1e6(x: f64) -> Result<u64, AppError> {
+// Scale by the market's collateral decimals
+fn amount_ui_to_base(x: f64, decimals: u8) -> Result<u64, AppError> {
     if !x.is_finite() || x <= 0.0 {
         return Err(AppError::bad_request("amount_ui must be > 0"));
     }
-    Ok((x * 1_000_000.0).round() as u64)
+    Ok(anchor_client_::ui_to_base_units(x, decimals))
+}
+
+// Wallet that referred the bettor at signup; attached to bets on positions it opens
+async fn wallet_referrer(state: &SharedState, wallet: &str) -> Result<Option<Pubkey>, AppError> {
+    let referrer = users::fetch_referrer(state.db.pool(), wallet)
+        .await
+        .map_err(AppError::Other)?;
+    Ok(referrer.and_then(|r| Pubkey::from_str(&r).ok()))
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -46,6 +57,9 @@ pub struct PreparePlaceBetRequest {
     pub outcome_idx: u8,   // binary: 0=YES, 1=NO; multi: 0..num_outcomes-1
     #[validate(range(min = 0.000001))]
     pub amount_ui: f64,
+    // CPMM markets only: minimum shares to receive, in UI units
+    #[serde(default)]
+    pub min_shares_out_ui: Option<f64>,
 }
 
 
@@ -67,11 +81,11 @@ pub async fn prepare_place_tx(
         Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
     let market_pk =
         Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
-    let amount_1e6 = amount_ui_to_1e6(req.amount_ui)?;
 
     let market = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
         .await
         .map_err(AppError::Other)?;
+    let amount = amount_ui_to_base(req.amount_ui, market.mint_decimals)?;
 
     // Validate market is active and not settled
     let now_ts = chrono::Utc::now().timestamp();
@@ -89,11 +103,34 @@ pub async fn prepare_place_tx(
 
     let side_yes = side_yes_from_outcome_idx(req.outcome_idx)?; // 0=>YES, 1=>NO
 
+    // Only look the position up when there is a wallet cap to check against
+    let held = if market.max_per_wallet > 0 {
+        let ctx = state.anchor.clone();
+        tokio::task::spawn_blocking(move || anchor_client_::get_position_account(&ctx, market_pk, user_pk))
+            .await
+            .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+            .map(|p| p.yes_bet.saturating_add(p.no_bet))
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    check_stake_limits(&market.stake_limits(), amount, held.saturating_add(amount))?;
+
     tracing::info!("side_yes: {}", side_yes);
 
+    let min_shares_out = match req.min_shares_out_ui {
+        Some(x) if x.is_finite() && x >= 0.0 => {
+            (x * 10f64.powi(market.mint_decimals as i32)).floor() as u64
+        }
+        Some(_) => return Err(AppError::bad_request("min_shares_out_ui must be >= 0")),
+        None => 0,
+    };
+
+    let referrer = wallet_referrer(&state, &user.wallet).await?;
+
     let ctx = state.anchor.clone();
     let ixs = tokio::task::spawn_blocking(move || {
-        anchor_client_::build_place_bet_ixs(&ctx, user_pk, market_pk, side_yes, amount_1e6)
+        anchor_client_::build_place_bet_ixs(&ctx, user_pk, market_pk, side_yes, amount, min_shares_out, referrer)
     })
     .await
     .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
@@ -133,14 +170,11 @@ pub async fn build_place_bet_multi_tx(
 
     let user_pk  = Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
     let market_pk= Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
-    if !req.amount_ui.is_finite() || req.amount_ui <= 0.0 {
-        return Err(AppError::bad_request("amount_ui must be > 0"));
-    }
-    let amount_1e6 = (req.amount_ui * 1_000_000.0).round() as u64;
 
     let market = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
         .await
         .map_err(AppError::Other)?;
+    let amount = amount_ui_to_base(req.amount_ui, market.mint_decimals)?;
 
     if (req.outcome_idx as u32) >= (market.num_outcomes as u32) {
         return Err(AppError::bad_request(format!(
@@ -157,12 +191,30 @@ pub async fn build_place_bet_multi_tx(
         return Err(AppError::bad_request("market already settled"));
     }
 
+    // The wallet cap covers the stake on every outcome of the market
+    let held = if market.max_per_wallet > 0 {
+        let ctx = state.anchor.clone();
+        let num_outcomes = market.num_outcomes;
+        tokio::task::spawn_blocking(move || {
+            (0..num_outcomes)
+                .filter_map(|i| anchor_client_::get_position_multi_account(&ctx, market_pk, user_pk, i).ok())
+                .fold(0u64, |acc, p| acc.saturating_add(p.stake))
+        })
+        .await
+        .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+    } else {
+        0
+    };
+    check_stake_limits(&market.stake_limits(), amount, held.saturating_add(amount))?;
+
+    let referrer = wallet_referrer(&state, &user.wallet).await?;
+
     let mut ixs = tokio::task::spawn_blocking({
         let ctx = state.anchor.clone();
         let u   = user_pk;
         let m   = market_pk;
         let idx = req.outcome_idx;
-        move || anchor_client_::build_place_bet_multi_ixs(&ctx, u, m, idx, amount_1e6)
+        move || anchor_client_::build_place_bet_multi_ixs(&ctx, u, m, idx, amount, referrer)
     })
     .await
     .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
@@ -179,4 +231,54 @@ pub async fn build_place_bet_multi_tx(
 
     let tx_b64 = anchor_client_::encode_unsigned_tx(&tx).map_err(AppError::Other)?;
     Ok(Json(PreparePlaceBetResponse { ok: true, tx_base64: tx_b64 }))
-}
\ No newline at end of file
+}
+
+
+// ====== POST /v1/markets/bets/exit/tx ======
+
+pub async fn prepare_exit_tx(
+    State(state): State<SharedState>,
+    Extension(user): Extension<CurrentUser>,
+    Json(req): Json<PreparePlaceBetRequest>,
+) -> Result<Json<PreparePlaceBetResponse>, AppError> {
+    let user_pk =
+        Pubkey::from_str(&user.wallet).map_err(|_| AppError::bad_request("bad user wallet"))?;
+    let market_pk =
+        Pubkey::from_str(&req.market_pda).map_err(|_| AppError::bad_request("bad market pda"))?;
+
+    let market = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
+        .await
+        .map_err(AppError::Other)?;
+    let amount = amount_ui_to_base(req.amount_ui, market.mint_decimals)?;
+
+    let now_ts = chrono::Utc::now().timestamp();
+    if now_ts >= market.exit_lock_ts {
+        return Err(AppError::bad_request("exits are locked for this market"));
+    }
+    if market.settled {
+        return Err(AppError::bad_request("market already settled"));
+    }
+
+    let is_multi = market.uses_outcome_vaults();
+    if (req.outcome_idx as u32) >= (market.num_outcomes as u32) {
+        return Err(AppError::bad_request(format!(
+            "outcome_idx {} out of range (num_outcomes={})",
+            req.outcome_idx, market.num_outcomes
+        )));
+    }
+
+    let ctx = state.anchor.clone();
+    let idx = req.outcome_idx;
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        if is_multi {
+            anchor_client_::build_exit_position_multi_unsigned(&ctx, user_pk, market_pk, idx, amount)
+        } else {
+            let side_yes = idx == OUTCOME_YES;
+            anchor_client_::build_exit_position_unsigned(&ctx, user_pk, market_pk, side_yes, amount)
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))??;
+
+    Ok(Json(PreparePlaceBetResponse { ok: true, tx_base64: tx_b64 }))
+}
@@ -22,6 +22,11 @@ pub struct FinalizeMultiReq {
     pub market_pda: String,
 }
 
+#[derive(Deserialize)]
+pub struct DisputeReq {
+    pub market_pda: String,
+}
+
 #[derive(Serialize)]
 pub struct UnsignedTxResponse {
     pub market_pda: String,
@@ -71,4 +76,79 @@ pub async fn prepare_multi_tx(
         market_pda: req.market_pda,
         tx: tx_b64,
     }))
-}
\ No newline at end of file
+}
+
+// ====== POST /v1/markets/ai/dispute/tx ======
+
+pub async fn prepare_dispute_tx(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<DisputeReq>,
+) -> Result<Json<UnsignedTxResponse>, AppError> {
+    let disputer_pk = current_user_pubkey(&headers, &state.jwt_secret)?;
+    let market_pda = Pubkey::from_str(&req.market_pda)
+        .map_err(|_| AppError::bad_request("bad market_pda"))?;
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_dispute_ai_unsigned(ctx.as_ref(), disputer_pk, market_pda)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(UnsignedTxResponse {
+        market_pda: req.market_pda,
+        tx: tx_b64,
+    }))
+}
+
+// ====== POST /v1/markets/ai/void/tx ======
+
+pub async fn prepare_void_tx(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<ResolveIxRequest>,
+) -> Result<Json<UnsignedTxResponse>, AppError> {
+    let payer_pk = current_user_pubkey(&headers, &state.jwt_secret)?;
+    let market_pda = Pubkey::from_str(&req.market_pda)
+        .map_err(|_| AppError::bad_request("bad market_pda"))?;
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_void_ai_market_unsigned(ctx.as_ref(), payer_pk, market_pda)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(UnsignedTxResponse {
+        market_pda: req.market_pda,
+        tx: tx_b64,
+    }))
+}
+
+// ====== POST /v1/markets/ai/dispute/expire/tx ======
+
+pub async fn prepare_expire_dispute_tx(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<DisputeReq>,
+) -> Result<Json<UnsignedTxResponse>, AppError> {
+    let payer_pk = current_user_pubkey(&headers, &state.jwt_secret)?;
+    let market_pda = Pubkey::from_str(&req.market_pda)
+        .map_err(|_| AppError::bad_request("bad market_pda"))?;
+
+    let ctx = state.anchor.clone();
+    let tx_b64 = tokio::task::spawn_blocking(move || {
+        anchor_client_::build_expire_dispute_unsigned(ctx.as_ref(), payer_pk, market_pda)
+    })
+    .await
+    .map_err(|e| AppError::Other(anyhow::anyhow!("join error: {e}")))?
+    .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    Ok(Json(UnsignedTxResponse {
+        market_pda: req.market_pda,
+        tx: tx_b64,
+    }))
+}
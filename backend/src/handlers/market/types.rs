@@ -9,8 +9,10 @@ use std::str::FromStr;
 
 use crate::{
     error::AppError,
-    repo::market::MarketRow
+    repo::market::MarketRow,
+    solana as anchor_client_,
 };
+use prediction_market_program as onchain;
 
 #[derive(Deserialize)]
 pub struct Claims {
@@ -26,6 +28,8 @@ pub struct Claims {
 pub enum MarketType {
     PriceThreshold,
     PriceRange,
+    PriceBuckets,
+    PriceScalar,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -38,6 +42,15 @@ pub enum MarketCategory {
 pub enum SeedSide {
     Yes,
     No,
+    Both, // two-sided liquidity through add_liquidity
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PricingKind {
+    #[default]
+    Parimutuel,
+    Cpmm,
 }
 
 #[derive(Deserialize, Validate)]
@@ -55,6 +68,10 @@ pub struct CreateMarketRequest {
 
     pub initial_side: SeedSide,
 
+    // CPMM markets use initial_liquidity to seed the curve instead of a bet
+    #[serde(default)]
+    pub pricing: PricingKind,
+
     pub feed_id: String,
 
     #[validate(length(max = 128, message = "Too long value"))]
@@ -65,6 +82,117 @@ pub struct CreateMarketRequest {
     pub threshold: Option<f64>,
     pub lower_bound: Option<f64>,
     pub upper_bound: Option<f64>,
+
+    // PriceBuckets only: ascending boundaries, N bounds => N + 1 buckets
+    #[serde(default)]
+    pub buckets: Option<Vec<f64>>,
+    #[serde(default)]
+    pub initial_bucket: Option<u8>,
+
+    // Collateral mint (base58); must be on the config allowlist, defaults to USDC_MINT
+    #[serde(default)]
+    pub collateral_mint: Option<String>,
+
+    // Optional stake limits in collateral UI units; bounded by the config caps
+    #[serde(default)]
+    pub min_bet: Option<f64>,
+    #[serde(default)]
+    pub max_bet: Option<f64>,
+    #[serde(default)]
+    pub max_per_wallet: Option<f64>,
+
+    // Pyth resolution rules: max conf/price in bps (0 or unset = no check),
+    // void instead of failing when it is exceeded, settle on the EMA price
+    #[serde(default)]
+    pub max_conf_bps: Option<u16>,
+    #[serde(default)]
+    pub void_on_wide_conf: bool,
+    #[serde(default)]
+    pub use_ema: bool,
+}
+
+/// Strike ladder: one threshold market per strike, same feed, comparator and end date
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_ladder_fields"))]
+pub struct CreateLadderRequest {
+    pub category: MarketCategory,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub end_date: OffsetDateTime,
+
+    pub feed_id: String,
+
+    #[validate(length(max = 128, message = "Too long value"))]
+    pub symbol: String,
+
+    pub comparator: Comparator,
+
+    // Ascending thresholds in USD, 2..=MAX_LADDER_STRIKES
+    pub strikes: Vec<f64>,
+
+    #[serde(default)]
+    pub collateral_mint: Option<String>,
+
+    #[serde(default)]
+    pub min_bet: Option<f64>,
+    #[serde(default)]
+    pub max_bet: Option<f64>,
+    #[serde(default)]
+    pub max_per_wallet: Option<f64>,
+
+    #[serde(default)]
+    pub max_conf_bps: Option<u16>,
+    #[serde(default)]
+    pub void_on_wide_conf: bool,
+    #[serde(default)]
+    pub use_ema: bool,
+}
+
+/// How a series round's strike is derived from the live price
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StrikeRule {
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+/// Recurring threshold market, e.g. "BTC above X at the top of every hour"
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_series_fields"))]
+pub struct CreateSeriesRequest {
+    pub feed_id: String,
+
+    #[validate(length(max = 128, message = "Too long value"))]
+    pub symbol: String,
+
+    pub comparator: Comparator,
+
+    // Rounds end on multiples of this, in seconds
+    pub cadence_secs: i64,
+
+    pub strike_rule: StrikeRule,
+    // Strike is the live price rounded to a multiple of this, in USD
+    pub strike_step: f64,
+
+    #[serde(default)]
+    pub collateral_mint: Option<String>,
+
+    #[serde(default)]
+    pub min_bet: Option<f64>,
+    #[serde(default)]
+    pub max_bet: Option<f64>,
+    #[serde(default)]
+    pub max_per_wallet: Option<f64>,
+
+    #[serde(default)]
+    pub max_conf_bps: Option<u16>,
+    #[serde(default)]
+    pub void_on_wide_conf: bool,
+    #[serde(default)]
+    pub use_ema: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -133,7 +261,7 @@ fn validate_market_fields(req: &CreateMarketRequest) -> Result<(), ValidationErr
                 return Err(ValidationError::new("threshold_required"));
             }
         }
-        MarketType::PriceRange => {
+        MarketType::PriceRange | MarketType::PriceScalar => {
             let (Some(lo), Some(hi)) = (req.lower_bound, req.upper_bound) else {
                 return Err(ValidationError::new("both_bounds_required"));
             };
@@ -143,6 +271,24 @@ fn validate_market_fields(req: &CreateMarketRequest) -> Result<(), ValidationErr
                 ));
             }
         }
+        MarketType::PriceBuckets => {
+            let Some(bounds) = req.buckets.as_deref() else {
+                return Err(ValidationError::new("buckets_required"));
+            };
+            if bounds.is_empty() || bounds.len() > 4 {
+                return Err(ValidationError::new("buckets_must_have_1_to_4_bounds"));
+            }
+            if bounds.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(ValidationError::new("buckets_must_be_increasing"));
+            }
+            if req.initial_bucket.unwrap_or(0) as usize > bounds.len() {
+                return Err(ValidationError::new("initial_bucket_out_of_range"));
+            }
+        }
+    }
+
+    if req.max_conf_bps.is_some_and(|b| b > 10_000) {
+        return Err(ValidationError::new("max_conf_bps_must_be_at_most_10000"));
     }
 
     // Feed ID must be valid 64-char hex string
@@ -154,6 +300,47 @@ fn validate_market_fields(req: &CreateMarketRequest) -> Result<(), ValidationErr
     Ok(())
 }
 
+fn validate_ladder_fields(req: &CreateLadderRequest) -> Result<(), ValidationError> {
+    if req.strikes.len() < 2 || req.strikes.len() > onchain::constants::MAX_LADDER_STRIKES {
+        return Err(ValidationError::new("strikes_must_have_2_to_4_values"));
+    }
+    if req.strikes.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(ValidationError::new("strikes_must_be_increasing"));
+    }
+    if req.max_conf_bps.is_some_and(|b| b > 10_000) {
+        return Err(ValidationError::new("max_conf_bps_must_be_at_most_10000"));
+    }
+
+    let s = req.feed_id.trim_start_matches("0x");
+    if s.len() != 64 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ValidationError::new("feed_id_must_be_64_hex"));
+    }
+
+    Ok(())
+}
+
+fn validate_series_fields(req: &CreateSeriesRequest) -> Result<(), ValidationError> {
+    if req.cadence_secs < onchain::constants::MIN_SERIES_CADENCE_SECS {
+        return Err(ValidationError::new("cadence_secs_must_be_at_least_300"));
+    }
+    if !(req.strike_step > 0.0) {
+        return Err(ValidationError::new("strike_step_must_be_positive"));
+    }
+    if matches!(req.comparator, Comparator::Eq | Comparator::Empty) {
+        return Err(ValidationError::new("comparator_not_supported"));
+    }
+    if req.max_conf_bps.is_some_and(|b| b > 10_000) {
+        return Err(ValidationError::new("max_conf_bps_must_be_at_most_10000"));
+    }
+
+    let s = req.feed_id.trim_start_matches("0x");
+    if s.len() != 64 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ValidationError::new("feed_id_must_be_64_hex"));
+    }
+
+    Ok(())
+}
+
 pub struct TitleSpec {
     pub symbol: Option<String>,
     pub end_date_utc: DateTime<Utc>,
@@ -163,6 +350,60 @@ pub struct TitleSpec {
     pub bound_hi_1e6: Option<i64>,
 }
 
+/// Convert UI stake limits to base units and check them against the config caps
+pub fn stake_limits_from_ui(
+    min_bet: Option<f64>,
+    max_bet: Option<f64>,
+    max_per_wallet: Option<f64>,
+    decimals: u8,
+    config: &onchain::state::config::Config,
+) -> Result<onchain::types::StakeLimits, AppError> {
+    let to_base = |name: &str, v: Option<f64>| -> Result<u64, AppError> {
+        match v {
+            None => Ok(0),
+            Some(x) if x.is_finite() && x >= 0.0 => Ok(anchor_client_::ui_to_base_units(x, decimals)),
+            Some(_) => Err(AppError::bad_request(format!("{name} must be >= 0"))),
+        }
+    };
+    let min_bet = to_base("minBet", min_bet)?;
+    let max_bet = to_base("maxBet", max_bet)?;
+    let max_per_wallet = to_base("maxPerWallet", max_per_wallet)?;
+
+    if config.max_bet_cap > 0 && max_bet > config.max_bet_cap {
+        return Err(AppError::bad_request("maxBet is above the platform cap"));
+    }
+    if config.max_per_wallet_cap > 0 && max_per_wallet > config.max_per_wallet_cap {
+        return Err(AppError::bad_request("maxPerWallet is above the platform cap"));
+    }
+
+    // Unset limits fall back to the caps, same as on-chain
+    let max_bet = if max_bet == 0 { config.max_bet_cap } else { max_bet };
+    let max_per_wallet = if max_per_wallet == 0 { config.max_per_wallet_cap } else { max_per_wallet };
+    if (max_bet > 0 && min_bet > max_bet) || (max_per_wallet > 0 && min_bet > max_per_wallet) {
+        return Err(AppError::bad_request("minBet is above the maximum"));
+    }
+
+    Ok(onchain::types::StakeLimits { min_bet, max_bet, max_per_wallet })
+}
+
+/// Mirror of the on-chain stake limit check so users see the reason before signing
+pub fn check_stake_limits(
+    l: &onchain::types::StakeLimits,
+    amount: u64,
+    position_total: u64,
+) -> Result<(), AppError> {
+    if amount < l.min_bet {
+        return Err(AppError::bad_request("bet is below the market minimum"));
+    }
+    if l.max_bet > 0 && amount > l.max_bet {
+        return Err(AppError::bad_request("bet is above the market maximum"));
+    }
+    if l.max_per_wallet > 0 && position_total > l.max_per_wallet {
+        return Err(AppError::bad_request("bet would exceed the per-wallet cap for this market"));
+    }
+    Ok(())
+}
+
 pub fn usd_to_1e6(x: f64) -> i64 {
     (x * 1_000_000f64).round() as i64
 }
@@ -197,6 +438,22 @@ pub fn generate_title(s: &TitleSpec) -> String {
                 lo, hi
             )
         }
+        "price-scalar" => {
+            let lo = (s.bound_lo_1e6.unwrap_or(0) as f64) / 1_000_000.0;
+            let hi = (s.bound_hi_1e6.unwrap_or(0) as f64) / 1_000_000.0;
+            format!(
+                "Where will {symbol_trimmed} land between ${:.2} and ${:.2} on {date_str}?",
+                lo, hi
+            )
+        }
+        "price-buckets" => {
+            let lo = (s.bound_lo_1e6.unwrap_or(0) as f64) / 1_000_000.0;
+            let hi = (s.bound_hi_1e6.unwrap_or(0) as f64) / 1_000_000.0;
+            format!(
+                "Where will {symbol_trimmed} be on {date_str}? Buckets from ${:.2} to ${:.2}",
+                lo, hi
+            )
+        }
         _ => format!("Will {symbol_trimmed} reach the target by {date_str}?"),
     }
 }
@@ -1,6 +1,7 @@
 pub mod leaderboard;
 pub mod webhooks;
 pub mod metadata;
+pub mod faucet;
 pub mod profile;
 pub mod health;
 pub mod market;
@@ -103,10 +103,10 @@ pub struct BetsPageResponse {
     next_cursor: Option<String>,
 }
 
-const DATETIME_API: &str = "%Y-%m-%d %H:%M:%S%:z";
+pub(super) const DATETIME_API: &str = "%Y-%m-%d %H:%M:%S%:z";
 // const DATETIME_QUESTION: &str = "%b %d, %Y UTC";
 
-fn resolve_wallet(
+pub(super) fn resolve_wallet(
     jar: &CookieJar,
     q_wallet: &Option<String>,
     state: &SharedState,
@@ -183,6 +183,7 @@ pub async fn list_bets_public(
                     None)
                 }
                 Some(3) => (Some("void".into()), Some(deadline_str.clone()), None),
+                Some(4) => (Some("scalar".into()), Some(deadline_str.clone()), None),
                 _ => (None, None, Some(deadline_str.clone())), // not settled
             };
 
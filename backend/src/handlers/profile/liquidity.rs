@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use axum_extra::extract::CookieJar;
+use chrono::{DateTime, Utc};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    repo::liquidity as lp_repo,
+    state::SharedState,
+    handlers::market::types::{generate_title, TitleSpec},
+};
+use super::bets::{resolve_wallet, DATETIME_API};
+
+impl From<&lp_repo::LpPositionRow> for TitleSpec {
+    fn from(p: &lp_repo::LpPositionRow) -> Self {
+        TitleSpec {
+            symbol: Some(p.symbol.clone()),
+            end_date_utc: DateTime::<Utc>::from_timestamp(p.end_date_utc.unix_timestamp(), 0)
+                .unwrap_or_default(),
+            market_type: Some(p.market_type.clone()),
+            comparator: p.comparator.clone(),
+            bound_lo_1e6: p.bound_lo_1e6,
+            bound_hi_1e6: p.bound_hi_1e6,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LpQuery {
+    limit: Option<u32>,
+    wallet: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LpPositionDto {
+    id: String,
+    title: String,
+    market_pda: String,
+    yes_amount: f64,
+    no_amount: f64,
+    shares: f64,
+    end_date: String,
+    market_outcome: Option<String>,
+    // Stake returned at settlement, before the LP fee share
+    residual: Option<f64>,
+    needs_claim: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LpPositionsResponse {
+    ok: bool,
+    items: Vec<LpPositionDto>,
+}
+
+// ====== GET /v1/profile/liquidity ======
+
+pub async fn list_lp_positions_public(
+    State(state): State<SharedState>,
+    jar: CookieJar,
+    Query(q): Query<LpQuery>,
+) -> Result<Json<LpPositionsResponse>, (StatusCode, String)> {
+    let wallet = resolve_wallet(&jar, &q.wallet, &state)?;
+    let limit = q.limit.unwrap_or(50).clamp(1, 100) as i64;
+
+    let rows = lp_repo::fetch_user_lp_positions(state.db.pool(), &wallet, limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("db error: {e}")))?;
+
+    let items = rows
+        .iter()
+        .map(|p| {
+            let market_outcome = match p.winning_side {
+                Some(1) if p.settled => Some("yes".to_string()),
+                Some(2) if p.settled => Some("no".to_string()),
+                Some(3) if p.settled => Some("void".to_string()),
+                _ => None,
+            };
+            let spec = TitleSpec::from(p);
+            LpPositionDto {
+                id: p.market_id.to_string(),
+                title: generate_title(&spec),
+                market_pda: p.market_pda.clone(),
+                yes_amount: (p.yes_1e6 as f64) / 1_000_000.0,
+                no_amount: (p.no_1e6 as f64) / 1_000_000.0,
+                shares: (p.shares_1e6 as f64) / 1_000_000.0,
+                end_date: spec.end_date_utc.format(DATETIME_API).to_string(),
+                market_outcome,
+                residual: p.residual_1e6.map(|v| (v as f64) / 1_000_000.0),
+                needs_claim: p.settled && !p.claimed,
+            }
+        })
+        .collect();
+
+    Ok(Json(LpPositionsResponse { ok: true, items }))
+}
@@ -1,2 +1,3 @@
 pub mod bets;
+pub mod liquidity;
 pub mod profile;
\ No newline at end of file
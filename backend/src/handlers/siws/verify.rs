@@ -3,12 +3,12 @@ use anchor_client::{solana_sdk::pubkey::Pubkey};
 use time::{Duration, OffsetDateTime};
 use cookie::{Cookie, SameSite};
 use std::str::FromStr;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 
 use axum::{
     Json,
-    extract::State,
+    extract::{Query, State},
     http::{StatusCode, header},
     response::IntoResponse,
 };
@@ -29,6 +29,12 @@ struct VerifyOk {
     ok: bool,
 }
 
+#[derive(Deserialize)]
+pub struct VerifyParams {
+    #[serde(rename = "ref")]
+    ref_code: Option<String>, // referral code of the wallet that invited this one
+}
+
 #[derive(Serialize)]
 struct Claims {
     sub: String,       // app user id
@@ -42,6 +48,7 @@ struct Claims {
 
 pub async fn verify(
     State(state): State<SharedState>,
+    Query(params): Query<VerifyParams>,
     Json(output): Json<SiwsOutput>,
 ) -> impl IntoResponse {
     // Parse SIWS message from signed bytes
@@ -101,7 +108,7 @@ pub async fn verify(
     let address = bs58::encode(&output.account.public_key).into_string();
 
     // Create or fetch wallet in DB
-    let (user_id, wallet_id, created_wallet) = match users::upsert_wallet(state.db.pool(), &address, now).await {
+    let (user_id, wallet_id, created_wallet) = match users::upsert_wallet(state.db.pool(), &address, now, params.ref_code.as_deref()).await {
         Ok(ids) => ids,
         Err(e) => {
             tracing::error!(error=?e, "db upsert_wallet failed");
@@ -115,7 +122,7 @@ pub async fn verify(
         let join_res = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let ctx  = anchor_client_::connect_devnet()?;
             let addr = Pubkey::from_str(&address_cloned)?;
-            let _sig = anchor_client_::airdrop_usdc_once(&ctx, addr)?;
+            let _sig = anchor_client_::airdrop_usdc(&ctx, addr)?;
             Ok(())
         })
         .await;
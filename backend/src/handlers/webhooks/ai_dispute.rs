@@ -0,0 +1,75 @@
+use axum::{extract::State, http::{HeaderMap, StatusCode}, Json};
+use serde::Deserialize;
+use serde_json::json;
+use anyhow::anyhow;
+use tracing::info;
+
+use crate::{
+    solana as anchor_client_,
+    state::SharedState,
+    error::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct RuleDisputeReq {
+    pub market_pda: String,
+    pub overturn: bool,
+    #[serde(default)]
+    pub outcome_idx: u8,
+    #[serde(default)]
+    pub winners_mask: u8,
+}
+
+fn admin_token() -> Result<String, AppError> {
+    std::env::var("ADMIN_TOKEN")
+        .map_err(|_| AppError::Other(anyhow!("ADMIN_TOKEN is not set")))
+}
+
+fn get_api_key(headers: &HeaderMap) -> Option<&str> {
+    use axum::http::header::HeaderName;
+    static X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+    headers.get(&X_API_KEY).and_then(|v| v.to_str().ok())
+}
+
+// ====== POST /v1/webhooks/ai/dispute/rule ======
+
+pub async fn rule_dispute_webhook(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(body): Json<RuleDisputeReq>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    let admin = admin_token()?;
+    match get_api_key(&headers) {
+        Some(v) if v == admin => {},
+        Some(_) => return Ok((StatusCode::UNAUTHORIZED, Json(json!({"error":"invalid X-API-KEY"})))),
+        None => return Ok((StatusCode::UNAUTHORIZED, Json(json!({"error":"missing X-API-KEY"})))),
+    }
+
+    let market_pubkey = anchor_client_::parse_pubkey(&body.market_pda)
+        .map_err(|e| AppError::bad_request(format!("invalid market_pda: {e}")))?;
+
+    let ctx = state.anchor.clone();
+    let (overturn, outcome_idx, winners_mask) = (body.overturn, body.outcome_idx, body.winners_mask);
+    let sig = tokio::task::spawn_blocking(move || {
+            anchor_client_::rule_dispute(
+                ctx.as_ref(),
+                &ctx.payer,
+                market_pubkey,
+                overturn,
+                outcome_idx,
+                winners_mask,
+            )
+        })
+        .await
+        .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+        .map_err(|e| AppError::Other(anyhow!("rule_dispute onchain failed: {e}")))?;
+
+    info!(%market_pubkey, overturn, "dispute ruled");
+
+    Ok((StatusCode::OK, Json(json!({
+        "status": "ok",
+        "market_pda": body.market_pda,
+        "overturned": overturn,
+        "tx": sig.to_string()
+    }))))
+}
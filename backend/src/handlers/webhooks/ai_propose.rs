@@ -15,6 +15,9 @@ use crate::{
 #[derive(Deserialize)]
 pub struct AiProposeReq {
     pub market_pda: String,
+    /// Sign a void attestation instead of asking the resolver model
+    #[serde(default)]
+    pub void: bool,
 }
 
 fn admin_token() -> Result<String, AppError> {
@@ -43,6 +46,29 @@ pub async fn ai_propose_webhook(
     let market_pubkey = anchor_client_::parse_pubkey(&body.market_pda)
         .map_err(|e| AppError::bad_request(format!("invalid market_pda: {e}")))?;
 
+    if body.void {
+        let state_cloned = state.clone();
+        let sig = tokio::task::spawn_blocking(move || {
+                anchor_client_::ai_propose_prepare(
+                    &state_cloned,
+                    market_pubkey,
+                    prediction_market_program::constants::OUTCOME_NONE,
+                )
+            })
+            .await
+            .map_err(|e| AppError::Other(anyhow!("join error: {e}")))?
+            .map_err(|e| AppError::Other(anyhow!("ai_propose void onchain failed: {e}")))?;
+
+        info!(%market_pubkey, "void attestation proposed");
+
+        return Ok((StatusCode::OK, Json(json!({
+            "status": "ok",
+            "market_pda": body.market_pda,
+            "void": true,
+            "tx": sig.to_string()
+        }))));
+    }
+
     let ai = market_repo::fetch_ai_resolver_input_by_pda(&state.db.pool(), &body.market_pda)
         .await
         .map_err(AppError::Other)?
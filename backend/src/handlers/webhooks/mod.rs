@@ -1,2 +1,3 @@
+pub mod ai_dispute;
 pub mod ai_propose;
 pub mod helius;
\ No newline at end of file
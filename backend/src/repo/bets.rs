@@ -100,6 +100,59 @@ pub async fn mark_position_claimed_by_pda(
     Ok(())
 }
 
+/// Marks one position paid out by `claim_many`; `outcome_idx` is set for multi-outcome markets.
+/// Unlike `mark_position_claimed_by_pda` this also covers void refunds.
+pub async fn mark_claimed_in_batch(
+    pool: &PgPool,
+    market_pda: &str,
+    user_pubkey: &str,
+    outcome_idx: Option<i16>,
+    tx_sig_claim: &str,
+) -> anyhow::Result<()> {
+    let res = match outcome_idx {
+        None => {
+            sqlx::query(
+                r#"
+                UPDATE market_positions mp
+                SET claimed      = TRUE,
+                    tx_sig_claim = COALESCE(mp.tx_sig_claim, $3)
+                FROM markets m
+                WHERE m.id = mp.market_id AND m.market_pda = $1 AND mp.user_pubkey = $2
+                "#,
+            )
+            .bind(market_pda)
+            .bind(user_pubkey)
+            .bind(tx_sig_claim)
+            .execute(pool)
+            .await
+        }
+        Some(idx) => {
+            sqlx::query(
+                r#"
+                UPDATE market_positions_multi pm
+                SET claimed      = TRUE,
+                    tx_sig_claim = COALESCE(pm.tx_sig_claim, $4)
+                FROM markets m
+                WHERE m.id = pm.market_id AND m.market_pda = $1
+                  AND pm.user_pubkey = $2 AND pm.outcome_idx = $3
+                "#,
+            )
+            .bind(market_pda)
+            .bind(user_pubkey)
+            .bind(idx)
+            .bind(tx_sig_claim)
+            .execute(pool)
+            .await
+        }
+    }
+    .context("mark batch claim failed")?;
+
+    if res.rows_affected() == 0 {
+        tracing::warn!(market_pda, user_pubkey, ?outcome_idx, "batch claim for unknown position");
+    }
+    Ok(())
+}
+
 pub async fn fetch_recent_bets(
     pool: &PgPool,
     limit: i64,
@@ -398,3 +451,134 @@ pub async fn insert_bet_and_upsert_position(
     tx.commit().await?;
     Ok(bet_id)
 }
+
+/// Applies an on-chain `exit_position*` to the position and market projections.
+/// `amount_1e6` is the full stake withdrawn (fee included); idempotent by `tx_sig`.
+pub async fn apply_exit_to_position(
+    pool: &PgPool,
+    market_id: Uuid,
+    user_pubkey: &str,
+    outcome_idx: u8,
+    amount_1e6: i64,
+    tx_sig: &str,
+) -> Result<()> {
+    if amount_1e6 <= 0 {
+        return Err(anyhow::anyhow!("Exit amount must be positive"));
+    }
+
+    let mut tx: Transaction<'_, Postgres> = pool.begin().await?;
+
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        .execute(&mut *tx)
+        .await
+        .context("Failed to set transaction isolation level")?;
+
+    let outcome_idx_i16 = outcome_idx as i16;
+
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO market_exits (market_id, user_pubkey, outcome_idx, amount_1e6, tx_sig)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (tx_sig) DO NOTHING
+        "#,
+    )
+    .bind(market_id)
+    .bind(user_pubkey)
+    .bind(outcome_idx_i16)
+    .bind(amount_1e6)
+    .bind(tx_sig)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        tracing::debug!(tx_sig = %tx_sig, "Duplicate exit transaction detected, skipping");
+        tx.commit().await?;
+        return Ok(());
+    }
+
+    if outcome_idx <= 1 {
+        let (yes_delta, no_delta) = if outcome_idx == 0 {
+            (amount_1e6, 0)
+        } else {
+            (0, amount_1e6)
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE market_positions
+            SET yes_bet_1e6 = GREATEST(yes_bet_1e6 - $3, 0),
+                no_bet_1e6  = GREATEST(no_bet_1e6  - $4, 0)
+            WHERE market_id = $1 AND user_pubkey = $2
+            "#,
+        )
+        .bind(market_id)
+        .bind(user_pubkey)
+        .bind(yes_delta)
+        .bind(no_delta)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE market_state
+            SET
+              yes_total_1e6 = GREATEST(yes_total_1e6 - $2, 0),
+              no_total_1e6  = GREATEST(no_total_1e6  - $3, 0),
+              updated_at    = NOW()
+            WHERE market_id = $1
+            "#,
+        )
+        .bind(market_id)
+        .bind(yes_delta)
+        .bind(no_delta)
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        sqlx::query(
+            r#"
+            UPDATE market_positions_multi
+            SET stake_1e6 = GREATEST(stake_1e6 - $4, 0)
+            WHERE market_id = $1 AND user_pubkey = $2 AND outcome_idx = $3
+            "#,
+        )
+        .bind(market_id)
+        .bind(user_pubkey)
+        .bind(outcome_idx_i16)
+        .bind(amount_1e6)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE market_state
+            SET
+              tvl_per_outcome_1e6 = jsonb_set(
+                COALESCE(tvl_per_outcome_1e6, '[]'::jsonb),
+                ARRAY[$2::text],
+                to_jsonb(GREATEST(COALESCE((tvl_per_outcome_1e6->$2)::bigint, 0) - $3, 0)),
+                true
+              ),
+              updated_at = NOW()
+            WHERE market_id = $1
+            "#,
+        )
+        .bind(market_id)
+        .bind(outcome_idx_i16.to_string())
+        .bind(amount_1e6)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tracing::info!(
+        market_id = %market_id,
+        user = %user_pubkey,
+        outcome_idx = outcome_idx,
+        amount = amount_1e6,
+        tx_sig = %tx_sig,
+        "Exit applied to position"
+    );
+
+    tx.commit().await?;
+    Ok(())
+}
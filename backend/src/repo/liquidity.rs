@@ -0,0 +1,288 @@
+use anyhow::{Context, Result};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A wallet's two-sided liquidity in one binary market
+pub struct LpPositionRow {
+    pub market_id: Uuid,
+    pub market_pda: String,
+    pub symbol: String,
+    pub market_type: String,
+    pub comparator: Option<String>,
+    pub bound_lo_1e6: Option<i64>,
+    pub bound_hi_1e6: Option<i64>,
+    pub end_date_utc: OffsetDateTime,
+    pub settled: bool,
+    pub winning_side: Option<i16>,
+
+    pub yes_1e6: i64,
+    pub no_1e6: i64,
+    pub shares_1e6: i64,
+    pub claimed: bool,
+
+    // Winning-side stake's payout (or both stakes on a void); excludes the LP fee share
+    pub residual_1e6: Option<i64>,
+}
+
+/// Record one liquidity tx in the ledger; false if it was already indexed
+async fn insert_ledger(
+    tx: &mut Transaction<'_, Postgres>,
+    market_id: Uuid,
+    user_pubkey: &str,
+    kind: &str,
+    amount_1e6: i64,
+    tx_sig: &str,
+) -> Result<bool> {
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO market_liquidity (market_id, user_pubkey, kind, amount_1e6, tx_sig)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (tx_sig) DO NOTHING
+        "#,
+    )
+    .bind(market_id)
+    .bind(user_pubkey)
+    .bind(kind)
+    .bind(amount_1e6)
+    .bind(tx_sig)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    Ok(inserted > 0)
+}
+
+/// `add_liquidity(amount)`: the deposit is split yes = amount - amount/2, no = amount/2
+/// and both halves count toward the market totals
+pub async fn apply_add_liquidity(
+    pool: &PgPool,
+    market_id: Uuid,
+    user_pubkey: &str,
+    amount_1e6: i64,
+    tx_sig: &str,
+) -> Result<()> {
+    if amount_1e6 <= 0 {
+        return Err(anyhow::anyhow!("Liquidity amount must be positive"));
+    }
+    let no_1e6 = amount_1e6 / 2;
+    let yes_1e6 = amount_1e6 - no_1e6;
+
+    let mut tx: Transaction<'_, Postgres> = pool.begin().await?;
+    if !insert_ledger(&mut tx, market_id, user_pubkey, "add", amount_1e6, tx_sig).await? {
+        tracing::debug!(tx_sig = %tx_sig, "Duplicate add_liquidity transaction detected, skipping");
+        tx.commit().await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO lp_positions (market_id, user_pubkey, yes_1e6, no_1e6, shares_1e6, claimed)
+        VALUES ($1, $2, $3, $4, $5, FALSE)
+        ON CONFLICT (market_id, user_pubkey) DO UPDATE
+          SET yes_1e6    = lp_positions.yes_1e6    + EXCLUDED.yes_1e6,
+              no_1e6     = lp_positions.no_1e6     + EXCLUDED.no_1e6,
+              shares_1e6 = lp_positions.shares_1e6 + EXCLUDED.shares_1e6,
+              updated_at = NOW()
+        "#,
+    )
+    .bind(market_id)
+    .bind(user_pubkey)
+    .bind(yes_1e6)
+    .bind(no_1e6)
+    .bind(amount_1e6)
+    .execute(&mut *tx)
+    .await
+    .context("upsert lp position failed")?;
+
+    sqlx::query(
+        r#"
+        UPDATE market_state
+        SET
+          yes_total_1e6 = yes_total_1e6 + $2,
+          no_total_1e6  = no_total_1e6  + $3,
+          updated_at    = NOW()
+        WHERE market_id = $1
+        "#,
+    )
+    .bind(market_id)
+    .bind(yes_1e6)
+    .bind(no_1e6)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// `remove_liquidity(shares)`: burns shares for the same pro-rata slice of each side
+/// the program returns (before the exit fee, which stays in the vaults)
+pub async fn apply_remove_liquidity(
+    pool: &PgPool,
+    market_id: Uuid,
+    user_pubkey: &str,
+    shares_1e6: i64,
+    tx_sig: &str,
+) -> Result<()> {
+    if shares_1e6 <= 0 {
+        return Err(anyhow::anyhow!("LP shares must be positive"));
+    }
+
+    let mut tx: Transaction<'_, Postgres> = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        .execute(&mut *tx)
+        .await
+        .context("Failed to set transaction isolation level")?;
+
+    if !insert_ledger(&mut tx, market_id, user_pubkey, "remove", shares_1e6, tx_sig).await? {
+        tracing::debug!(tx_sig = %tx_sig, "Duplicate remove_liquidity transaction detected, skipping");
+        tx.commit().await?;
+        return Ok(());
+    }
+
+    // SET expressions all see the pre-update row
+    let row = sqlx::query(
+        r#"
+        WITH prev AS (
+          SELECT yes_1e6, no_1e6, shares_1e6
+          FROM lp_positions
+          WHERE market_id = $1 AND user_pubkey = $2
+          FOR UPDATE
+        )
+        UPDATE lp_positions lp
+        SET
+          yes_1e6    = lp.yes_1e6 - FLOOR(prev.yes_1e6::NUMERIC * $3 / NULLIF(prev.shares_1e6, 0))::BIGINT,
+          no_1e6     = lp.no_1e6  - FLOOR(prev.no_1e6::NUMERIC  * $3 / NULLIF(prev.shares_1e6, 0))::BIGINT,
+          shares_1e6 = GREATEST(lp.shares_1e6 - $3, 0),
+          updated_at = NOW()
+        FROM prev
+        WHERE lp.market_id = $1 AND lp.user_pubkey = $2
+        RETURNING prev.yes_1e6 - lp.yes_1e6 AS yes_out, prev.no_1e6 - lp.no_1e6 AS no_out
+        "#,
+    )
+    .bind(market_id)
+    .bind(user_pubkey)
+    .bind(shares_1e6)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("update lp position failed")?;
+
+    if let Some(r) = row {
+        let yes_out: Option<i64> = r.try_get("yes_out")?;
+        let no_out: Option<i64> = r.try_get("no_out")?;
+        sqlx::query(
+            r#"
+            UPDATE market_state
+            SET
+              yes_total_1e6 = GREATEST(yes_total_1e6 - $2, 0),
+              no_total_1e6  = GREATEST(no_total_1e6  - $3, 0),
+              updated_at    = NOW()
+            WHERE market_id = $1
+            "#,
+        )
+        .bind(market_id)
+        .bind(yes_out.unwrap_or(0))
+        .bind(no_out.unwrap_or(0))
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        tracing::warn!(market_id = %market_id, user = %user_pubkey, "remove_liquidity for unknown lp position");
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn mark_lp_claimed(
+    pool: &PgPool,
+    market_id: Uuid,
+    user_pubkey: &str,
+    tx_sig: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE lp_positions
+        SET claimed = TRUE, tx_sig_claim = $3, updated_at = NOW()
+        WHERE market_id = $1 AND user_pubkey = $2 AND claimed = FALSE
+        "#,
+    )
+    .bind(market_id)
+    .bind(user_pubkey)
+    .bind(tx_sig)
+    .execute(pool)
+    .await
+    .context("mark lp claimed failed")?;
+
+    Ok(())
+}
+
+/// A wallet's LP positions, newest markets first
+pub async fn fetch_user_lp_positions(
+    pool: &PgPool,
+    user_pubkey: &str,
+    limit: i64,
+) -> Result<Vec<LpPositionRow>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+          lp.market_id,
+          mv.market_pda,
+          mv.symbol,
+          mv.market_type,
+          mv.comparator,
+          mv.bound_lo_1e6,
+          mv.bound_hi_1e6,
+          mv.end_date_utc,
+          mv.settled,
+          mv.winning_side,
+          lp.yes_1e6,
+          lp.no_1e6,
+          lp.shares_1e6,
+          lp.claimed,
+          CASE
+            WHEN mv.settled AND mv.winning_side = 3 THEN lp.yes_1e6 + lp.no_1e6
+            WHEN mv.settled AND mv.winning_side IN (1,2) THEN
+              COALESCE(FLOOR(
+                mv.payout_pool_1e6::NUMERIC
+                * (CASE WHEN mv.winning_side = 1 THEN lp.yes_1e6 ELSE lp.no_1e6 END)::NUMERIC
+                / NULLIF((CASE WHEN mv.winning_side = 1 THEN mv.yes_total_1e6 ELSE mv.no_total_1e6 END)::NUMERIC, 0)
+              )::BIGINT, 0)
+              + (CASE WHEN mv.winning_side = 1 THEN lp.no_1e6 ELSE lp.yes_1e6 END)
+            ELSE NULL
+          END AS residual_1e6
+        FROM lp_positions lp
+        JOIN market_view mv ON mv.id = lp.market_id
+        WHERE lp.user_pubkey = $1
+          AND (lp.shares_1e6 > 0 OR lp.claimed)
+        ORDER BY mv.end_date_utc DESC, lp.market_id DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(user_pubkey)
+    .bind(limit.clamp(1, 100))
+    .fetch_all(pool)
+    .await
+    .context("fetch lp positions failed")?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(LpPositionRow {
+                market_id: r.try_get("market_id")?,
+                market_pda: r.try_get("market_pda")?,
+                symbol: r.try_get("symbol")?,
+                market_type: r.try_get("market_type")?,
+                comparator: r.try_get("comparator")?,
+                bound_lo_1e6: r.try_get("bound_lo_1e6")?,
+                bound_hi_1e6: r.try_get("bound_hi_1e6")?,
+                end_date_utc: r.try_get("end_date_utc")?,
+                settled: r.try_get("settled")?,
+                winning_side: r.try_get("winning_side")?,
+                yes_1e6: r.try_get("yes_1e6")?,
+                no_1e6: r.try_get("no_1e6")?,
+                shares_1e6: r.try_get("shares_1e6")?,
+                claimed: r.try_get("claimed")?,
+                residual_1e6: r.try_get("residual_1e6")?,
+            })
+        })
+        .collect()
+}
@@ -51,6 +51,7 @@ pub struct MarketRowFetch {
     pub status: String,
     pub market_kind: Option<String>,
     pub ai_topic: Option<String>,
+    pub series_pda: Option<String>,
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -103,6 +104,7 @@ pub struct ResolveSnapshot {
     pub winning_side: Option<i16>, 
     pub resolved_price_1e6: Option<i64>,
     pub payout_pool_1e6: Option<i64>,
+    pub winning_outcome_idx: Option<i16>,
     pub resolver_pubkey: String,
     pub tx_sig_resolve: String,
 }
@@ -127,6 +129,8 @@ fn market_type_str(mt: MarketType) -> &'static str {
     match mt {
         MarketType::PriceThreshold => "price-threshold",
         MarketType::PriceRange => "price-range",
+        MarketType::PriceBuckets => "price-buckets",
+        MarketType::PriceScalar => "price-scalar",
     }
 }
 
@@ -302,11 +306,72 @@ pub async fn upsert_initial_state(
 }
 
 
+/// Stores the ordered bucket boundaries of a Pyth bucket market
+pub async fn set_bucket_bounds(
+    pool: &sqlx::PgPool,
+    market_id: Uuid,
+    bounds_1e6: &[i64],
+) -> anyhow::Result<()> {
+    sqlx::query(r#"UPDATE markets SET bucket_bounds_1e6 = $2 WHERE id = $1"#)
+        .bind(market_id)
+        .bind(bounds_1e6)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Links a market to its strike ladder; `root_pda` is the ladder's first strike
+pub async fn set_ladder(
+    pool: &sqlx::PgPool,
+    market_id: Uuid,
+    root_pda: &str,
+    strike_idx: i16,
+) -> anyhow::Result<()> {
+    sqlx::query(r#"UPDATE markets SET ladder_root_pda = $2, ladder_index = $3 WHERE id = $1"#)
+        .bind(market_id)
+        .bind(root_pda)
+        .bind(strike_idx)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Keeps the CPMM curve reserves in `market_state` so `market_view.price_yes_bp`
+/// can quote the executable price (pool_no / (pool_yes + pool_no)).
+pub async fn update_cpmm_reserves(
+    pool: &sqlx::PgPool,
+    market_id: Uuid,
+    pool_yes_1e6: i64,
+    pool_no_1e6: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE market_state
+        SET
+          pricing_kind = 1,
+          pool_yes_1e6 = $2,
+          pool_no_1e6  = $3,
+          updated_at   = now()
+        WHERE market_id = $1
+        "#,
+    )
+    .bind(market_id)
+    .bind(pool_yes_1e6)
+    .bind(pool_no_1e6)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn fetch_markets_page(
     pool: &PgPool,
     limit: i64,
     cursor: Option<&str>,
     category: Option<&str>,
+    series: Option<&str>,
     sort: Option<&str>,
     statuses: Option<&[&str]>,
 ) -> Result<MarketsPage> {
@@ -380,6 +445,7 @@ pub async fn fetch_markets_page(
             id, market_pda, category, total_volume_1e6, participants, price_yes_bp,
             end_date_utc, updated_at, symbol, market_type, comparator,
             bound_lo_1e6, bound_hi_1e6, status, market_kind, ai_topic,
+            (SELECT mk.series_pda FROM markets mk WHERE mk.id = market_view.id) AS series_pda,
             CASE status
               WHEN 'active'           THEN 1
               WHEN 'awaiting_resolve' THEN 2
@@ -395,6 +461,11 @@ pub async fn fetch_markets_page(
     if let Some(cat) = category {
         qb.push(" AND category = ").push_bind(cat);
     }
+    if let Some(series_pda) = series {
+        qb.push(" AND id IN (SELECT id FROM markets WHERE series_pda = ")
+          .push_bind(series_pda)
+          .push(")");
+    }
 
     qb.push(" AND status = ANY(")
       .push_bind(status_vec)
@@ -467,6 +538,7 @@ pub async fn fetch_markets_page(
             status: r.try_get("status")?,
             market_kind: r.try_get("market_kind")?,
             ai_topic: r.try_get("ai_topic")?,
+            series_pda: r.try_get("series_pda")?,
         });
     }
 
@@ -632,17 +704,18 @@ pub async fn confirm_resolve_persist(
         r#"
         INSERT INTO market_state (
             market_id, settled, winning_side, resolved_price_1e6, payout_pool_1e6,
-            resolver_pubkey, tx_sig_resolve, updated_at
+            winning_outcome_idx, resolver_pubkey, tx_sig_resolve, updated_at
         )
-        VALUES ($1,$2,$3,$4,$5,$6,$7, now())
+        VALUES ($1,$2,$3,$4,$5,$6,$7,$8, now())
         ON CONFLICT (market_id) DO UPDATE SET
-            settled            = EXCLUDED.settled,
-            winning_side       = EXCLUDED.winning_side,
-            resolved_price_1e6 = EXCLUDED.resolved_price_1e6,
-            payout_pool_1e6    = EXCLUDED.payout_pool_1e6,
-            resolver_pubkey    = EXCLUDED.resolver_pubkey,
-            tx_sig_resolve     = EXCLUDED.tx_sig_resolve,
-            updated_at         = now()
+            settled             = EXCLUDED.settled,
+            winning_side        = EXCLUDED.winning_side,
+            resolved_price_1e6  = EXCLUDED.resolved_price_1e6,
+            payout_pool_1e6     = EXCLUDED.payout_pool_1e6,
+            winning_outcome_idx = EXCLUDED.winning_outcome_idx,
+            resolver_pubkey     = EXCLUDED.resolver_pubkey,
+            tx_sig_resolve      = EXCLUDED.tx_sig_resolve,
+            updated_at          = now()
         "#,
     )
     .bind(market_id)
@@ -650,6 +723,7 @@ pub async fn confirm_resolve_persist(
     .bind(snap.winning_side)
     .bind(snap.resolved_price_1e6)
     .bind(snap.payout_pool_1e6)
+    .bind(snap.winning_outcome_idx)
     .bind(&snap.resolver_pubkey)
     .bind(&snap.tx_sig_resolve)
     .execute(&mut *tx)
@@ -3,4 +3,6 @@ pub mod profile;
 pub mod points;
 pub mod market;
 pub mod users;
-pub mod bets;
\ No newline at end of file
+pub mod bets;
+pub mod series;
+pub mod liquidity;
\ No newline at end of file
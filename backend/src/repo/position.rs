@@ -32,7 +32,7 @@ pub struct PositionRow {
     pub user_yes_bet_1e6: i64,
     pub user_no_bet_1e6: i64,
 
-    pub winning_side: Option<i16>,      // 1=yes, 2=no, 3=void
+    pub winning_side: Option<i16>,      // 1=yes, 2=no, 3=void, 4=scalar
     pub market_outcome: Option<String>, // "yes" | "no" | "void" | NULL
     pub needs_claim: bool,
     pub net_claim_1e6: Option<i64>,
@@ -135,12 +135,13 @@ pub async fn fetch_user_positions_page(
             WHEN mv.settled AND mv.winning_side = 1 THEN 'yes'
             WHEN mv.settled AND mv.winning_side = 2 THEN 'no'
             WHEN mv.settled AND mv.winning_side = 3 THEN 'void'
+            WHEN mv.settled AND mv.winning_side = 4 THEN 'scalar'
             ELSE NULL
           END AS market_outcome,
 
           CASE
             WHEN mv.settled = TRUE AND mp.claimed = FALSE AND (
-              (mv.winning_side = 3 AND (mp.yes_bet_1e6 + mp.no_bet_1e6) > 0) OR
+              (mv.winning_side IN (3,4) AND (mp.yes_bet_1e6 + mp.no_bet_1e6) > 0) OR
               (mv.winning_side = 1 AND mp.yes_bet_1e6 > 0) OR
               (mv.winning_side = 2 AND mp.no_bet_1e6  > 0)
             )
@@ -253,6 +254,183 @@ pub async fn fetch_user_positions_page(
     Ok(PositionsPage { items, next_cursor })
 }
 
+/// Unclaimed position on a settled market that pays out (winner or void refund)
+pub struct ClaimableRow {
+    pub market_pda: String,
+    pub outcome_idx: Option<i16>, // None for binary markets
+}
+
+/// Every unclaimed winning or refundable position of a wallet, binary and multi-outcome
+pub async fn fetch_claimable_positions(
+    pool: &PgPool,
+    user_pubkey: &str,
+    limit: i64,
+) -> Result<Vec<ClaimableRow>> {
+    use sqlx::Row;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT mv.market_pda, NULL::smallint AS outcome_idx
+        FROM market_positions mp
+        JOIN market_view mv ON mv.id = mp.market_id
+        WHERE mp.user_pubkey = $1
+          AND mv.settled = TRUE
+          AND mp.claimed = FALSE
+          AND (
+            (mv.winning_side = 3 AND (mp.yes_bet_1e6 + mp.no_bet_1e6) > 0) OR
+            (mv.winning_side = 1 AND mp.yes_bet_1e6 > 0) OR
+            (mv.winning_side = 2 AND mp.no_bet_1e6  > 0)
+          )
+        UNION ALL
+        SELECT mv.market_pda, pm.outcome_idx
+        FROM market_positions_multi pm
+        JOIN market_view mv ON mv.id = pm.market_id
+        JOIN market_state ms ON ms.market_id = pm.market_id
+        WHERE pm.user_pubkey = $1
+          AND ms.settled = TRUE
+          AND pm.claimed = FALSE
+          AND pm.stake_1e6 > 0
+          AND (
+            ms.winning_side = 3
+            OR ms.winning_outcome_idx IS NULL
+            OR ms.winning_outcome_idx = 255
+            OR ms.winning_outcome_idx = pm.outcome_idx
+          )
+        LIMIT $2
+        "#,
+    )
+    .bind(user_pubkey)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("fetch claimable positions failed")?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(ClaimableRow {
+                market_pda: r.try_get("market_pda")?,
+                outcome_idx: r.try_get("outcome_idx")?,
+            })
+        })
+        .collect()
+}
+
+/// Indexed market or position, for the account layout migration sweep
+pub struct MigrationRow {
+    pub market_pda: String,
+    pub user_pubkey: Option<String>, // None for the market row itself
+    pub outcome_idx: Option<i16>,    // multi-outcome positions only
+    pub lp: bool,
+}
+
+/// Every indexed market followed by its binary, multi-outcome and LP positions.
+/// Positions already closed on-chain are still listed; the migration skips missing accounts.
+pub async fn fetch_migration_rows(pool: &PgPool) -> Result<Vec<MigrationRow>> {
+    use sqlx::Row;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT * FROM (
+          SELECT mv.market_pda, NULL::text AS user_pubkey, NULL::smallint AS outcome_idx, FALSE AS lp
+          FROM market_view mv
+          UNION ALL
+          SELECT mv.market_pda, mp.user_pubkey, NULL::smallint, FALSE
+          FROM market_positions mp
+          JOIN market_view mv ON mv.id = mp.market_id
+          UNION ALL
+          SELECT mv.market_pda, pm.user_pubkey, pm.outcome_idx, FALSE
+          FROM market_positions_multi pm
+          JOIN market_view mv ON mv.id = pm.market_id
+          UNION ALL
+          SELECT mv.market_pda, lp.user_pubkey, NULL::smallint, TRUE
+          FROM lp_positions lp
+          JOIN market_view mv ON mv.id = lp.market_id
+        ) r
+        ORDER BY market_pda, user_pubkey NULLS FIRST
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("fetch migration rows failed")?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(MigrationRow {
+                market_pda: r.try_get("market_pda")?,
+                user_pubkey: r.try_get("user_pubkey")?,
+                outcome_idx: r.try_get("outcome_idx")?,
+                lp: r.try_get("lp")?,
+            })
+        })
+        .collect()
+}
+
+/// Unclaimed winning position the payout crank can push
+pub struct CrankableRow {
+    pub market_pda: String,
+    pub user_pubkey: String,
+    pub outcome_idx: Option<i16>, // None for binary markets
+}
+
+/// Unclaimed winners on markets that ended between `lookback_secs` and `delay_secs` ago,
+/// grouped by market so the crank can batch them per vault
+pub async fn fetch_crankable_positions(
+    pool: &PgPool,
+    delay_secs: i64,
+    lookback_secs: i64,
+    limit: i64,
+) -> Result<Vec<CrankableRow>> {
+    use sqlx::Row;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT * FROM (
+          SELECT mv.market_pda, mp.user_pubkey, NULL::smallint AS outcome_idx
+          FROM market_positions mp
+          JOIN market_view mv ON mv.id = mp.market_id
+          WHERE mv.settled = TRUE
+            AND mp.claimed = FALSE
+            AND mv.end_date_utc <= now() - make_interval(secs => $1)
+            AND mv.end_date_utc >  now() - make_interval(secs => $2)
+            AND (
+              (mv.winning_side = 1 AND mp.yes_bet_1e6 > 0) OR
+              (mv.winning_side = 2 AND mp.no_bet_1e6  > 0)
+            )
+          UNION ALL
+          SELECT mv.market_pda, pm.user_pubkey, pm.outcome_idx
+          FROM market_positions_multi pm
+          JOIN market_view mv ON mv.id = pm.market_id
+          JOIN market_state ms ON ms.market_id = pm.market_id
+          WHERE ms.settled = TRUE
+            AND ms.winning_side IS DISTINCT FROM 3
+            AND pm.claimed = FALSE
+            AND pm.stake_1e6 > 0
+            AND mv.end_date_utc <= now() - make_interval(secs => $1)
+            AND mv.end_date_utc >  now() - make_interval(secs => $2)
+            AND (ms.winning_outcome_idx = 255 OR ms.winning_outcome_idx = pm.outcome_idx)
+        ) c
+        ORDER BY market_pda, outcome_idx
+        LIMIT $3
+        "#,
+    )
+    .bind(delay_secs as f64)
+    .bind(lookback_secs as f64)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("fetch crankable positions failed")?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(CrankableRow {
+                market_pda: r.try_get("market_pda")?,
+                user_pubkey: r.try_get("user_pubkey")?,
+                outcome_idx: r.try_get("outcome_idx")?,
+            })
+        })
+        .collect()
+}
+
 pub async fn apply_bet_to_position(
     pool: &PgPool,
     market_id: Uuid,
@@ -0,0 +1,123 @@
+use anyhow::{Context, Result};
+use sqlx::{PgPool, Row};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Registered market series; `comparator` is in the markets table format (">", "<=", ...)
+pub struct SeriesRow {
+    pub series_pda: String,
+    pub authority_pubkey: String,
+    pub symbol: String,
+    pub feed_id: String,
+    pub price_feed_account: String,
+    pub mint: String,
+    pub comparator: String,
+    pub cadence_secs: i64,
+}
+
+/// Register a series so the roller picks it up once its create tx confirms
+pub async fn insert_series(pool: &PgPool, s: &SeriesRow) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO market_series (
+          series_pda, authority_pubkey, symbol, feed_id, price_feed_account, mint,
+          comparator, cadence_secs
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (series_pda) DO NOTHING
+        "#,
+    )
+    .bind(&s.series_pda)
+    .bind(&s.authority_pubkey)
+    .bind(&s.symbol)
+    .bind(&s.feed_id)
+    .bind(&s.price_feed_account)
+    .bind(&s.mint)
+    .bind(&s.comparator)
+    .bind(s.cadence_secs)
+    .execute(pool)
+    .await
+    .context("insert market series failed")?;
+
+    Ok(())
+}
+
+pub async fn fetch_series(pool: &PgPool) -> Result<Vec<SeriesRow>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT series_pda, authority_pubkey, symbol, feed_id, price_feed_account, mint,
+               comparator, cadence_secs
+        FROM market_series
+        ORDER BY created_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("fetch market series failed")?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(SeriesRow {
+                series_pda: r.try_get("series_pda")?,
+                authority_pubkey: r.try_get("authority_pubkey")?,
+                symbol: r.try_get("symbol")?,
+                feed_id: r.try_get("feed_id")?,
+                price_feed_account: r.try_get("price_feed_account")?,
+                mint: r.try_get("mint")?,
+                comparator: r.try_get("comparator")?,
+                cadence_secs: r.try_get("cadence_secs")?,
+            })
+        })
+        .collect()
+}
+
+/// Insert the market a series round opened, linked to its series.
+/// `tx_sig_create` is empty when someone else rolled the round.
+pub async fn insert_series_market(
+    pool: &PgPool,
+    s: &SeriesRow,
+    market_pda: &str,
+    round: i64,
+    strike_1e6: i64,
+    end_date_utc: OffsetDateTime,
+    tx_sig_create: &str,
+) -> Result<Uuid> {
+    let id = sqlx::query_scalar(
+        r#"
+        INSERT INTO markets (
+          market_kind,
+          market_pda, authority_pubkey, tx_sig_create,
+          category, symbol,
+          market_type, comparator, bound_lo_1e6, bound_hi_1e6, end_date_utc,
+          feed_id, price_feed_account, mint,
+          initial_liquidity_1e6,
+          series_pda, series_round
+        )
+        VALUES ('pyth',
+                $1, $2, $3,
+                'crypto', $4,
+                'price-threshold', $5, $6, 0, $7::timestamptz,
+                $8, $9, $10,
+                0,
+                $11, $12)
+        RETURNING id
+        "#,
+    )
+    .bind(market_pda)
+    .bind(&s.authority_pubkey)
+    .bind(tx_sig_create)
+    .bind(&s.symbol)
+    .bind(&s.comparator)
+    .bind(strike_1e6)
+    .bind(end_date_utc)
+    .bind(&s.feed_id)
+    .bind(&s.price_feed_account)
+    .bind(&s.mint)
+    .bind(&s.series_pda)
+    .bind(round)
+    .fetch_one(pool)
+    .await
+    .context("insert series market failed")?;
+
+    Ok(id)
+}
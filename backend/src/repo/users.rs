@@ -36,6 +36,7 @@ pub async fn upsert_wallet(
     pool: &sqlx::PgPool,
     wallet: &str,
     now: OffsetDateTime,
+    referral_code: Option<&str>,
 ) -> Result<(Uuid, Uuid, bool)> {
     if wallet.is_empty() {
         return Err(anyhow::anyhow!("Wallet address cannot be empty"));
@@ -80,16 +81,23 @@ pub async fn upsert_wallet(
         .fetch_one(&mut *tx)
         .await?;
 
+    // Referral code is only honoured at signup; an unknown code leaves `referred_by` null
+    let own_code = Uuid::new_v4().simple().to_string()[..8].to_string();
     let wallet_row = sqlx::query_as::<_, WalletRow>(
         r#"
-        INSERT INTO wallets (user_id, wallet_address, last_login_at, login_count)
-        VALUES ($1, $2, $3, 1)
+        INSERT INTO wallets (user_id, wallet_address, last_login_at, login_count, referral_code, referred_by)
+        VALUES (
+            $1, $2, $3, 1, $4,
+            (SELECT wallet_address FROM wallets WHERE referral_code = $5 AND wallet_address <> $2)
+        )
         RETURNING id, user_id, true AS created_wallet
         "#,
     )
     .bind(new_user_id)
     .bind(wallet)
     .bind(now)
+    .bind(&own_code)
+    .bind(referral_code)
     .fetch_one(&mut *tx)
     .await
     .context("Failed to create wallet")?;
@@ -105,6 +113,19 @@ pub async fn upsert_wallet(
     Ok((wallet_row.user_id, wallet_row.id, true))
 }
 
+/// Wallet that referred `wallet` at signup, if any
+pub async fn fetch_referrer(pool: &PgPool, wallet: &str) -> Result<Option<String>> {
+    let referrer = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT referred_by FROM wallets WHERE wallet_address = $1",
+    )
+    .bind(wallet)
+    .fetch_optional(pool)
+    .await?
+    .flatten();
+
+    Ok(referrer)
+}
+
 pub async fn exists_user_wallet(
     pool: &sqlx::PgPool,
     user_id: Uuid,
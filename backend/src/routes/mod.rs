@@ -32,6 +32,13 @@ pub fn build(state: SharedState) -> Router {
         .finish()
         .unwrap();
 
+    // faucet drips are on-chain transactions paid by the server: a couple per IP, then one a minute
+    let faucet_governor = GovernorConfigBuilder::default()
+        .per_second(60)
+        .burst_size(2)
+        .finish()
+        .unwrap();
+
     // --- CORS ---
     let origin: HeaderValue = state
         .uri
@@ -48,6 +55,7 @@ pub fn build(state: SharedState) -> Router {
     let public_v1 = Router::new()
         .route("/profile/{wallet}", get(handlers::profile::profile::wallet_overview_public))
         .route("/profile/bets", get(handlers::profile::bets::list_bets_public))
+        .route("/profile/liquidity", get(handlers::profile::liquidity::list_lp_positions_public))
         .route("/auth/nonce", get(handlers::siws::nonce::get_nonce))
         .route("/auth/verify", post(handlers::siws::verify::verify))
         .route("/leaderboard", get(handlers::leaderboard::handle))
@@ -58,6 +66,11 @@ pub fn build(state: SharedState) -> Router {
     let protected_v1 = Router::new()
         .route("/profile/overview", get(handlers::profile::profile::wallet_overview))
         .route("/admin/metadata", post(handlers::metadata::set_token_metadata))
+        .merge(
+            Router::new()
+                .route("/faucet/claim", post(handlers::faucet::claim))
+                .layer(GovernorLayer::new(faucet_governor)),
+        )
         .route("/health", get(handlers::health::health))
         .merge(handlers::market::protected_routes())
         .route_layer(middleware::from_fn_with_state(state.clone(), require_user));
@@ -65,6 +78,7 @@ pub fn build(state: SharedState) -> Router {
 Router::new()
         .route("/", get(handlers::root::index))
         .route("/v1/webhooks/ai/propose", post(handlers::webhooks::ai_propose::ai_propose_webhook))
+        .route("/v1/webhooks/ai/dispute/rule", post(handlers::webhooks::ai_dispute::rule_dispute_webhook))
         .route("/v1/webhooks/helius", post(handlers::webhooks::helius::helius_webhook))
         .nest("/v1", public_v1.merge(protected_v1))
         .with_state(state)
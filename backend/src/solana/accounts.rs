@@ -1,22 +1,50 @@
 use super::context::{AnchorCtx, program};
-use super::pda::{pda_position, pda_position_multi, pda_config};
-use anchor_client::solana_sdk::pubkey::Pubkey;
+use super::pda::{pda_position, pda_position_multi, pda_lp_position, pda_config, pda_faucet, pda_claim};
+use anchor_client::{anchor_lang::AccountDeserialize, solana_sdk::pubkey::Pubkey};
 use anyhow::Result;
 use std::sync::Arc;
 use prediction_market_program as onchain;
 
+/// Raw account data, for accounts that may predate the current layout
+fn get_account_data(ctx: &AnchorCtx, pda: Pubkey) -> Result<Vec<u8>> {
+    let program = program(ctx)?;
+    program
+        .rpc()
+        .get_account_data(&pda)
+        .map_err(|e| anyhow::anyhow!("account {pda} fetch failed: {e}"))
+}
+
+/// Decode a position written under any layout version; fields appended since read as zero
+fn decode_position<T: AccountDeserialize>(data: &[u8], space: usize) -> Result<T> {
+    let mut buf = data.to_vec();
+    if buf.len() < space {
+        buf.resize(space, 0);
+    }
+    T::try_deserialize(&mut &buf[..]).map_err(|e| anyhow::anyhow!("position decode failed: {e}"))
+}
+
 /// Fetch position account for binary markets
 pub fn get_position_account(
     ctx: &AnchorCtx,
     market_pda: Pubkey,
     user: Pubkey,
 ) -> Result<onchain::state::position::PositionBin> {
-    let program = program(ctx)?;
     let (pos_pda, _) = pda_position(&market_pda, &user);
-    let acc: onchain::state::position::PositionBin = program
-        .account(pos_pda)
+    let data = get_account_data(ctx, pos_pda)
         .map_err(|e| anyhow::anyhow!("position(bin) fetch failed: {e}"))?;
-    Ok(acc)
+    decode_position(&data, onchain::state::position::PositionBin::SPACE)
+}
+
+/// Fetch a liquidity provider's position in a binary market
+pub fn get_lp_position_account(
+    ctx: &AnchorCtx,
+    market_pda: Pubkey,
+    owner: Pubkey,
+) -> Result<onchain::state::position::LpPosition> {
+    let (lp_pda, _) = pda_lp_position(&market_pda, &owner);
+    let data = get_account_data(ctx, lp_pda)
+        .map_err(|e| anyhow::anyhow!("lp position fetch failed: {e}"))?;
+    decode_position(&data, onchain::state::position::LpPosition::SPACE)
 }
 
 /// Fetch position account for multi-outcome markets
@@ -26,33 +54,72 @@ pub fn get_position_multi_account(
     user: Pubkey,
     outcome_idx: u8,
 ) -> Result<onchain::state::position::PositionMulti> {
-    let program = program(ctx)?;
     let (pos_pda, _) = pda_position_multi(&market_pda, &user, outcome_idx);
-    let acc: onchain::state::position::PositionMulti = program
-        .account(pos_pda)
+    let data = get_account_data(ctx, pos_pda)
         .map_err(|e| anyhow::anyhow!("position(multi) fetch failed: {e}"))?;
-    Ok(acc)
+    decode_position(&data, onchain::state::position::PositionMulti::SPACE)
 }
 
 /// Fetch config account
 pub fn get_config_account(ctx: &AnchorCtx) -> Result<onchain::state::config::Config> {
-    let program = program(ctx)?;
     let (config_pda, _) = pda_config();
-    let acc: onchain::state::config::Config = program
-        .account(config_pda)
+    let data = get_account_data(ctx, config_pda)
         .map_err(|e| anyhow::anyhow!("config account fetch failed: {e}"))?;
+    // Reads a pre-versioning config too, so the backend keeps working until migrate_config
+    onchain::state::config::Config::decode_any_version(&data)
+        .map_err(|e| anyhow::anyhow!("config account decode failed: {e}"))
+}
+
+/// Fetch faucet config account
+pub fn get_faucet_account(ctx: &AnchorCtx) -> Result<onchain::state::faucet::FaucetConfig> {
+    let program = program(ctx)?;
+    let (faucet_pda, _) = pda_faucet();
+    let acc: onchain::state::faucet::FaucetConfig = program
+        .account(faucet_pda)
+        .map_err(|e| anyhow::anyhow!("faucet account fetch failed: {e}"))?;
     Ok(acc)
 }
 
-/// Fetch market account synchronously
-pub fn get_market_account(ctx: &AnchorCtx, market_pda: Pubkey) -> Result<onchain::state::market::Market> {
+/// Fetch a wallet's faucet claim record (absent until the first draw)
+pub fn get_airdrop_claim_account(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+) -> Result<onchain::state::position::AirdropClaim> {
     let program = program(ctx)?;
-    let acc: onchain::state::market::Market = program
-        .account(market_pda)
-        .map_err(|e| anyhow::anyhow!("market account fetch failed: {e}"))?;
+    let (claim_pda, _) = pda_claim(&user);
+    let acc: onchain::state::position::AirdropClaim = program
+        .account(claim_pda)
+        .map_err(|e| anyhow::anyhow!("airdrop claim fetch failed: {e}"))?;
+    Ok(acc)
+}
+
+/// Fetch AI oracle committee account
+pub fn get_committee_account(
+    ctx: &AnchorCtx,
+    committee_pda: Pubkey,
+) -> Result<onchain::state::committee::OracleCommittee> {
+    let program = program(ctx)?;
+    let acc: onchain::state::committee::OracleCommittee = program
+        .account(committee_pda)
+        .map_err(|e| anyhow::anyhow!("committee account fetch failed: {e}"))?;
     Ok(acc)
 }
 
+/// Fetch market account synchronously. Decodes every layout version, so markets that have
+/// not been through `migrate_market` yet still read with legacy defaults.
+pub fn get_market_account(ctx: &AnchorCtx, market_pda: Pubkey) -> Result<onchain::state::market::Market> {
+    let data = get_account_data(ctx, market_pda)
+        .map_err(|e| anyhow::anyhow!("market account fetch failed: {e}"))?;
+    onchain::state::market::Market::decode_any_version(&data)
+        .map_err(|e| anyhow::anyhow!("market account decode failed: {e}"))
+}
+
+/// Layout version stored in a market account; below `MARKET_VERSION` needs `migrate_market`
+pub fn get_market_version(ctx: &AnchorCtx, market_pda: Pubkey) -> Result<u8> {
+    let data = get_account_data(ctx, market_pda)?;
+    Ok(onchain::state::market::Market::data_version(&data))
+}
+
 /// Fetch market account asynchronously
 pub async fn fetch_market_account(
     ctx: Arc<AnchorCtx>,
@@ -67,9 +134,10 @@ pub async fn fetch_market_account(
 #[derive(Debug, Clone)]
 pub struct MarketSnapshot {
     pub settled: bool,
-    pub winning_side: Option<i16>,      // 1=YES, 2=NO, 3=VOID
+    pub winning_side: Option<i16>,      // 1=YES, 2=NO, 3=VOID, 4=SCALAR
     pub resolved_price_1e6: Option<i64>,
     pub payout_pool_1e6: Option<i64>,
+    pub outcome_idx: Option<i16>,       // winning outcome for multi-outcome markets
 }
 
 /// Extract snapshot from market account
@@ -79,6 +147,7 @@ pub fn snapshot_from_market(m: &onchain::state::market::Market) -> MarketSnapsho
             1 => Some(1), // YES
             2 => Some(2), // NO
             3 => Some(3), // VOID
+            4 => Some(4), // SCALAR: both sides paid by the long/short split
             _ => None,
         }
     } else {
@@ -91,12 +160,15 @@ pub fn snapshot_from_market(m: &onchain::state::market::Market) -> MarketSnapsho
         Some(m.resolved_price_1e6)
     };
     let payout_pool_1e6 = Some(m.payout_pool as i64);
+    let outcome_idx = (m.settled && m.outcome_idx != onchain::constants::OUTCOME_NONE)
+        .then_some(m.outcome_idx as i16);
 
     MarketSnapshot {
         settled: m.settled,
         winning_side,
         resolved_price_1e6,
         payout_pool_1e6,
+        outcome_idx,
     }
 }
 
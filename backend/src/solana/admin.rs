@@ -1,6 +1,6 @@
 use super::{
     context::{AnchorCtx, program},
-    pda::{pda_config, pda_mint_auth, pda_claim},
+    pda::{pda_config, pda_mint_auth, pda_claim, pda_committee, pda_faucet},
 };
 use anchor_client::solana_sdk::{
     pubkey::Pubkey,
@@ -20,10 +20,7 @@ pub fn init_config(
     ctx: &AnchorCtx,
     admin: &Keypair,
     treasury_wallet: Pubkey,
-    fee_bps: u16,
-    resolver_bps: u16,
-    creator_bps: u16,
-    resolver_tip_cap: u64,
+    p: onchain::instructions::InitConfigParams,
 ) -> Result<Signature> {
     let program = program(ctx)?;
     let (config_pda, _) = pda_config();
@@ -36,12 +33,7 @@ pub fn init_config(
             config: config_pda,
             system_program: system_program::ID,
         })
-        .args(onchain::instruction::InitConfig {
-            fee_bps,
-            resolver_bps,
-            creator_bps,
-            resolver_tip_cap,
-        })
+        .args(onchain::instruction::InitConfig { p })
         .signer(admin)
         .send()?;
     
@@ -52,11 +44,7 @@ pub fn init_config(
 pub fn update_config(
     ctx: &AnchorCtx,
     admin: &Keypair,
-    fee_bps: Option<u16>,
-    resolver_bps: Option<u16>,
-    creator_bps: Option<u16>,
-    resolver_tip_cap: Option<u64>,
-    new_treasury: Option<Pubkey>,
+    p: onchain::instructions::UpdateConfigParams,
 ) -> Result<Signature> {
     let program = program(ctx)?;
     let (config_pda, _) = pda_config();
@@ -67,13 +55,7 @@ pub fn update_config(
             admin: admin.pubkey(),
             config: config_pda,
         })
-        .args(onchain::instruction::UpdateConfig {
-            fee_bps,
-            resolver_bps,
-            creator_bps,
-            resolver_tip_cap,
-            new_treasury,
-        })
+        .args(onchain::instruction::UpdateConfig { p })
         .signer(admin)
         .send()?;
 
@@ -101,30 +83,246 @@ pub fn close_config(
     Ok(sig)
 }
 
-/// Airdrop USDC once per user (devnet only)
-pub fn airdrop_usdc_once(ctx: &AnchorCtx, user: Pubkey) -> Result<Signature> {
+/// Add a mint to the collateral allowlist
+pub fn add_collateral_mint(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    mint: Pubkey,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::AddCollateralMint {
+            admin: admin.pubkey(),
+            config: config_pda,
+            mint,
+        })
+        .args(onchain::instruction::AddCollateralMint {})
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Remove a mint from the collateral allowlist
+pub fn remove_collateral_mint(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    mint: Pubkey,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::UpdateConfig {
+            admin: admin.pubkey(),
+            config: config_pda,
+        })
+        .args(onchain::instruction::RemoveCollateralMint { mint })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Propose a new admin; it takes over once it calls accept_admin
+pub fn propose_admin(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    new_admin: Pubkey,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::UpdateConfig {
+            admin: admin.pubkey(),
+            config: config_pda,
+        })
+        .args(onchain::instruction::ProposeAdmin { new_admin })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Accept a pending admin handover, signed by the proposed admin
+pub fn accept_admin(
+    ctx: &AnchorCtx,
+    new_admin: &Keypair,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::AcceptAdmin {
+            new_admin: new_admin.pubkey(),
+            config: config_pda,
+        })
+        .args(onchain::instruction::AcceptAdmin {})
+        .signer(new_admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Set the global pause bits (betting, creation, claims)
+pub fn set_paused(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    paused: u8,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::UpdateConfig {
+            admin: admin.pubkey(),
+            config: config_pda,
+        })
+        .args(onchain::instruction::SetPaused { paused })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Create an AI oracle committee
+pub fn create_committee(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+    let (committee_pda, _) = pda_committee(id);
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::CreateCommittee {
+            admin: admin.pubkey(),
+            config: config_pda,
+            committee: committee_pda,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CreateCommittee { id, members, threshold })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Replace the members and threshold of an AI oracle committee
+pub fn update_committee(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+    let (committee_pda, _) = pda_committee(id);
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::UpdateCommittee {
+            admin: admin.pubkey(),
+            config: config_pda,
+            committee: committee_pda,
+        })
+        .args(onchain::instruction::UpdateCommittee { members, threshold })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Create the faucet config (devnet only)
+pub fn init_faucet(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    amount: u64,
+    cooldown_secs: i64,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+    let (faucet_pda, _) = pda_faucet();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::InitFaucet {
+            admin: admin.pubkey(),
+            config: config_pda,
+            faucet: faucet_pda,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::InitFaucet { amount, cooldown_secs })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Change faucet drip amount and/or cooldown; `None` keeps the current value
+pub fn update_faucet(
+    ctx: &AnchorCtx,
+    admin: &Keypair,
+    amount: Option<u64>,
+    cooldown_secs: Option<i64>,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let (config_pda, _) = pda_config();
+    let (faucet_pda, _) = pda_faucet();
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::UpdateFaucet {
+            admin: admin.pubkey(),
+            config: config_pda,
+            faucet: faucet_pda,
+        })
+        .args(onchain::instruction::UpdateFaucet { amount, cooldown_secs })
+        .signer(admin)
+        .send()?;
+
+    Ok(sig)
+}
+
+/// Airdrop faucet USDC to a user, at most once per faucet cooldown (devnet only)
+pub fn airdrop_usdc(ctx: &AnchorCtx, user: Pubkey) -> Result<Signature> {
     let state = crate::state::global();
     let program = program(ctx)?;
     let mint = Pubkey::from_str(&state.usdc_mint)?;
 
     let (mint_auth_pda, _) = pda_mint_auth();
+    let (faucet_pda, _) = pda_faucet();
     let (claim_pda, _) = pda_claim(&user);
     let user_ata = get_associated_token_address(&user, &mint);
 
     let sig = program
         .request()
-        .accounts(onchain::accounts::AirdropOnce {
+        .accounts(onchain::accounts::Airdrop {
             user,
             payer: ctx.payer.pubkey(),
             mint,
             mint_authority: mint_auth_pda,
+            faucet: faucet_pda,
             user_ata,
             claim: claim_pda,
             token_program: TOKEN_PROGRAM_ID,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
         })
-        .args(onchain::instruction::AirdropOnce {})
+        .args(onchain::instruction::Airdrop {})
         .signer(&*ctx.payer)
         .send()?;
 
@@ -6,7 +6,7 @@ use anchor_client::solana_sdk::{
 use ed25519_dalek::{SigningKey, Signer as DalekSigner};
 
 /// Domain separator for attestations
-const DOMAIN: &[u8] = b"SOLPREDICT_ATTESTATION_v1";
+const DOMAIN: &[u8] = b"SOLPREDICT_ATTESTATION_v2";
 
 /// Build attestation message for single winner
 #[inline]
@@ -17,8 +17,9 @@ pub fn build_message_single_client(
     attest_ts: i64,
     nonce: u64,
     program_id: &Pubkey,
+    question_hash: &[u8; 32],
 ) -> Vec<u8> {
-    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32);
+    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32 + 32);
     v.extend_from_slice(DOMAIN);
     v.extend_from_slice(market.as_ref());
     v.push(outcome_idx);
@@ -26,9 +27,23 @@ pub fn build_message_single_client(
     v.extend_from_slice(&attest_ts.to_le_bytes());
     v.extend_from_slice(&nonce.to_le_bytes());
     v.extend_from_slice(program_id.as_ref());
+    v.extend_from_slice(question_hash);
     v
 }
 
+/// Build void attestation message (single-winner layout, outcome byte = 255)
+#[inline]
+pub fn build_message_void_client(
+    market: &Pubkey,
+    end_ts: i64,
+    attest_ts: i64,
+    nonce: u64,
+    program_id: &Pubkey,
+    question_hash: &[u8; 32],
+) -> Vec<u8> {
+    build_message_single_client(market, u8::MAX, end_ts, attest_ts, nonce, program_id, question_hash)
+}
+
 /// Build attestation message for multiple winners (not implemented yet)
 #[inline]
 pub fn build_message_multi_client(
@@ -38,11 +53,12 @@ pub fn build_message_multi_client(
     attest_ts: i64,
     nonce: u64,
     program_id: &Pubkey,
+    question_hash: &[u8; 32],
 ) -> Vec<u8> {
     winners.sort_unstable();
     winners.dedup();
     
-    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + winners.len() + 8 + 8 + 8 + 32);
+    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + winners.len() + 8 + 8 + 8 + 32 + 32);
     v.extend_from_slice(DOMAIN);
     v.extend_from_slice(market.as_ref());
     v.push(winners.len() as u8);
@@ -51,6 +67,7 @@ pub fn build_message_multi_client(
     v.extend_from_slice(&attest_ts.to_le_bytes());
     v.extend_from_slice(&nonce.to_le_bytes());
     v.extend_from_slice(program_id.as_ref());
+    v.extend_from_slice(question_hash);
     v
 }
 
@@ -1,18 +1,21 @@
 use super::{
     context::{AnchorCtx, program},
-    pda::{pda_escrow_auth, pda_escrow_auth_outcome, pda_position, pda_position_multi},
+    pda::{
+        pda_config, pda_escrow_auth, pda_escrow_auth_outcome, pda_position, pda_position_multi,
+        pda_referral_stake,
+    },
     encoding::encode_unsigned_tx,
+    accounts::{get_market_account, get_position_account, get_position_multi_account},
+    migrate::with_migration,
+    token::{ata, token_program_for_mint},
 };
 use anchor_client::solana_sdk::{
     pubkey::Pubkey,
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     transaction::Transaction,
     system_program, sysvar, hash::Hash,
 };
-use anchor_spl::{
-    token::ID as TOKEN_PROGRAM_ID,
-    associated_token::{ID as ASSOCIATED_TOKEN_PROGRAM_ID, get_associated_token_address},
-};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
 use anyhow::Result;
 use std::sync::Arc;
 use prediction_market_program as onchain;
@@ -22,6 +25,21 @@ fn latest_blockhash(program: &anchor_client::Program<Arc<anchor_client::solana_s
     Ok(program.rpc().get_latest_blockhash()?)
 }
 
+/// (referrer, referral stake) accounts for a bet or exit. A position keeps the referrer
+/// it was opened with; a new position takes the wallet's referrer, if any.
+fn referral_accounts(
+    market_pda: &Pubkey,
+    position_referrer: Option<Pubkey>,
+    wallet_referrer: Option<Pubkey>,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    let referrer = match position_referrer {
+        Some(r) if r == Pubkey::default() => None,
+        Some(r) => Some(r),
+        None => wallet_referrer,
+    };
+    (referrer, referrer.map(|r| pda_referral_stake(market_pda, &r).0))
+}
+
 /// Build place bet instructions for binary markets
 pub fn build_place_bet_ixs(
     ctx: &AnchorCtx,
@@ -29,16 +47,24 @@ pub fn build_place_bet_ixs(
     market_pda: Pubkey,
     side_yes: bool,
     amount_1e6: u64,
+    min_shares_out: u64,
+    referrer: Option<Pubkey>,
 ) -> Result<Vec<Instruction>> {
     let program = program(ctx)?;
-    let mint = onchain::constants::USDC_MINT;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, b"yes");
     let (escrow_no, _) = pda_escrow_auth(&market_pda, b"no");
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
-    let user_ata = get_associated_token_address(&user_pubkey, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
     let (position_pda, _) = pda_position(&market_pda, &user_pubkey);
+    let position_referrer = get_position_account(ctx, market_pda, user_pubkey)
+        .ok()
+        .map(|p| p.referrer);
+    let (referrer, referral_stake) = referral_accounts(&market_pda, position_referrer, referrer);
 
     let side = if side_yes {
         onchain::types::Side::Yes
@@ -58,18 +84,22 @@ pub fn build_place_bet_ixs(
             escrow_authority_no: escrow_no,
             escrow_vault_yes: vault_yes,
             escrow_vault_no: vault_no,
-            token_program: TOKEN_PROGRAM_ID,
+            config: pda_config().0,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
             rent: sysvar::rent::ID,
+            referrer,
+            referral_stake,
         })
         .args(onchain::instruction::PlaceBet {
             side,
             amount: amount_1e6,
+            min_shares_out,
         })
         .instructions()?;
 
-    Ok(ixs)
+    with_migration(ctx, user_pubkey, market_pda, &[position_pda], ixs)
 }
 
 /// Build place bet instructions for multi-outcome markets
@@ -79,16 +109,23 @@ pub fn build_place_bet_multi_ixs(
     market_pda: Pubkey,
     outcome_idx: u8,
     amount_1e6: u64,
+    referrer: Option<Pubkey>,
 ) -> Result<Vec<Instruction>> {
     let program = program(ctx)?;
-    let mint = onchain::constants::USDC_MINT;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let (escrow_auth_outcome, _) = pda_escrow_auth_outcome(&market_pda, outcome_idx);
-    let escrow_vault_for_outcome = get_associated_token_address(&escrow_auth_outcome, &mint);
-    let user_ata = get_associated_token_address(&user_pubkey, &mint);
+    let escrow_vault_for_outcome = ata(&escrow_auth_outcome, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
     let (position_pda, _) = pda_position_multi(&market_pda, &user_pubkey, outcome_idx);
+    let position_referrer = get_position_multi_account(ctx, market_pda, user_pubkey, outcome_idx)
+        .ok()
+        .map(|p| p.referrer);
+    let (referrer, referral_stake) = referral_accounts(&market_pda, position_referrer, referrer);
 
-    let ixs = program
+    let mut req = program
         .request()
         .accounts(onchain::accounts::PlaceBetMulti {
             user: user_pubkey,
@@ -98,18 +135,28 @@ pub fn build_place_bet_multi_ixs(
             escrow_authority_outcome: escrow_auth_outcome,
             escrow_vault_for_outcome,
             position: position_pda,
-            token_program: TOKEN_PROGRAM_ID,
+            config: pda_config().0,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
             rent: sysvar::rent::ID,
+            referrer,
+            referral_stake,
         })
         .args(onchain::instruction::PlaceBetMulti {
             outcome_idx,
             amount: amount_1e6,
-        })
-        .instructions()?;
+        });
+    // The program sums the wallet's other outcome positions against max_per_wallet
+    if market.max_per_wallet > 0 {
+        for i in (0..market.num_outcomes).filter(|&i| i != outcome_idx) {
+            let (other, _) = pda_position_multi(&market_pda, &user_pubkey, i);
+            req = req.accounts(AccountMeta::new_readonly(other, false));
+        }
+    }
+    let ixs = req.instructions()?;
 
-    Ok(ixs)
+    with_migration(ctx, user_pubkey, market_pda, &[position_pda], ixs)
 }
 
 /// Build unsigned place bet transaction for multi-outcome markets
@@ -119,9 +166,114 @@ pub fn build_place_bet_multi_unsigned(
     market_pda: Pubkey,
     outcome_idx: u8,
     amount_1e6: u64,
+    referrer: Option<Pubkey>,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let ixs = build_place_bet_multi_ixs(ctx, user_pubkey, market_pda, outcome_idx, amount_1e6, referrer)?;
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build unsigned exit transaction for binary markets
+pub fn build_exit_position_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    market_pda: Pubkey,
+    side_yes: bool,
+    amount_1e6: u64,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    let (escrow_yes, _) = pda_escrow_auth(&market_pda, b"yes");
+    let (escrow_no, _) = pda_escrow_auth(&market_pda, b"no");
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
+    let (position_pda, _) = pda_position(&market_pda, &user_pubkey);
+    let position = get_position_account(ctx, market_pda, user_pubkey)?;
+    let (_, referral_stake) = referral_accounts(&market_pda, Some(position.referrer), None);
+
+    let side = if side_yes {
+        onchain::types::Side::Yes
+    } else {
+        onchain::types::Side::No
+    };
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::ExitPosition {
+            user: user_pubkey,
+            market: market_pda,
+            mint,
+            user_ata,
+            escrow_authority_yes: escrow_yes,
+            escrow_authority_no: escrow_no,
+            escrow_vault_yes: vault_yes,
+            escrow_vault_no: vault_no,
+            position: position_pda,
+            config: pda_config().0,
+            token_program,
+            referral_stake,
+        })
+        .args(onchain::instruction::ExitPosition {
+            side,
+            amount: amount_1e6,
+        })
+        .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[position_pda], ixs)?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build unsigned exit transaction for multi-outcome markets
+pub fn build_exit_position_multi_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    market_pda: Pubkey,
+    outcome_idx: u8,
+    amount_1e6: u64,
 ) -> Result<String> {
     let program = program(ctx)?;
-    let ixs = build_place_bet_multi_ixs(ctx, user_pubkey, market_pda, outcome_idx, amount_1e6)?;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    let (escrow_auth_outcome, _) = pda_escrow_auth_outcome(&market_pda, outcome_idx);
+    let escrow_vault_for_outcome = ata(&escrow_auth_outcome, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
+    let (position_pda, _) = pda_position_multi(&market_pda, &user_pubkey, outcome_idx);
+    let position = get_position_multi_account(ctx, market_pda, user_pubkey, outcome_idx)?;
+    let (_, referral_stake) = referral_accounts(&market_pda, Some(position.referrer), None);
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::ExitPositionMulti {
+            user: user_pubkey,
+            market: market_pda,
+            mint,
+            user_ata,
+            escrow_authority_outcome: escrow_auth_outcome,
+            escrow_vault_for_outcome,
+            position: position_pda,
+            config: pda_config().0,
+            token_program,
+            referral_stake,
+        })
+        .args(onchain::instruction::ExitPositionMulti {
+            outcome_idx,
+            amount: amount_1e6,
+        })
+        .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[position_pda], ixs)?;
+
     let bh = latest_blockhash(&program)?;
     let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
     tx.message.recent_blockhash = bh;
@@ -0,0 +1,258 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::{pda_config, pda_escrow_auth, pda_escrow_auth_outcome, pda_position, pda_position_multi, SIDE_YES, SIDE_NO},
+    encoding::encode_unsigned_versioned_tx,
+    accounts::{get_market_account, get_position_account, get_position_multi_account},
+    migrate::migrate_ixs_if_needed,
+    token::{ata, token_program_for_mint},
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    instruction::{Instruction, AccountMeta},
+    transaction::{Transaction, VersionedTransaction},
+    message::{v0, Message, VersionedMessage},
+    address_lookup_table::{instruction as alt_ix, AddressLookupTableAccount},
+    compute_budget::ComputeBudgetInstruction,
+    commitment_config::CommitmentConfig,
+    packet::PACKET_DATA_SIZE,
+    signature::Signature,
+    system_program,
+};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use prediction_market_program as onchain;
+
+/// Compute budget requested per claimed position (two PDA derivations + transfer CPI)
+const CU_PER_CLAIM: u32 = 40_000;
+const CU_BASE: u32 = 60_000;
+/// Compute budget for one prepended `migrate_market` (realloc + rent top-up)
+const CU_PER_MIGRATION: u32 = 20_000;
+/// Keeps a v0 claim tx under the 64 account-lock limit with the shared accounts
+const MAX_CLAIMS_PER_V0_TX: usize = 12;
+/// Addresses per `extend_lookup_table` tx
+const ALT_EXTEND_CHUNK: usize = 20;
+
+/// A settled position the wallet may be able to claim; `outcome_idx` is set for multi-outcome markets
+pub struct ClaimCandidate {
+    pub market: Pubkey,
+    pub outcome_idx: Option<u8>,
+}
+
+/// Unsigned claim txs for a wallet, plus the positions that could not be batched.
+/// `txs_base64` must be sent in order: lookup table setup (if any) comes first.
+pub struct ClaimManyBundle {
+    pub txs_base64: Vec<String>,
+    pub lookup_table: Option<Pubkey>,
+    pub claimed: Vec<Pubkey>,
+    pub skipped: Vec<Pubkey>,
+}
+
+/// (market, position, vault, escrow authority) for one claimed position
+type ClaimGroup = [AccountMeta; 4];
+
+/// One claimable position and the `migrate_market` it needs first, if any
+struct Claimable {
+    group: ClaimGroup,
+    migrate: Vec<Instruction>,
+}
+
+/// Build `claim_many` transactions for the given candidates, one set per collateral mint.
+/// Positions that fit in a single legacy tx go out as-is; larger batches get an address
+/// lookup table so each v0 tx can carry more claims. The wallet pays for everything: the
+/// lookup table is created with the wallet as payer and authority (it can close it later for
+/// the rent), and legacy accounts are migrated by instructions in the claim txs themselves.
+pub fn build_claim_many_unsigned(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+    candidates: &[ClaimCandidate],
+) -> Result<ClaimManyBundle> {
+    let mut by_mint: BTreeMap<Pubkey, Vec<Claimable>> = BTreeMap::new();
+    let mut claimed = Vec::new();
+    let mut skipped = Vec::new();
+
+    for c in candidates {
+        match claim_group(ctx, user, c)? {
+            Some((mint, claimable)) => {
+                by_mint.entry(mint).or_default().push(claimable);
+                claimed.push(c.market);
+            }
+            None => skipped.push(c.market),
+        }
+    }
+
+    let mut txs_base64 = Vec::new();
+    let mut lookup_table = None;
+
+    for (mint, claimables) in by_mint {
+        let token_program = token_program_for_mint(ctx, &mint)?;
+        let user_ata = ata(&user, &mint, &token_program);
+        let base_ixs = |cs: &[Claimable]| claim_many_ixs(ctx, user, mint, user_ata, token_program, cs);
+
+        let bh = program(ctx)?.rpc().get_latest_blockhash()?;
+
+        // Everything for this mint in one legacy tx when it fits
+        let ixs = base_ixs(&claimables)?;
+        let legacy = Message::new_with_blockhash(&ixs, Some(&user), &bh);
+        if legacy_tx_size(&legacy) <= PACKET_DATA_SIZE && claimables.len() <= MAX_CLAIMS_PER_V0_TX {
+            let tx = Transaction::new_unsigned(legacy);
+            txs_base64.push(encode_unsigned_versioned_tx(&VersionedTransaction::from(tx))?);
+            continue;
+        }
+
+        let addresses: Vec<Pubkey> = claimables
+            .iter()
+            .flat_map(|c| c.group.iter().map(|m| m.pubkey))
+            .collect();
+        let (alt, setup_txs) = lookup_table_txs(ctx, user, &addresses)?;
+        txs_base64.extend(setup_txs);
+        lookup_table = Some(alt.key);
+
+        for chunk in claimables.chunks(MAX_CLAIMS_PER_V0_TX) {
+            let ixs = base_ixs(chunk)?;
+            let msg = v0::Message::try_compile(&user, &ixs, std::slice::from_ref(&alt), bh)
+                .context("compile v0 claim message failed")?;
+            let msg = VersionedMessage::V0(msg);
+            let tx = VersionedTransaction {
+                signatures: vec![Signature::default(); msg.header().num_required_signatures as usize],
+                message: msg,
+            };
+            txs_base64.push(encode_unsigned_versioned_tx(&tx)?);
+        }
+    }
+
+    Ok(ClaimManyBundle { txs_base64, lookup_table, claimed, skipped })
+}
+
+/// Accounts for one position, or None when the position has nothing `claim_many` can pay.
+/// Void binary positions with stakes on both sides need both vaults and go through `claim`.
+fn claim_group(ctx: &AnchorCtx, user: Pubkey, c: &ClaimCandidate) -> Result<Option<(Pubkey, Claimable)>> {
+    let market = get_market_account(ctx, c.market)?;
+    if !market.settled {
+        return Ok(None);
+    }
+    let void = market.winning_side == onchain::constants::WINNING_SIDE_VOID;
+    let token_program = token_program_for_mint(ctx, &market.mint)?;
+
+    let (position, auth) = match c.outcome_idx {
+        Some(idx) if market.uses_outcome_vaults() => {
+            let Ok(pos) = get_position_multi_account(ctx, c.market, user, idx) else {
+                return Ok(None);
+            };
+            let is_single = market.winners_mask == 0 && market.outcome_idx != u8::MAX;
+            let winner = if is_single {
+                market.outcome_idx == idx
+            } else {
+                market.winners_mask & (1 << idx) != 0
+            };
+            if pos.claimed || pos.stake == 0 || !(void || winner) {
+                return Ok(None);
+            }
+            (pda_position_multi(&c.market, &user, idx).0, pda_escrow_auth_outcome(&c.market, idx).0)
+        }
+        None if market.is_price_binary() => {
+            let Ok(pos) = get_position_account(ctx, c.market, user) else {
+                return Ok(None);
+            };
+            if pos.claimed {
+                return Ok(None);
+            }
+            let side = match (market.winning_side, pos.yes_bet > 0, pos.no_bet > 0) {
+                (1, true, _) => SIDE_YES,
+                (2, _, true) => SIDE_NO,
+                (3, true, false) => SIDE_YES,
+                (3, false, true) => SIDE_NO,
+                _ => return Ok(None),
+            };
+            (pda_position(&c.market, &user).0, pda_escrow_auth(&c.market, side).0)
+        }
+        _ => return Ok(None),
+    };
+
+    // One legacy-layout position fails the whole claim_many tx, so migrate it in the same tx
+    let migrate = migrate_ixs_if_needed(ctx, user, c.market, &[position])?;
+
+    let vault = ata(&auth, &market.mint, &token_program);
+    Ok(Some((
+        market.mint,
+        Claimable {
+            group: [
+                AccountMeta::new_readonly(c.market, false),
+                AccountMeta::new(position, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(auth, false),
+            ],
+            migrate,
+        },
+    )))
+}
+
+fn claim_many_ixs(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+    mint: Pubkey,
+    user_ata: Pubkey,
+    token_program: Pubkey,
+    claimables: &[Claimable],
+) -> Result<Vec<Instruction>> {
+    let program = program(ctx)?;
+    let migrations: Vec<&Instruction> = claimables.iter().flat_map(|c| &c.migrate).collect();
+    let mut req = program
+        .request()
+        .instruction(ComputeBudgetInstruction::set_compute_unit_limit(
+            CU_BASE
+                + CU_PER_CLAIM * claimables.len() as u32
+                + CU_PER_MIGRATION * migrations.len() as u32,
+        ));
+    for ix in migrations {
+        req = req.instruction(ix.clone());
+    }
+    req = req
+        .accounts(onchain::accounts::ClaimMany {
+            user,
+            mint,
+            user_ata,
+            config: pda_config().0,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::ClaimMany {});
+    for meta in claimables.iter().flat_map(|c| &c.group) {
+        req = req.accounts(meta.clone());
+    }
+    Ok(req.instructions()?)
+}
+
+/// Wire size of a legacy tx: signature count + signatures + message
+fn legacy_tx_size(msg: &Message) -> usize {
+    1 + 64 * msg.header.num_required_signatures as usize + msg.serialize().len()
+}
+
+/// Unsigned txs creating and filling a lookup table with `user` as payer and authority.
+/// The create must land while its recent slot is still in SlotHashes (~512 slots).
+fn lookup_table_txs(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+    addresses: &[Pubkey],
+) -> Result<(AddressLookupTableAccount, Vec<String>)> {
+    let program = program(ctx)?;
+    let rpc = program.rpc();
+
+    let recent_slot = rpc
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .context("get_slot failed")?;
+    let (create_ix, key) = alt_ix::create_lookup_table(user, user, recent_slot);
+    let bh = rpc.get_latest_blockhash()?;
+
+    let mut txs = Vec::new();
+    let mut pending = vec![create_ix];
+    for chunk in addresses.chunks(ALT_EXTEND_CHUNK) {
+        pending.push(alt_ix::extend_lookup_table(key, user, Some(user), chunk.to_vec()));
+        let tx = Transaction::new_unsigned(Message::new_with_blockhash(&pending, Some(&user), &bh));
+        txs.push(encode_unsigned_versioned_tx(&VersionedTransaction::from(tx))?);
+        pending.clear();
+    }
+
+    Ok((AddressLookupTableAccount { key, addresses: addresses.to_vec() }, txs))
+}
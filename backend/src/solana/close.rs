@@ -0,0 +1,157 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::{pda_escrow_auth, pda_escrow_auth_outcome, pda_position, pda_position_multi, SIDE_YES, SIDE_NO, SIDE_DISPUTE, SIDE_REFERRAL},
+    encoding::encode_unsigned_tx,
+    accounts::get_market_account,
+    migrate::with_migration,
+    token::{ata, token_program_for_mint},
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    instruction::AccountMeta,
+    transaction::Transaction,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+};
+use anyhow::Result;
+use std::sync::Arc;
+use prediction_market_program as onchain;
+
+/// Get latest blockhash
+fn latest_blockhash(program: &anchor_client::Program<Arc<anchor_client::solana_sdk::signature::Keypair>>) -> Result<Hash> {
+    Ok(program.rpc().get_latest_blockhash()?)
+}
+
+/// Build close position transaction; `outcome_idx` is only used on multi-outcome markets
+pub fn build_close_position_unsigned(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+    market_pda: Pubkey,
+    outcome_idx: u8,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+    if !market.settled {
+        anyhow::bail!("market is not settled");
+    }
+
+    let position = if market.uses_outcome_vaults() {
+        pda_position_multi(&market_pda, &user, outcome_idx).0
+    } else {
+        pda_position(&market_pda, &user).0
+    };
+
+    let ixs = if market.uses_outcome_vaults() {
+        program
+            .request()
+            .accounts(onchain::accounts::ClosePositionMulti {
+                user,
+                market: market_pda,
+                position,
+            })
+            .args(onchain::instruction::ClosePositionMulti { outcome_idx })
+            .instructions()?
+    } else {
+        program
+            .request()
+            .accounts(onchain::accounts::ClosePosition {
+                user,
+                market: market_pda,
+                position,
+            })
+            .args(onchain::instruction::ClosePosition {})
+            .instructions()?
+    };
+    let ixs = with_migration(ctx, user, market_pda, &[position], ixs)?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build close market transaction for the market authority; every escrow the market can
+/// own is passed with its vault, in program order, so leftover dust gets swept and closed
+pub fn build_close_market_unsigned(
+    ctx: &AnchorCtx,
+    authority: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+    if market.authority != authority {
+        anyhow::bail!("only the market authority can close it");
+    }
+    if !market.settled {
+        anyhow::bail!("market is not settled");
+    }
+    if market.positions_untracked {
+        anyhow::bail!("market predates position counting and cannot be closed");
+    }
+    if market.open_positions > 0 {
+        anyhow::bail!("market still has {} open positions", market.open_positions);
+    }
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let treasury_ata = ata(&market.treasury_wallet_snapshot, &mint, &token_program);
+
+    let mut escrows = vec![
+        pda_escrow_auth(&market_pda, SIDE_DISPUTE).0,
+        pda_escrow_auth(&market_pda, SIDE_REFERRAL).0,
+    ];
+    if market.uses_outcome_vaults() {
+        escrows.extend((0..market.num_outcomes).map(|i| pda_escrow_auth_outcome(&market_pda, i).0));
+    } else {
+        escrows.push(pda_escrow_auth(&market_pda, SIDE_YES).0);
+        escrows.push(pda_escrow_auth(&market_pda, SIDE_NO).0);
+    }
+
+    // Void refunds leave the exit fees behind; the program sweeps them from the stake vaults
+    let mut unclaimable_fees = if market.winning_side == onchain::constants::WINNING_SIDE_VOID {
+        market.exit_fee_pool
+    } else {
+        0
+    };
+    let mut remaining: Vec<AccountMeta> = Vec::with_capacity(escrows.len() * 2);
+    for (i, auth) in escrows.into_iter().enumerate() {
+        let vault = ata(&auth, &mint, &token_program);
+        let exists = program
+            .rpc()
+            .get_account_with_commitment(&vault, CommitmentConfig::processed())?
+            .value
+            .is_some();
+        if exists {
+            let amount: u64 = program.rpc().get_token_account_balance(&vault)?.amount.parse()?;
+            let over = amount.saturating_sub(onchain::constants::MAX_CLOSE_DUST);
+            // The first two escrows are the dispute bond and referral vaults
+            if over > 0 && (i < 2 || over > unclaimable_fees) {
+                anyhow::bail!("escrow vault {vault} still holds {amount} unclaimed base units");
+            }
+            unclaimable_fees -= over;
+        }
+        remaining.push(AccountMeta::new_readonly(auth, false));
+        remaining.push(AccountMeta::new(vault, false));
+    }
+
+    let mut req = program
+        .request()
+        .accounts(onchain::accounts::CloseMarket {
+            authority,
+            market: market_pda,
+            rent_payer: market.rent_refund(),
+            mint,
+            treasury_ata,
+            token_program,
+        })
+        .args(onchain::instruction::CloseMarket {});
+    for meta in remaining {
+        req = req.accounts(meta);
+    }
+    let ixs = req.instructions()?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&authority));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
@@ -0,0 +1,81 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::{pda_config, pda_escrow_auth, pda_escrow_auth_outcome, pda_position, pda_position_multi, SIDE_YES, SIDE_NO},
+    accounts::get_market_account,
+    migrate::ensure_migrated,
+    token::{ata, token_program_for_mint},
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    instruction::AccountMeta,
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Signature, Signer},
+    system_program,
+};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
+use anyhow::Result;
+use prediction_market_program as onchain;
+
+/// Owners per crank tx; each adds (position, owner, owner ATA) and an ATA-create CPI
+pub const MAX_CRANK_BATCH: usize = 6;
+const CU_PER_PAYOUT: u32 = 60_000;
+const CU_BASE: u32 = 40_000;
+
+/// Push winnings from one vault to `owners`, signed and paid by the backend keypair.
+/// `outcome_idx` selects the outcome vault on multi-outcome markets; None for binary.
+pub fn crank_payout(
+    ctx: &AnchorCtx,
+    market_pda: Pubkey,
+    outcome_idx: Option<u8>,
+    owners: &[Pubkey],
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let cranker = &*ctx.payer;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    let escrow_authority = match outcome_idx {
+        Some(idx) => pda_escrow_auth_outcome(&market_pda, idx).0,
+        None if market.winning_side == 1 => pda_escrow_auth(&market_pda, SIDE_YES).0,
+        None => pda_escrow_auth(&market_pda, SIDE_NO).0,
+    };
+
+    let mut req = program
+        .request()
+        .instruction(ComputeBudgetInstruction::set_compute_unit_limit(
+            CU_BASE + CU_PER_PAYOUT * owners.len() as u32,
+        ))
+        .accounts(onchain::accounts::CrankPayout {
+            cranker: cranker.pubkey(),
+            market: market_pda,
+            mint,
+            cranker_ata: ata(&cranker.pubkey(), &mint, &token_program),
+            escrow_authority,
+            escrow_vault: ata(&escrow_authority, &mint, &token_program),
+            config: pda_config().0,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CrankPayout { outcome_idx: outcome_idx.unwrap_or(0) });
+
+    let positions: Vec<Pubkey> = owners
+        .iter()
+        .map(|owner| match outcome_idx {
+            Some(idx) => pda_position_multi(&market_pda, owner, idx).0,
+            None => pda_position(&market_pda, owner).0,
+        })
+        .collect();
+    // Positions on an older layout would fail the whole batch; bring them current first
+    ensure_migrated(ctx, market_pda, &positions)?;
+
+    for (owner, position) in owners.iter().zip(positions) {
+        req = req
+            .accounts(AccountMeta::new(position, false))
+            .accounts(AccountMeta::new_readonly(*owner, false))
+            .accounts(AccountMeta::new(ata(owner, &mint, &token_program), false));
+    }
+
+    Ok(req.signer(cranker).send()?)
+}
@@ -1,5 +1,5 @@
 use anchor_client::solana_sdk::{
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
     pubkey::Pubkey,
 };
 use anyhow::{Context, Result};
@@ -14,6 +14,13 @@ pub fn encode_unsigned_tx(tx: &Transaction) -> Result<String> {
     Ok(general_purpose::STANDARD.encode(bytes))
 }
 
+/// Encode unsigned versioned (legacy or v0) transaction to base64 string for client signing
+pub fn encode_unsigned_versioned_tx(tx: &VersionedTransaction) -> Result<String> {
+    let bytes = encode_to_vec(tx, standard())
+        .map_err(|e| anyhow::anyhow!("bincode encode failed: {e}"))?;
+    Ok(general_purpose::STANDARD.encode(bytes))
+}
+
 /// Parse Pubkey from string with better error message
 pub fn parse_pubkey(s: &str) -> Result<Pubkey> {
     Pubkey::from_str(s).context("invalid pubkey string")
@@ -0,0 +1,154 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::{pda_config, pda_escrow_auth, pda_lp_position, SIDE_YES, SIDE_NO},
+    encoding::encode_unsigned_tx,
+    accounts::get_market_account,
+    migrate::with_migration,
+    token::{ata, token_program_for_mint},
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    instruction::Instruction,
+    transaction::Transaction,
+    system_program,
+};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
+use anyhow::Result;
+use prediction_market_program as onchain;
+
+/// Accounts every liquidity instruction touches on a binary market
+struct LpAccounts {
+    mint: Pubkey,
+    token_program: Pubkey,
+    user_ata: Pubkey,
+    lp_position: Pubkey,
+    escrow_yes: Pubkey,
+    escrow_no: Pubkey,
+    vault_yes: Pubkey,
+    vault_no: Pubkey,
+}
+
+fn lp_accounts(ctx: &AnchorCtx, user_pubkey: Pubkey, market_pda: Pubkey) -> Result<LpAccounts> {
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
+    let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
+    Ok(LpAccounts {
+        mint,
+        token_program,
+        user_ata: ata(&user_pubkey, &mint, &token_program),
+        lp_position: pda_lp_position(&market_pda, &user_pubkey).0,
+        escrow_yes,
+        escrow_no,
+        vault_yes: ata(&escrow_yes, &mint, &token_program),
+        vault_no: ata(&escrow_no, &mint, &token_program),
+    })
+}
+
+fn unsigned_tx(ctx: &AnchorCtx, user_pubkey: Pubkey, ixs: &[Instruction]) -> Result<String> {
+    let program = program(ctx)?;
+    let mut tx = Transaction::new_with_payer(ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = program.rpc().get_latest_blockhash()?;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build unsigned add_liquidity transaction; `amount_1e6` is split across both sides
+pub fn build_add_liquidity_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    market_pda: Pubkey,
+    amount_1e6: u64,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let a = lp_accounts(ctx, user_pubkey, market_pda)?;
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::AddLiquidity {
+            user: user_pubkey,
+            market: market_pda,
+            mint: a.mint,
+            user_ata: a.user_ata,
+            escrow_authority_yes: a.escrow_yes,
+            escrow_authority_no: a.escrow_no,
+            escrow_vault_yes: a.vault_yes,
+            escrow_vault_no: a.vault_no,
+            lp_position: a.lp_position,
+            config: pda_config().0,
+            token_program: a.token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::AddLiquidity { amount: amount_1e6 })
+        .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[a.lp_position], ixs)?;
+
+    unsigned_tx(ctx, user_pubkey, &ixs)
+}
+
+/// Build unsigned remove_liquidity transaction burning `shares` LP shares
+pub fn build_remove_liquidity_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    market_pda: Pubkey,
+    shares: u64,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let a = lp_accounts(ctx, user_pubkey, market_pda)?;
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::RemoveLiquidity {
+            user: user_pubkey,
+            market: market_pda,
+            mint: a.mint,
+            user_ata: a.user_ata,
+            escrow_authority_yes: a.escrow_yes,
+            escrow_authority_no: a.escrow_no,
+            escrow_vault_yes: a.vault_yes,
+            escrow_vault_no: a.vault_no,
+            lp_position: a.lp_position,
+            config: pda_config().0,
+            token_program: a.token_program,
+        })
+        .args(onchain::instruction::RemoveLiquidity { shares })
+        .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[a.lp_position], ixs)?;
+
+    unsigned_tx(ctx, user_pubkey, &ixs)
+}
+
+/// Build unsigned claim_lp transaction for a settled market
+pub fn build_claim_lp_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let a = lp_accounts(ctx, user_pubkey, market_pda)?;
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::ClaimLp {
+            user: user_pubkey,
+            market: market_pda,
+            mint: a.mint,
+            user_ata: a.user_ata,
+            lp_position: a.lp_position,
+            escrow_authority_yes: a.escrow_yes,
+            escrow_authority_no: a.escrow_no,
+            escrow_vault_yes: a.vault_yes,
+            escrow_vault_no: a.vault_no,
+            config: pda_config().0,
+            token_program: a.token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::ClaimLp {})
+        .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[a.lp_position], ixs)?;
+
+    unsigned_tx(ctx, user_pubkey, &ixs)
+}
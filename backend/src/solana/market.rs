@@ -1,18 +1,20 @@
 use super::{
     context::{AnchorCtx, program},
-    pda::{pda_market, pda_market_ai, pda_escrow_auth, pda_position, pda_config, SIDE_YES, SIDE_NO},
+    pda::{
+        pda_market, pda_market_ai, pda_market_buckets, pda_market_ladder, pda_escrow_auth,
+        pda_escrow_auth_outcome, pda_position, pda_position_multi, pda_lp_position, pda_config,
+        SIDE_YES, SIDE_NO,
+    },
     encoding::encode_unsigned_tx,
+    token::{ata, token_program_for_mint},
 };
 use anchor_client::solana_sdk::{
     pubkey::Pubkey,
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     transaction::Transaction,
     system_program, sysvar, hash::Hash,
 };
-use anchor_spl::{
-    token::ID as TOKEN_PROGRAM_ID,
-    associated_token::{ID as ASSOCIATED_TOKEN_PROGRAM_ID, get_associated_token_address},
-};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
 use anyhow::Result;
 use std::sync::Arc;
 use prediction_market_program as onchain;
@@ -26,21 +28,24 @@ fn latest_blockhash(program: &anchor_client::Program<Arc<anchor_client::solana_s
 pub fn create_market(
     ctx: &AnchorCtx,
     user_pubkey: Pubkey,
+    mint: Pubkey,
     feed_id: [u8; 32],
     market_type: onchain::types::MarketType,
     comparator: u8,
     bound_lo_usd_6: i64,
     bound_hi_usd_6: i64,
     end_ts: i64,
+    limits: onchain::types::StakeLimits,
+    rules: onchain::types::ResolutionRules,
 ) -> Result<String> {
     let program = program(ctx)?;
-    let mint = onchain::constants::USDC_MINT;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let (market_pda, _) = pda_market(&user_pubkey, &feed_id, end_ts);
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
     let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
     let (config_pda, _) = pda_config();
 
     let ixs = program
@@ -54,7 +59,7 @@ pub fn create_market(
             escrow_vault_yes: vault_yes,
             escrow_vault_no: vault_no,
             config: config_pda,
-            token_program: TOKEN_PROGRAM_ID,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
         })
@@ -65,6 +70,8 @@ pub fn create_market(
             bound_hi_usd_6,
             end_ts,
             feed_id,
+            limits,
+            rules,
         })
         .instructions()?;
 
@@ -75,30 +82,95 @@ pub fn create_market(
     encode_unsigned_tx(&tx)
 }
 
-/// Create market with optional seed bet in one transaction
+/// Create Switchboard binary market (unsigned transaction); the market PDA uses the feed account key as its feed id
+pub fn build_create_market_switchboard_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    mint: Pubkey,
+    switchboard_feed: Pubkey,
+    market_type: onchain::types::MarketType,
+    comparator: u8,
+    bound_lo_usd_6: i64,
+    bound_hi_usd_6: i64,
+    end_ts: i64,
+    limits: onchain::types::StakeLimits,
+    rules: onchain::types::ResolutionRules,
+) -> Result<(String, Pubkey)> {
+    let program = program(ctx)?;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    let (market_pda, _) = pda_market(&user_pubkey, &switchboard_feed.to_bytes(), end_ts);
+    let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
+    let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let (config_pda, _) = pda_config();
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::CreateMarketSwitchboard {
+            authority: user_pubkey,
+            market: market_pda,
+            mint,
+            config: config_pda,
+            switchboard_feed,
+            escrow_authority_yes: escrow_yes,
+            escrow_authority_no: escrow_no,
+            escrow_vault_yes: vault_yes,
+            escrow_vault_no: vault_no,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CreateMarketSwitchboard {
+            p: onchain::instructions::market_create::CreateMarketSwitchboardParams {
+                market_type,
+                comparator,
+                bound_lo_usd_6,
+                bound_hi_usd_6,
+                end_ts,
+                limits,
+                rules,
+            },
+        })
+        .instructions()?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = bh;
+
+    Ok((encode_unsigned_tx(&tx)?, market_pda))
+}
+
+/// Create market with optional seed bet (or CPMM seed liquidity) in one transaction.
+/// A parimutuel seed with no `seed_side` goes in as two-sided liquidity via add_liquidity.
 pub fn build_create_and_seed(
     ctx: &AnchorCtx,
     user_pubkey: Pubkey,
+    mint: Pubkey,
     feed_id: [u8; 32],
     market_type: onchain::types::MarketType,
     comparator: u8,
     bound_lo_usd_6: i64,
     bound_hi_usd_6: i64,
     end_ts: i64,
-    seed_side: onchain::types::Side,
+    limits: onchain::types::StakeLimits,
+    rules: onchain::types::ResolutionRules,
+    seed_side: Option<onchain::types::Side>,
     seed_amount: u64,
+    seed_cpmm: bool,
     memo_opt: Option<&[u8]>,
     recent_blockhash: Hash,
 ) -> Result<String> {
     let program = program(ctx)?;
-    let mint = onchain::constants::USDC_MINT;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let (market_pda, _) = pda_market(&user_pubkey, &feed_id, end_ts);
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
     let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
-    let user_ata = get_associated_token_address(&user_pubkey, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
     let (position_pda, _) = pda_position(&market_pda, &user_pubkey);
     let (config_pda, _) = pda_config();
 
@@ -114,7 +186,7 @@ pub fn build_create_and_seed(
             escrow_vault_yes: vault_yes,
             escrow_vault_no: vault_no,
             config: config_pda,
-            token_program: TOKEN_PROGRAM_ID,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
         })
@@ -125,12 +197,59 @@ pub fn build_create_and_seed(
             bound_hi_usd_6,
             end_ts,
             feed_id,
+            limits,
+            rules,
         })
         .instructions()?;
 
-    // Optional seed bet
-    if seed_amount > 0 {
-        let mut place_ixs = program
+    // Optional seed bet, CPMM curve seed, or two-sided liquidity
+    if seed_amount > 0 && seed_side.is_none() && !seed_cpmm {
+        let mut lp_ixs = program
+            .request()
+            .accounts(onchain::accounts::AddLiquidity {
+                user: user_pubkey,
+                market: market_pda,
+                mint,
+                user_ata,
+                escrow_authority_yes: escrow_yes,
+                escrow_authority_no: escrow_no,
+                escrow_vault_yes: vault_yes,
+                escrow_vault_no: vault_no,
+                lp_position: pda_lp_position(&market_pda, &user_pubkey).0,
+                config: config_pda,
+                token_program,
+                associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+                system_program: system_program::ID,
+            })
+            .args(onchain::instruction::AddLiquidity { amount: seed_amount })
+            .instructions()?;
+
+        ixs.append(&mut lp_ixs);
+    } else if seed_amount > 0 && seed_cpmm {
+        let mut seed_ixs = program
+            .request()
+            .accounts(onchain::accounts::SeedCpmm {
+                user: user_pubkey,
+                market: market_pda,
+                mint,
+                user_ata,
+                escrow_authority_yes: escrow_yes,
+                escrow_authority_no: escrow_no,
+                escrow_vault_yes: vault_yes,
+                escrow_vault_no: vault_no,
+                position: position_pda,
+                config: config_pda,
+                token_program,
+                system_program: system_program::ID,
+            })
+            .args(onchain::instruction::SeedCpmm {
+                initial_liquidity: seed_amount,
+            })
+            .instructions()?;
+
+        ixs.append(&mut seed_ixs);
+    } else if seed_amount > 0 {
+        let mut seed_ixs = program
             .request()
             .accounts(onchain::accounts::PlaceBet {
                 user: user_pubkey,
@@ -142,18 +261,22 @@ pub fn build_create_and_seed(
                 escrow_vault_yes: vault_yes,
                 escrow_vault_no: vault_no,
                 position: position_pda,
-                token_program: TOKEN_PROGRAM_ID,
+                config: config_pda,
+                token_program,
                 associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
                 system_program: system_program::ID,
                 rent: sysvar::rent::ID,
+                referrer: None,
+                referral_stake: None,
             })
             .args(onchain::instruction::PlaceBet {
-                side: seed_side,
+                side: seed_side.unwrap_or(onchain::types::Side::Yes),
                 amount: seed_amount,
+                min_shares_out: 0,
             })
             .instructions()?;
 
-        ixs.append(&mut place_ixs);
+        ixs.append(&mut seed_ixs);
     }
 
     // Optional memo
@@ -171,8 +294,13 @@ pub fn build_create_and_seed(
 pub fn build_create_market_ai_binary_unsigned(
     ctx: &AnchorCtx,
     authority: Pubkey,
+    mint: Pubkey,
     end_ts: i64,
     ai_oracle_authority: Pubkey,
+    oracle_committee: Option<Pubkey>,
+    limits: onchain::types::StakeLimits,
+    question_hash: [u8; 32],
+    spec_base_url: Option<&str>,
     memo_opt: Option<&str>,
 ) -> Result<(String, Pubkey)> {
     let program = program(ctx)?;
@@ -188,13 +316,19 @@ pub fn build_create_market_ai_binary_unsigned(
 
     let (market_pda, _) = pda_market_ai(&authority, end_ts, oracle_kind, &salt);
     let (config_pda, _) = pda_config();
+    // Spec is served per market, so the URI can only be built once the PDA is known
+    let metadata_uri = spec_base_url
+        .map(|base| format!("{}/v1/markets/{market_pda}/spec", base.trim_end_matches('/')))
+        .unwrap_or_default();
 
     let ixs_main = program
         .request()
         .accounts(onchain::accounts::CreateMarketMulti {
             authority,
             market: market_pda,
+            mint,
             config: config_pda,
+            oracle_committee,
             system_program: system_program::ID,
         })
         .args(onchain::instruction::CreateMarketMulti {
@@ -204,6 +338,9 @@ pub fn build_create_market_ai_binary_unsigned(
                 end_ts,
                 ai_oracle_authority,
                 salt,
+                limits,
+                question_hash,
+                metadata_uri,
             }
         })
         .instructions()?;
@@ -220,4 +357,175 @@ pub fn build_create_market_ai_binary_unsigned(
 
     let tx_b64 = encode_unsigned_tx(&tx)?;
     Ok((tx_b64, market_pda))
-}
\ No newline at end of file
+}
+
+/// Create Pyth bucket market with optional seed bet into one bucket (unsigned transaction)
+pub fn build_create_buckets_and_seed(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    mint: Pubkey,
+    feed_id: [u8; 32],
+    end_ts: i64,
+    bounds_usd_6: Vec<i64>,
+    limits: onchain::types::StakeLimits,
+    rules: onchain::types::ResolutionRules,
+    seed_bucket: u8,
+    seed_amount: u64,
+    memo_opt: Option<&[u8]>,
+    recent_blockhash: Hash,
+) -> Result<(String, Pubkey)> {
+    let program = program(ctx)?;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+
+    // Same salt scheme as AI markets: lets one creator open several bucket sets per feed/end
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap();
+    let mut salt = [0u8; 8];
+    salt.copy_from_slice(&(now.as_nanos() as u64).to_le_bytes());
+
+    let (market_pda, _) = pda_market_buckets(&user_pubkey, end_ts, &feed_id, &salt);
+    let (config_pda, _) = pda_config();
+    let num_buckets = bounds_usd_6.len() as u8 + 1;
+    let wallet_capped = limits.max_per_wallet > 0;
+
+    let mut ixs = program
+        .request()
+        .accounts(onchain::accounts::CreateMarketBuckets {
+            authority: user_pubkey,
+            market: market_pda,
+            mint,
+            config: config_pda,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CreateMarketBuckets {
+            p: onchain::instructions::market_create::CreateMarketBucketsParams {
+                feed_id,
+                end_ts,
+                bounds: bounds_usd_6,
+                salt,
+                limits,
+                rules,
+            },
+        })
+        .instructions()?;
+
+    // Optional seed bet; one bucket only so the webhook indexes a single bet per tx
+    if seed_amount > 0 {
+        let (escrow_auth_outcome, _) = pda_escrow_auth_outcome(&market_pda, seed_bucket);
+        let escrow_vault_for_outcome = ata(&escrow_auth_outcome, &mint, &token_program);
+        let user_ata = ata(&user_pubkey, &mint, &token_program);
+        let (position_pda, _) = pda_position_multi(&market_pda, &user_pubkey, seed_bucket);
+
+        let mut seed_req = program
+            .request()
+            .accounts(onchain::accounts::PlaceBetMulti {
+                user: user_pubkey,
+                market: market_pda,
+                mint,
+                user_ata,
+                escrow_authority_outcome: escrow_auth_outcome,
+                escrow_vault_for_outcome,
+                position: position_pda,
+                config: config_pda,
+                token_program,
+                associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                referrer: None,
+                referral_stake: None,
+            })
+            .args(onchain::instruction::PlaceBetMulti {
+                outcome_idx: seed_bucket,
+                amount: seed_amount,
+            });
+        // The other buckets' positions, which the program sums against max_per_wallet
+        if wallet_capped {
+            for i in (0..num_buckets).filter(|&i| i != seed_bucket) {
+                let (other, _) = pda_position_multi(&market_pda, &user_pubkey, i);
+                seed_req = seed_req.accounts(AccountMeta::new_readonly(other, false));
+            }
+        }
+        let mut seed_ixs = seed_req.instructions()?;
+
+        ixs.append(&mut seed_ixs);
+    }
+
+    if let Some(memo_bytes) = memo_opt {
+        ixs.push(spl_memo::build_memo(memo_bytes, &[]));
+    }
+
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = recent_blockhash;
+
+    let tx_b64 = encode_unsigned_tx(&tx)?;
+    Ok((tx_b64, market_pda))
+}
+
+/// Create a Pyth strike ladder: one threshold market per strike in a single instruction.
+/// Returns the unsigned transaction and the market PDAs in strike order.
+pub fn build_create_ladder_unsigned(
+    ctx: &AnchorCtx,
+    user_pubkey: Pubkey,
+    mint: Pubkey,
+    feed_id: [u8; 32],
+    comparator: u8,
+    end_ts: i64,
+    strikes_usd_6: Vec<i64>,
+    limits: onchain::types::StakeLimits,
+    rules: onchain::types::ResolutionRules,
+    memo_opt: Option<&[u8]>,
+    recent_blockhash: Hash,
+) -> Result<(String, Vec<Pubkey>)> {
+    let program = program(ctx)?;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (config_pda, _) = pda_config();
+
+    let mut market_pdas = Vec::with_capacity(strikes_usd_6.len());
+    let mut remaining: Vec<AccountMeta> = Vec::with_capacity(strikes_usd_6.len() * 5);
+    for i in 0..strikes_usd_6.len() {
+        let (market_pda, _) = pda_market_ladder(&user_pubkey, &feed_id, end_ts, i as u8);
+        let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
+        let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
+        remaining.push(AccountMeta::new(market_pda, false));
+        remaining.push(AccountMeta::new_readonly(escrow_yes, false));
+        remaining.push(AccountMeta::new_readonly(escrow_no, false));
+        remaining.push(AccountMeta::new(ata(&escrow_yes, &mint, &token_program), false));
+        remaining.push(AccountMeta::new(ata(&escrow_no, &mint, &token_program), false));
+        market_pdas.push(market_pda);
+    }
+
+    let mut req = program
+        .request()
+        .accounts(onchain::accounts::CreateMarketLadder {
+            authority: user_pubkey,
+            mint,
+            config: config_pda,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CreateMarketLadder {
+            p: onchain::instructions::market_create::CreateMarketLadderParams {
+                feed_id,
+                comparator,
+                end_ts,
+                strikes: strikes_usd_6,
+                limits,
+                rules,
+            },
+        });
+    for meta in remaining {
+        req = req.accounts(meta);
+    }
+    let mut ixs = req.instructions()?;
+
+    if let Some(memo_bytes) = memo_opt {
+        ixs.push(spl_memo::build_memo(memo_bytes, &[]));
+    }
+
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
+    tx.message.recent_blockhash = recent_blockhash;
+
+    Ok((encode_unsigned_tx(&tx)?, market_pdas))
+}
@@ -0,0 +1,158 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::pda_config,
+};
+use anchor_client::{
+    anchor_lang::Discriminator,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Signature, Signer},
+        system_program,
+    },
+};
+use anyhow::Result;
+use prediction_market_program as onchain;
+use onchain::{
+    constants::{CONFIG_VERSION, MARKET_VERSION, POSITION_VERSION},
+    state::{Config, LpPosition, Market, PositionBin, PositionMulti},
+};
+
+/// Offset of the `version` byte in a position account, by discriminator
+fn position_version_offset(data: &[u8]) -> Option<usize> {
+    let disc = data.get(..8)?;
+    if disc == PositionBin::DISCRIMINATOR {
+        Some(PositionBin::VERSION_OFFSET)
+    } else if disc == PositionMulti::DISCRIMINATOR {
+        Some(PositionMulti::VERSION_OFFSET)
+    } else if disc == LpPosition::DISCRIMINATOR {
+        Some(LpPosition::VERSION_OFFSET)
+    } else {
+        None
+    }
+}
+
+/// Build a `migrate_market` instruction for `market` and the given positions of it
+pub fn build_migrate_market_ix(
+    ctx: &AnchorCtx,
+    payer: Pubkey,
+    market: Pubkey,
+    positions: &[Pubkey],
+) -> Result<Vec<Instruction>> {
+    let program = program(ctx)?;
+
+    let mut req = program
+        .request()
+        .accounts(onchain::accounts::MigrateMarket {
+            payer,
+            market,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::MigrateMarket {});
+    for position in positions {
+        req = req.accounts(AccountMeta::new(*position, false));
+    }
+
+    Ok(req.instructions()?)
+}
+
+/// `migrate_market` for `market` and whichever of `positions` are still on an older layout;
+/// empty when everything is current. Prepended to user transactions so legacy accounts
+/// load under the current program. Positions that do not exist yet are skipped.
+pub fn migrate_ixs_if_needed(
+    ctx: &AnchorCtx,
+    payer: Pubkey,
+    market: Pubkey,
+    positions: &[Pubkey],
+) -> Result<Vec<Instruction>> {
+    let program = program(ctx)?;
+
+    let keys: Vec<Pubkey> = std::iter::once(market).chain(positions.iter().copied()).collect();
+    let accs = program
+        .rpc()
+        .get_multiple_accounts(&keys)
+        .map_err(|e| anyhow::anyhow!("migration check fetch failed: {e}"))?;
+
+    let market_stale = accs[0]
+        .as_ref()
+        .is_some_and(|a| Market::data_version(&a.data) < MARKET_VERSION);
+
+    let stale_positions: Vec<Pubkey> = keys[1..]
+        .iter()
+        .zip(&accs[1..])
+        .filter_map(|(key, acc)| {
+            let data = &acc.as_ref()?.data;
+            let offset = position_version_offset(data)?;
+            (data.get(offset).copied().unwrap_or(0) < POSITION_VERSION).then_some(*key)
+        })
+        .collect();
+
+    if !market_stale && stale_positions.is_empty() {
+        return Ok(Vec::new());
+    }
+    build_migrate_market_ix(ctx, payer, market, &stale_positions)
+}
+
+/// Send instructions paid and signed by the backend payer
+fn send_as_payer(ctx: &AnchorCtx, ixs: Vec<Instruction>) -> Result<Signature> {
+    let program = program(ctx)?;
+    let mut req = program.request();
+    for ix in ixs {
+        req = req.instruction(ix);
+    }
+    Ok(req.signer(&*ctx.payer).send()?)
+}
+
+/// Migrate a market and the given positions, paid and signed by the backend payer
+pub fn migrate_market(ctx: &AnchorCtx, market: Pubkey, positions: &[Pubkey]) -> Result<Signature> {
+    let ixs = build_migrate_market_ix(ctx, ctx.payer.pubkey(), market, positions)?;
+    send_as_payer(ctx, ixs)
+}
+
+/// Migrate whichever of `market` and `positions` are stale, paid by the backend payer;
+/// for backend-built transactions that load many positions at once (batch claims, crank)
+pub fn ensure_migrated(ctx: &AnchorCtx, market: Pubkey, positions: &[Pubkey]) -> Result<Option<Signature>> {
+    let ixs = migrate_ixs_if_needed(ctx, ctx.payer.pubkey(), market, positions)?;
+    if ixs.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(send_as_payer(ctx, ixs)?))
+}
+
+/// `ixs` preceded by whatever `migrate_ixs_if_needed` returns for the accounts they touch
+pub fn with_migration(
+    ctx: &AnchorCtx,
+    payer: Pubkey,
+    market: Pubkey,
+    positions: &[Pubkey],
+    ixs: Vec<Instruction>,
+) -> Result<Vec<Instruction>> {
+    let mut out = migrate_ixs_if_needed(ctx, payer, market, positions)?;
+    out.extend(ixs);
+    Ok(out)
+}
+
+/// Migrate the global config if it is on an older layout, paid and signed by the backend payer.
+/// Run once right after a program upgrade: until then every instruction loading the config fails.
+pub fn ensure_config_migrated(ctx: &AnchorCtx) -> Result<Option<Signature>> {
+    let program = program(ctx)?;
+    let (config, _) = pda_config();
+    let data = program
+        .rpc()
+        .get_account_data(&config)
+        .map_err(|e| anyhow::anyhow!("config account fetch failed: {e}"))?;
+    if Config::data_version(&data) >= CONFIG_VERSION {
+        return Ok(None);
+    }
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::MigrateConfig {
+            payer: ctx.payer.pubkey(),
+            config,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::MigrateConfig {})
+        .instructions()?;
+    Ok(Some(send_as_payer(ctx, ixs)?))
+}
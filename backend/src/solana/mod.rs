@@ -8,43 +8,113 @@ mod admin;
 mod accounts;
 mod attestation;
 mod transactions;
+mod close;
+mod token;
+mod claim_many;
+mod crank;
+mod series;
+mod liquidity;
+mod migrate;
 
 // Re-export main types and functions
 pub use context::{AnchorCtx, connect_devnet, program};
+pub use pda::{pda_committee, pda_lp_position, pda_position, pda_position_multi, pda_series};
 pub use market::{
     create_market, 
     build_create_and_seed,
+    build_create_buckets_and_seed,
     build_create_market_ai_binary_unsigned,
+    build_create_market_switchboard_unsigned,
+    build_create_ladder_unsigned,
 };
 pub use accounts::{
     get_config_account,
+    get_committee_account,
+    get_faucet_account,
+    get_airdrop_claim_account,
     get_market_account,
+    get_market_version,
     fetch_market_account,
     fetch_market_snapshot,
     snapshot_from_market,
     MarketSnapshot,
     get_position_account,
     get_position_multi_account,
+    get_lp_position_account,
 };
 pub use betting::{
     build_place_bet_ixs,
     build_place_bet_multi_ixs,
     build_place_bet_multi_unsigned,
+    build_exit_position_unsigned,
+    build_exit_position_multi_unsigned,
 };
 pub use resolution::{
     build_resolve,
     build_resolve_ix_bundle,
+    build_resolve_switchboard,
     build_claim_ix,
     ai_propose_prepare,
     finalize_ai_unsigned,
+    build_dispute_ai_unsigned,
+    rule_dispute,
+    build_void_ai_market_unsigned,
+    build_expire_dispute_unsigned,
+    build_refund_multi_unsigned,
+    build_claim_referral_unsigned,
+};
+pub use token::{
+    get_mint_info,
+    token_program_for_mint,
+    ui_to_base_units,
+    MintInfo,
+};
+pub use close::{
+    build_close_position_unsigned,
+    build_close_market_unsigned,
 };
 pub use admin::{
     init_config,
     update_config,
     close_config,
-    airdrop_usdc_once,
+    add_collateral_mint,
+    remove_collateral_mint,
+    propose_admin,
+    accept_admin,
+    set_paused,
+    create_committee,
+    update_committee,
+    init_faucet,
+    update_faucet,
+    airdrop_usdc,
     set_token_metadata,
 };
+pub use claim_many::{
+    build_claim_many_unsigned,
+    ClaimCandidate,
+    ClaimManyBundle,
+};
+pub use crank::{
+    crank_payout,
+    MAX_CRANK_BATCH,
+};
+pub use liquidity::{
+    build_add_liquidity_unsigned,
+    build_remove_liquidity_unsigned,
+    build_claim_lp_unsigned,
+};
+pub use migrate::{
+    build_migrate_market_ix,
+    migrate_ixs_if_needed,
+    ensure_migrated,
+    ensure_config_migrated,
+    migrate_market,
+};
+pub use series::{
+    build_create_series_unsigned,
+    get_series_account,
+    roll_series,
+};
 pub use encoding::{
     encode_unsigned_tx,
     parse_pubkey,
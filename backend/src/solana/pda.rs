@@ -7,11 +7,19 @@ const ESCROW_SEED: &[u8] = b"escrow-auth";
 const CONFIG_SEED: &[u8] = b"config";
 const POSITION_SEED: &[u8] = b"position";
 const MINT_AUTH_SEED: &[u8] = b"mint-auth";
-const CLAIM_SEED: &[u8] = b"claim";
+const CLAIM_SEED: &[u8] = b"faucet-claim";
+const FAUCET_SEED: &[u8] = b"faucet";
 const OUTCOME_PREFIX: &[u8] = b"o";
+const COMMITTEE_SEED: &[u8] = b"committee";
+const NONCE_SEED: &[u8] = b"nonce";
+const REFERRAL_SEED: &[u8] = b"referral";
+const SERIES_SEED: &[u8] = b"series";
+const LP_SEED: &[u8] = b"lp";
 
 pub const SIDE_YES: &[u8] = b"yes";
 pub const SIDE_NO: &[u8] = b"no";
+pub const SIDE_DISPUTE: &[u8] = b"dispute";
+pub const SIDE_REFERRAL: &[u8] = b"referral";
 
 /// Derive market PDA for Pyth oracle markets
 pub fn pda_market(user: &Pubkey, feed: &[u8; 32], end_ts: i64) -> (Pubkey, u8) {
@@ -29,6 +37,38 @@ pub fn pda_market_ai(authority: &Pubkey, end_ts: i64, oracle_kind: u8, salt: &[u
     )
 }
 
+/// Derive market PDA for Pyth bucket markets
+pub fn pda_market_buckets(authority: &Pubkey, end_ts: i64, feed: &[u8; 32], salt: &[u8; 8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_SEED, authority.as_ref(), &end_ts.to_le_bytes(), feed.as_ref(), salt.as_ref()],
+        &onchain::ID,
+    )
+}
+
+/// Derive market PDA for one strike of a Pyth strike ladder
+pub fn pda_market_ladder(authority: &Pubkey, feed: &[u8; 32], end_ts: i64, strike_idx: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_SEED, authority.as_ref(), feed.as_ref(), &end_ts.to_le_bytes(), &[strike_idx]],
+        &onchain::ID,
+    )
+}
+
+/// Derive market series PDA
+pub fn pda_series(authority: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SERIES_SEED, authority.as_ref(), &id.to_le_bytes()],
+        &onchain::ID,
+    )
+}
+
+/// Derive market PDA for one round of a market series
+pub fn pda_market_series(series: &Pubkey, round: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_SEED, series.as_ref(), &round.to_le_bytes()],
+        &onchain::ID,
+    )
+}
+
 /// Derive escrow authority PDA for binary markets (yes/no)
 pub fn pda_escrow_auth(market: &Pubkey, side: &[u8]) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -66,6 +106,14 @@ pub fn pda_position_multi(market: &Pubkey, user: &Pubkey, outcome_idx: u8) -> (P
     )
 }
 
+/// Derive liquidity provider position PDA for binary markets
+pub fn pda_lp_position(market: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LP_SEED, market.as_ref(), owner.as_ref()],
+        &onchain::ID,
+    )
+}
+
 /// Derive mint authority PDA
 pub fn pda_mint_auth() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[MINT_AUTH_SEED], &onchain::ID)
@@ -75,3 +123,26 @@ pub fn pda_mint_auth() -> (Pubkey, u8) {
 pub fn pda_claim(user: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CLAIM_SEED, user.as_ref()], &onchain::ID)
 }
+
+/// Derive faucet config PDA
+pub fn pda_faucet() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_SEED], &onchain::ID)
+}
+
+/// Derive AI oracle committee PDA
+pub fn pda_committee(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMITTEE_SEED, &id.to_le_bytes()], &onchain::ID)
+}
+
+/// Consumed-nonce PDA for an attester (committee or single oracle key)
+pub fn pda_used_nonce(attester: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NONCE_SEED, attester.as_ref(), &nonce.to_le_bytes()], &onchain::ID)
+}
+
+/// Derive a referrer's stake PDA for one market
+pub fn pda_referral_stake(market: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRAL_SEED, market.as_ref(), referrer.as_ref()],
+        &onchain::ID,
+    )
+}
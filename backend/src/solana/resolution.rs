@@ -1,11 +1,17 @@
 use super::{
     context::{AnchorCtx, program},
-    pda::{pda_market, pda_escrow_auth, pda_escrow_auth_outcome, pda_position, SIDE_YES, SIDE_NO},
+    pda::{
+        pda_config, pda_market, pda_escrow_auth, pda_escrow_auth_outcome, pda_position,
+        pda_position_multi, pda_referral_stake, pda_used_nonce, SIDE_YES, SIDE_NO, SIDE_DISPUTE,
+        SIDE_REFERRAL,
+    },
     encoding::{encode_unsigned_tx, decode_oracle_secret_32},
-    accounts::get_market_account,
-    attestation::{build_message_single_client},
+    accounts::{get_committee_account, get_market_account, get_position_multi_account},
+    attestation::{build_message_single_client, build_message_void_client},
+    migrate::{migrate_ixs_if_needed, with_migration},
+    token::{ata, token_program_for_mint},
 };
-use crate::{types::ix::{IxAccountMetaJson, IxJson, ResolveIxBundle}, state};
+use crate::types::ix::{IxAccountMetaJson, IxJson, ResolveIxBundle};
 use anchor_client::solana_sdk::{
     pubkey::Pubkey,
     instruction::{Instruction, AccountMeta},
@@ -15,15 +21,13 @@ use anchor_client::solana_sdk::{
     signature::{Signature, Signer},
     ed25519_instruction::new_ed25519_instruction_with_signature,
 };
-use anchor_spl::{
-    token::ID as TOKEN_PROGRAM_ID,
-    associated_token::{ID as ASSOCIATED_TOKEN_PROGRAM_ID, get_associated_token_address},
-};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
 use spl_associated_token_account::instruction as ata_ix;
 use anyhow::{Context, Result};
 use base64::{Engine, engine::general_purpose};
 use std::sync::Arc;
 use ed25519_dalek::{SigningKey, Signer as DalekSigner};
+use rand::{rngs::OsRng, RngCore};
 use prediction_market_program as onchain;
 
 /// Get latest blockhash
@@ -37,9 +41,10 @@ pub fn ensure_ata_exists(
     ixs: &mut Vec<Instruction>,
     owner: &Pubkey,
     mint: &Pubkey,
+    token_program: &Pubkey,
     payer: &Pubkey,
 ) -> Result<Pubkey> {
-    let ata = get_associated_token_address(owner, mint);
+    let ata = ata(owner, mint, token_program);
 
     let need_create = program
         .rpc()
@@ -49,13 +54,30 @@ pub fn ensure_ata_exists(
 
     if need_create {
         ixs.push(ata_ix::create_associated_token_account(
-            payer, owner, mint, &TOKEN_PROGRAM_ID,
+            payer, owner, mint, token_program,
         ));
     }
 
     Ok(ata)
 }
 
+/// Referral escrow ATA a settlement pays the referral slice into; only passed (and
+/// created if missing) when the market has volume placed through referrers
+fn ensure_referral_vault(
+    program: &anchor_client::Program<Arc<anchor_client::solana_sdk::signature::Keypair>>,
+    ixs: &mut Vec<Instruction>,
+    market_pda: &Pubkey,
+    market: &onchain::state::Market,
+    token_program: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Option<Pubkey>> {
+    if market.referred_volume == 0 || market.referral_bps_snapshot == 0 {
+        return Ok(None);
+    }
+    let (auth, _) = pda_escrow_auth(market_pda, SIDE_REFERRAL);
+    ensure_ata_exists(program, ixs, &auth, &market.mint, token_program, payer).map(Some)
+}
+
 /// Build resolve market transaction for Pyth oracle
 pub fn build_resolve(
     ctx: &AnchorCtx,
@@ -65,22 +87,23 @@ pub fn build_resolve(
     end_ts: i64,
     price_update: Pubkey,
 ) -> Result<String> {
-    let state = state::global();
     let program = program(ctx)?;
-    let mint = state.usdc_mint.parse()?;
 
     let (market_pda, _) = pda_market(&market_authority, &feed_id, end_ts);
-    let market_acc: onchain::state::Market = program.account(market_pda)?;
+    let market_acc = get_market_account(ctx, market_pda)?;
+    let mint = market_acc.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
     let treasury_owner = market_acc.treasury_wallet_snapshot;
 
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
     let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
-    let resolver_ata = get_associated_token_address(&resolver_pubkey, &mint);
-    let treasury_ata = get_associated_token_address(&treasury_owner, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let resolver_ata = ata(&resolver_pubkey, &mint, &token_program);
+    let treasury_ata = ata(&treasury_owner, &mint, &token_program);
 
-    let mut ixs: Vec<Instruction> = Vec::new();
+    // Markets on an older layout are migrated in the same tx so they load
+    let mut ixs = migrate_ixs_if_needed(ctx, resolver_pubkey, market_pda, &[])?;
 
     // Create treasury ATA if needed
     let need_treasury_ata = program
@@ -94,9 +117,17 @@ pub fn build_resolve(
             &resolver_pubkey,
             &treasury_owner,
             &mint,
-            &TOKEN_PROGRAM_ID,
+            &token_program,
         ));
     }
+    let referral_vault = ensure_referral_vault(
+        &program,
+        &mut ixs,
+        &market_pda,
+        &market_acc,
+        &token_program,
+        &resolver_pubkey,
+    )?;
 
     // ResolveMarket instruction
     let mut resolve_ixs = program
@@ -112,9 +143,10 @@ pub fn build_resolve(
             escrow_authority_no: escrow_no,
             escrow_vault_yes: vault_yes,
             escrow_vault_no: vault_no,
-            token_program: TOKEN_PROGRAM_ID,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
+            referral_vault,
         })
         .args(onchain::instruction::ResolveMarket {})
         .instructions()?;
@@ -128,6 +160,77 @@ pub fn build_resolve(
     encode_unsigned_tx(&tx)
 }
 
+/// Build resolve transaction for a Switchboard market. The pull feed must already hold an
+/// update published after end_ts; the resolver prepends the feed update ix when it does not.
+pub fn build_resolve_switchboard(
+    ctx: &AnchorCtx,
+    resolver_pubkey: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+
+    let market_acc = get_market_account(ctx, market_pda)?;
+    if market_acc.oracle_kind != onchain::types::OracleKind::Switchboard as u8 {
+        anyhow::bail!("market is not a Switchboard market");
+    }
+    let switchboard_feed = Pubkey::new_from_array(market_acc.feed_id);
+    let mint = market_acc.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let treasury_owner = market_acc.treasury_wallet_snapshot;
+
+    let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
+    let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let resolver_ata = ata(&resolver_pubkey, &mint, &token_program);
+
+    let mut ixs = migrate_ixs_if_needed(ctx, resolver_pubkey, market_pda, &[])?;
+    let treasury_ata = ensure_ata_exists(
+        &program,
+        &mut ixs,
+        &treasury_owner,
+        &mint,
+        &token_program,
+        &resolver_pubkey,
+    )?;
+    let referral_vault = ensure_referral_vault(
+        &program,
+        &mut ixs,
+        &market_pda,
+        &market_acc,
+        &token_program,
+        &resolver_pubkey,
+    )?;
+
+    let mut resolve_ixs = program
+        .request()
+        .accounts(onchain::accounts::ResolveMarketSwitchboard {
+            market: market_pda,
+            switchboard_feed,
+            resolver: resolver_pubkey,
+            resolver_ata,
+            mint,
+            treasury_ata,
+            escrow_authority_yes: escrow_yes,
+            escrow_authority_no: escrow_no,
+            escrow_vault_yes: vault_yes,
+            escrow_vault_no: vault_no,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            referral_vault,
+        })
+        .args(onchain::instruction::ResolveMarketSwitchboard {})
+        .instructions()?;
+    ixs.append(&mut resolve_ixs);
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&resolver_pubkey));
+    tx.message.recent_blockhash = bh;
+
+    encode_unsigned_tx(&tx)
+}
+
 /// Convert instruction to JSON format
 fn ix_to_json(ix: Instruction) -> IxJson {
     IxJson {
@@ -152,12 +255,13 @@ pub fn build_resolve_ix_bundle(
     market_pda: Pubkey,
 ) -> Result<ResolveIxBundle> {
     let program = program(ctx)?;
-    let mint: Pubkey = onchain::constants::USDC_MINT;
-    
-    let market_acc: onchain::state::Market = program.account(market_pda)?;
+
+    let market_acc = get_market_account(ctx, market_pda)?;
     if market_acc.settled {
         anyhow::bail!("market already settled");
     }
+    let mint = market_acc.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let feed_id_hex = format!("0x{}", hex::encode(market_acc.feed_id));
     let end_ts = market_acc.end_ts;
@@ -165,12 +269,15 @@ pub fn build_resolve_ix_bundle(
 
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
     let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
-    let resolver_ata = get_associated_token_address(&resolver_pubkey, &mint);
-    let treasury_ata = get_associated_token_address(&treasury_owner, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let resolver_ata = ata(&resolver_pubkey, &mint, &token_program);
+    let treasury_ata = ata(&treasury_owner, &mint, &token_program);
 
-    let mut out: Vec<IxJson> = vec![];
+    let mut out: Vec<IxJson> = migrate_ixs_if_needed(ctx, resolver_pubkey, market_pda, &[])?
+        .into_iter()
+        .map(ix_to_json)
+        .collect();
 
     // Create treasury ATA if needed
     let need_treasury_ata = program
@@ -184,13 +291,71 @@ pub fn build_resolve_ix_bundle(
             &resolver_pubkey,
             &treasury_owner,
             &mint,
-            &TOKEN_PROGRAM_ID,
+            &token_program,
         );
         out.push(ix_to_json(create_ata_ix));
     }
 
-    // ResolveMarket instruction with placeholder price_update
+    // Optional account not passed is encoded as the program id
+    let mut referral_ixs = Vec::new();
+    let referral_vault_meta = match ensure_referral_vault(
+        &program,
+        &mut referral_ixs,
+        &market_pda,
+        &market_acc,
+        &token_program,
+        &resolver_pubkey,
+    )? {
+        Some(vault) => AccountMeta::new(vault, false),
+        None => AccountMeta::new_readonly(onchain::ID, false),
+    };
+    out.extend(referral_ixs.into_iter().map(ix_to_json));
+
     let price_update_placeholder = Pubkey::default();
+
+    // Bucket markets: one (escrow authority, vault) pair per staked bucket in remaining accounts
+    if market_acc.market_type == onchain::types::MarketType::PriceBuckets as u8 {
+        let creator_ata = ata(&market_acc.authority, &mint, &token_program);
+        let mut accounts = vec![
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(price_update_placeholder, false),
+            AccountMeta::new(resolver_pubkey, true),
+            AccountMeta::new(resolver_ata, false),
+            AccountMeta::new(creator_ata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(treasury_ata, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            referral_vault_meta.clone(),
+        ];
+        for i in 0..market_acc.num_outcomes {
+            if market_acc.tvl_per_outcome[i as usize] == 0 {
+                continue;
+            }
+            let (escrow_auth, _) = pda_escrow_auth_outcome(&market_pda, i);
+            let vault = ata(&escrow_auth, &mint, &token_program);
+            accounts.push(AccountMeta::new_readonly(escrow_auth, false));
+            accounts.push(AccountMeta::new(vault, false));
+        }
+
+        let data = anchor_client::anchor_lang::InstructionData::data(
+            &onchain::instruction::ResolveMarketBuckets {},
+        );
+        out.push(ix_to_json(Instruction { program_id: onchain::ID, accounts, data }));
+
+        return Ok(ResolveIxBundle {
+            ok: true,
+            market_id: market_pda.to_string(),
+            end_ts,
+            feed_id_hex,
+            price_update_index: 1,
+            instructions: out,
+            message: "Resolve buckets ix bundle; inject price_update and send with Pyth Receiver".into(),
+        });
+    }
+
+    // ResolveMarket instruction with placeholder price_update
     let accounts = vec![
         AccountMeta::new(market_pda, false),
         AccountMeta::new_readonly(price_update_placeholder, false),
@@ -202,9 +367,10 @@ pub fn build_resolve_ix_bundle(
         AccountMeta::new_readonly(escrow_no, false),
         AccountMeta::new(vault_yes, false),
         AccountMeta::new(vault_no, false),
-        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(token_program, false),
         AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
         AccountMeta::new_readonly(system_program::ID, false),
+        referral_vault_meta,
     ];
 
     let data = anchor_client::anchor_lang::InstructionData::data(&onchain::instruction::ResolveMarket {});
@@ -233,13 +399,15 @@ pub fn build_claim_ix(
     market_pda: Pubkey,
 ) -> Result<String> {
     let program = program(ctx)?;
-    let mint = onchain::constants::USDC_MINT;
+    let market = get_market_account(ctx, market_pda)?;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
 
     let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
     let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
-    let vault_yes = get_associated_token_address(&escrow_yes, &mint);
-    let vault_no = get_associated_token_address(&escrow_no, &mint);
-    let user_ata = get_associated_token_address(&user_pubkey, &mint);
+    let vault_yes = ata(&escrow_yes, &mint, &token_program);
+    let vault_no = ata(&escrow_no, &mint, &token_program);
+    let user_ata = ata(&user_pubkey, &mint, &token_program);
     let (position_pda, _) = pda_position(&market_pda, &user_pubkey);
 
     let ixs = program
@@ -254,12 +422,14 @@ pub fn build_claim_ix(
             escrow_authority_no: escrow_no,
             escrow_vault_yes: vault_yes,
             escrow_vault_no: vault_no,
-            token_program: TOKEN_PROGRAM_ID,
+            config: pda_config().0,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
         })
         .args(onchain::instruction::Claim {})
         .instructions()?;
+    let ixs = with_migration(ctx, user_pubkey, market_pda, &[position_pda], ixs)?;
 
     let bh = latest_blockhash(&program)?;
     let mut tx = Transaction::new_with_payer(&ixs, Some(&user_pubkey));
@@ -280,42 +450,68 @@ pub fn ai_propose_prepare(
     let market_acc = get_market_account(ctx.as_ref(), market_pda)
         .context("market account fetch failed")?;
     let end_ts = market_acc.end_ts;
-    let oracle_pubkey_bytes: [u8; 32] = market_acc.ai_oracle_authority.to_bytes();
+
+    // Committee markets need `threshold` member signatures; legacy markets one from ai_oracle_authority
+    let oracle_committee = (market_acc.oracle_committee != Pubkey::default())
+        .then_some(market_acc.oracle_committee);
+    let (members, threshold) = match oracle_committee {
+        Some(c) => {
+            let committee = get_committee_account(ctx.as_ref(), c)?;
+            (committee.members().to_vec(), committee.threshold as usize)
+        }
+        None => (vec![market_acc.ai_oracle_authority], 1),
+    };
 
     let attest_ts = time::OffsetDateTime::now_utc().unix_timestamp();
-    let nonce: u64 = (attest_ts as u64)
-        ^ u64::from_le_bytes(market_pda.to_bytes()[..8].try_into().unwrap());
+    // Random so a nonce can't be predicted or collide; the program records each one used
+    let nonce: u64 = OsRng.next_u64();
+    let attester = oracle_committee.unwrap_or(market_acc.ai_oracle_authority);
+    let (used_nonce, _) = pda_used_nonce(&attester, nonce);
 
-    let oracle_secret_env = std::env::var("AI_ORACLE_SECRET")
-        .context("AI_ORACLE_SECRET is not set")?;
-    let oracle_secret_seed32 = decode_oracle_secret_32(&oracle_secret_env)?;
+    // AI_ORACLE_SECRETS holds every member key this backend may sign with (comma separated)
+    let oracle_secrets_env = std::env::var("AI_ORACLE_SECRETS")
+        .or_else(|_| std::env::var("AI_ORACLE_SECRET"))
+        .context("AI_ORACLE_SECRETS is not set")?;
 
-    let message = build_message_single_client(
-        &market_pda,
-        outcome_idx,
-        end_ts,
-        attest_ts,
-        nonce,
-        &onchain::ID,
-    );
+    // Attestations bind to the question the market was created with
+    let question_hash = market_acc.question_hash;
+    let message = if outcome_idx == onchain::constants::OUTCOME_NONE {
+        build_message_void_client(&market_pda, end_ts, attest_ts, nonce, &onchain::ID, &question_hash)
+    } else {
+        build_message_single_client(
+            &market_pda, outcome_idx, end_ts, attest_ts, nonce, &onchain::ID, &question_hash,
+        )
+    };
 
-    let sk = SigningKey::from_bytes(&oracle_secret_seed32);
+    // One ed25519 instruction per member signature, all over the same message
+    let mut ixs: Vec<Instruction> = Vec::new();
+    for secret in oracle_secrets_env.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let sk = SigningKey::from_bytes(&decode_oracle_secret_32(secret)?);
+        let pk_bytes = sk.verifying_key().to_bytes();
+        if !members.contains(&Pubkey::new_from_array(pk_bytes)) {
+            continue;
+        }
+        let sig_bytes = sk.sign(&message).to_bytes();
+        ixs.push(new_ed25519_instruction_with_signature(&message, &sig_bytes, &pk_bytes));
+    }
     anyhow::ensure!(
-        sk.verifying_key().to_bytes() == oracle_pubkey_bytes,
-        "AI_ORACLE_SECRET doesn't match on-chain ai_oracle_authority"
+        ixs.len() >= threshold,
+        "only {} of {} required oracle signatures available",
+        ixs.len(),
+        threshold
     );
 
-    let sig_bytes = sk.sign(&message).to_bytes();
-    let ed_ix = new_ed25519_instruction_with_signature(&message, &sig_bytes, &oracle_pubkey_bytes);
-
-    let mut ixs = vec![ed_ix];
     let mut propose_ixs = program
         .request()
         .accounts(onchain::accounts::ResolveAiPropose {
+            payer: payer.pubkey(),
             market: market_pda,
             ix_sysvar: sysvar::instructions::ID,
+            oracle_committee,
+            used_nonce,
+            system_program: system_program::ID,
         })
-        .args(onchain::instruction::ResolveAiPropose {})
+        .args(onchain::instruction::ResolveAiPropose { nonce })
         .instructions()?;
     ixs.append(&mut propose_ixs);
 
@@ -337,20 +533,29 @@ pub fn build_ai_finalize_single_tx(
     payer: Pubkey,
     market_pda: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
     treasury_owner: Pubkey,
     creator: Pubkey,
+    disputer: Option<Pubkey>,
+    referral_authority: Option<Pubkey>,
     win_idx: u8,
     losers: &[(u8, Pubkey, Pubkey)],
 ) -> Result<String> {
     let program = program(ctx)?;
-    let mut ixs: Vec<Instruction> = Vec::new();
+    let mut ixs = migrate_ixs_if_needed(ctx, payer, market_pda, &[])?;
 
-    let treasury_ata = ensure_ata_exists(&program, &mut ixs, &treasury_owner, &mint, &payer)?;
-    let resolver_ata = ensure_ata_exists(&program, &mut ixs, &payer, &mint, &payer)?;
-    let creator_ata = ensure_ata_exists(&program, &mut ixs, &creator, &mint, &payer)?;
+    let treasury_ata = ensure_ata_exists(&program, &mut ixs, &treasury_owner, &mint, &token_program, &payer)?;
+    let resolver_ata = ensure_ata_exists(&program, &mut ixs, &payer, &mint, &token_program, &payer)?;
+    let creator_ata = ensure_ata_exists(&program, &mut ixs, &creator, &mint, &token_program, &payer)?;
+    let disputer_ata = disputer
+        .map(|d| ensure_ata_exists(&program, &mut ixs, &d, &mint, &token_program, &payer))
+        .transpose()?;
+    let referral_vault = referral_authority
+        .map(|r| ensure_ata_exists(&program, &mut ixs, &r, &mint, &token_program, &payer))
+        .transpose()?;
 
     let (win_auth, _) = pda_escrow_auth_outcome(&market_pda, win_idx);
-    let win_vault = get_associated_token_address(&win_auth, &mint);
+    let win_vault = ata(&win_auth, &mint, &token_program);
 
     let mut req = program
         .request()
@@ -360,11 +565,13 @@ pub fn build_ai_finalize_single_tx(
             creator_ata,
             mint,
             treasury_ata,
+            disputer_ata,
             win_authority: win_auth,
             win_vault,
-            token_program: TOKEN_PROGRAM_ID,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
+            referral_vault,
         })
         .args(onchain::instruction::FinalizeAi {});
 
@@ -389,16 +596,25 @@ pub fn build_ai_finalize_multi_tx(
     payer: Pubkey,
     market_pda: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
     treasury_owner: Pubkey,
     creator: Pubkey,
+    disputer: Option<Pubkey>,
+    referral_authority: Option<Pubkey>,
     all_outcomes: &[(u8, Pubkey, Pubkey)],
 ) -> Result<String> {
     let program = program(ctx)?;
-    let mut ixs: Vec<Instruction> = Vec::new();
+    let mut ixs = migrate_ixs_if_needed(ctx, payer, market_pda, &[])?;
 
-    let treasury_ata = ensure_ata_exists(&program, &mut ixs, &treasury_owner, &mint, &payer)?;
-    let resolver_ata = ensure_ata_exists(&program, &mut ixs, &payer, &mint, &payer)?;
-    let creator_ata = ensure_ata_exists(&program, &mut ixs, &creator, &mint, &payer)?;
+    let treasury_ata = ensure_ata_exists(&program, &mut ixs, &treasury_owner, &mint, &token_program, &payer)?;
+    let resolver_ata = ensure_ata_exists(&program, &mut ixs, &payer, &mint, &token_program, &payer)?;
+    let creator_ata = ensure_ata_exists(&program, &mut ixs, &creator, &mint, &token_program, &payer)?;
+    let disputer_ata = disputer
+        .map(|d| ensure_ata_exists(&program, &mut ixs, &d, &mint, &token_program, &payer))
+        .transpose()?;
+    let referral_vault = referral_authority
+        .map(|r| ensure_ata_exists(&program, &mut ixs, &r, &mint, &token_program, &payer))
+        .transpose()?;
 
     let first_idx = all_outcomes
         .first()
@@ -406,7 +622,7 @@ pub fn build_ai_finalize_multi_tx(
         .0;
     
     let (win_authority, _) = pda_escrow_auth_outcome(&market_pda, first_idx);
-    let win_vault = get_associated_token_address(&win_authority, &mint);
+    let win_vault = ata(&win_authority, &mint, &token_program);
 
     let mut req = program
         .request()
@@ -416,11 +632,13 @@ pub fn build_ai_finalize_multi_tx(
             creator_ata,
             mint,
             treasury_ata,
+            disputer_ata,
             win_authority,
             win_vault,
-            token_program: TOKEN_PROGRAM_ID,
+            token_program,
             associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
             system_program: system_program::ID,
+            referral_vault,
         })
         .args(onchain::instruction::FinalizeAi {});
 
@@ -453,12 +671,27 @@ pub fn finalize_ai_unsigned(
     if market.settled {
         anyhow::bail!("market already settled");
     }
+    match market.dispute_status {
+        onchain::constants::DISPUTE_OPEN => anyhow::bail!("market has an open dispute"),
+        onchain::constants::DISPUTE_NONE => {
+            let now = time::OffsetDateTime::now_utc().unix_timestamp();
+            if now < market.proposed_at + market.dispute_window_secs {
+                anyhow::bail!("dispute window is still open");
+            }
+        }
+        _ => {}
+    }
 
-    let mint = onchain::constants::USDC_MINT;
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
     let treasury_owner = market.treasury_wallet_snapshot;
     let creator = market.authority;
+    let disputer = (market.dispute_status == onchain::constants::DISPUTE_OVERTURNED)
+        .then_some(market.disputer);
 
     let is_single = market.winners_mask == 0 && market.outcome_idx != u8::MAX;
+    let referral_authority = (market.referred_volume > 0 && market.referral_bps_snapshot > 0)
+        .then(|| pda_escrow_auth(&market_pda, SIDE_REFERRAL).0);
     
     if is_single {
         let win_idx = market.outcome_idx;
@@ -469,12 +702,13 @@ pub fn finalize_ai_unsigned(
                 continue;
             }
             let (auth_pda, _) = pda_escrow_auth_outcome(&market_pda, idx);
-            let vault_ata = get_associated_token_address(&auth_pda, &mint);
+            let vault_ata = ata(&auth_pda, &mint, &token_program);
             losers.push((idx, auth_pda, vault_ata));
         }
         
         build_ai_finalize_single_tx(
-            ctx, payer, market_pda, mint, treasury_owner, creator, win_idx, &losers,
+            ctx, payer, market_pda, mint, token_program, treasury_owner, creator, disputer, referral_authority, win_idx,
+            &losers,
         )
     } else {
         let mut all_outcomes: Vec<(u8, Pubkey, Pubkey)> =
@@ -482,12 +716,326 @@ pub fn finalize_ai_unsigned(
 
         for idx in 0..market.num_outcomes {
             let (auth_pda, _) = pda_escrow_auth_outcome(&market_pda, idx);
-            let vault_ata = get_associated_token_address(&auth_pda, &mint);
+            let vault_ata = ata(&auth_pda, &mint, &token_program);
             all_outcomes.push((idx, auth_pda, vault_ata));
         }
 
         build_ai_finalize_multi_tx(
-            ctx, payer, market_pda, mint, treasury_owner, creator, &all_outcomes,
+            ctx, payer, market_pda, mint, token_program, treasury_owner, creator, disputer, referral_authority,
+            &all_outcomes,
         )
     }
-}
\ No newline at end of file
+}
+
+/// Build dispute transaction posting the bond against a tentative AI proposal
+pub fn build_dispute_ai_unsigned(
+    ctx: &AnchorCtx,
+    disputer: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+
+    if !market.tentative || market.settled {
+        anyhow::bail!("market has no pending proposal");
+    }
+    if market.dispute_status != onchain::constants::DISPUTE_NONE {
+        anyhow::bail!("market already disputed");
+    }
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    if now >= market.proposed_at + market.dispute_window_secs {
+        anyhow::bail!("dispute window has closed");
+    }
+
+    // Any staked position proves the disputer is a bettor
+    let outcome_idx = (0..market.num_outcomes)
+        .find(|&idx| {
+            get_position_multi_account(ctx, market_pda, disputer, idx)
+                .map(|p| p.stake > 0)
+                .unwrap_or(false)
+        })
+        .ok_or_else(|| anyhow::anyhow!("no position in this market"))?;
+    let (position, _) = pda_position_multi(&market_pda, &disputer, outcome_idx);
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (bond_authority, _) = pda_escrow_auth(&market_pda, SIDE_DISPUTE);
+    let bond_vault = ata(&bond_authority, &mint, &token_program);
+    let disputer_ata = ata(&disputer, &mint, &token_program);
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::DisputeAi {
+            disputer,
+            market: market_pda,
+            position,
+            mint,
+            disputer_ata,
+            bond_authority,
+            bond_vault,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::DisputeAi {})
+        .instructions()?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&disputer));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Rule on an open dispute (admin or dispute authority signs)
+pub fn rule_dispute(
+    ctx: &AnchorCtx,
+    authority: &anchor_client::solana_sdk::signature::Keypair,
+    market_pda: Pubkey,
+    overturn: bool,
+    outcome_idx: u8,
+    winners_mask: u8,
+) -> Result<Signature> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+    if market.dispute_status != onchain::constants::DISPUTE_OPEN {
+        anyhow::bail!("market has no open dispute");
+    }
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    if now >= dispute_ruling_deadline(&market) {
+        anyhow::bail!("dispute ruling deadline has passed");
+    }
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (config, _) = pda_config();
+    let (bond_authority, _) = pda_escrow_auth(&market_pda, SIDE_DISPUTE);
+    let bond_vault = ata(&bond_authority, &mint, &token_program);
+
+    let mut ixs: Vec<Instruction> = Vec::new();
+    let payer = authority.pubkey();
+    let disputer_ata = ensure_ata_exists(&program, &mut ixs, &market.disputer, &mint, &token_program, &payer)?;
+    let treasury_ata =
+        ensure_ata_exists(&program, &mut ixs, &market.treasury_wallet_snapshot, &mint, &token_program, &payer)?;
+
+    let mut rule_ixs = program
+        .request()
+        .accounts(onchain::accounts::RuleDispute {
+            authority: payer,
+            config,
+            market: market_pda,
+            mint,
+            bond_authority,
+            bond_vault,
+            disputer_ata,
+            treasury_ata,
+            token_program,
+        })
+        .args(onchain::instruction::RuleDispute {
+            overturn,
+            outcome_idx,
+            winners_mask,
+        })
+        .instructions()?;
+    ixs.append(&mut rule_ixs);
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&payer));
+    tx.sign(&[authority], bh);
+
+    let sig = program
+        .rpc()
+        .send_and_confirm_transaction(&tx)
+        .context("send_and_confirm RuleDispute tx failed")?;
+
+    Ok(sig)
+}
+
+fn dispute_ruling_deadline(market: &onchain::state::Market) -> i64 {
+    market.proposed_at + market.dispute_window_secs + onchain::constants::DISPUTE_RULING_SECS
+}
+
+/// Build permissionless transaction voiding an AI market whose dispute was never ruled
+pub fn build_expire_dispute_unsigned(
+    ctx: &AnchorCtx,
+    payer: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+
+    if market.dispute_status != onchain::constants::DISPUTE_OPEN {
+        anyhow::bail!("market has no open dispute");
+    }
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    if now < dispute_ruling_deadline(&market) {
+        anyhow::bail!("dispute can still be ruled");
+    }
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (bond_authority, _) = pda_escrow_auth(&market_pda, SIDE_DISPUTE);
+    let bond_vault = ata(&bond_authority, &mint, &token_program);
+
+    let mut ixs: Vec<Instruction> = Vec::new();
+    let disputer_ata = ensure_ata_exists(&program, &mut ixs, &market.disputer, &mint, &token_program, &payer)?;
+
+    let mut expire_ixs = program
+        .request()
+        .accounts(onchain::accounts::ExpireDispute {
+            market: market_pda,
+            mint,
+            bond_authority,
+            bond_vault,
+            disputer_ata,
+            token_program,
+        })
+        .args(onchain::instruction::ExpireDispute {})
+        .instructions()?;
+    ixs.append(&mut expire_ixs);
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&payer));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build permissionless void transaction for an AI market past its resolution horizon
+pub fn build_void_ai_market_unsigned(
+    ctx: &AnchorCtx,
+    payer: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+
+    if market.settled {
+        anyhow::bail!("market already settled");
+    }
+    if market.tentative {
+        anyhow::bail!("market already has a pending proposal");
+    }
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    if now < market.end_ts + onchain::constants::RESOLVE_HORIZON_SECS {
+        anyhow::bail!("resolution horizon has not passed");
+    }
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::VoidAiMarket { market: market_pda })
+        .args(onchain::instruction::VoidAiMarket {})
+        .instructions()?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&payer));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build refund transaction for a multi-outcome position on a void market
+pub fn build_refund_multi_unsigned(
+    ctx: &AnchorCtx,
+    user: Pubkey,
+    market_pda: Pubkey,
+    outcome_idx: u8,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+
+    if !market.settled || market.winning_side != onchain::constants::WINNING_SIDE_VOID {
+        anyhow::bail!("market is not void");
+    }
+    let pos = get_position_multi_account(ctx, market_pda, user, outcome_idx)?;
+    if pos.claimed {
+        anyhow::bail!("position already refunded");
+    }
+    if pos.stake == 0 {
+        anyhow::bail!("nothing to refund");
+    }
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (win_authority, _) = pda_escrow_auth_outcome(&market_pda, outcome_idx);
+    let win_vault = ata(&win_authority, &mint, &token_program);
+    let user_ata = ata(&user, &mint, &token_program);
+    let (position, _) = pda_position_multi(&market_pda, &user, outcome_idx);
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::ClaimMulti {
+            user,
+            market: market_pda,
+            mint,
+            user_ata,
+            position,
+            win_authority,
+            win_vault,
+            config: pda_config().0,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::RefundMulti { outcome_idx })
+        .instructions()?;
+    let ixs = with_migration(ctx, user, market_pda, &[position], ixs)?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&user));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
+
+/// Build claim transaction paying a referrer's share of a settled market's referral pool
+pub fn build_claim_referral_unsigned(
+    ctx: &AnchorCtx,
+    referrer: Pubkey,
+    market_pda: Pubkey,
+) -> Result<String> {
+    let program = program(ctx)?;
+    let market = get_market_account(ctx, market_pda)?;
+
+    if !market.settled {
+        anyhow::bail!("market is not settled");
+    }
+    if market.referral_pool == 0 {
+        anyhow::bail!("market has no referral pool");
+    }
+    let (referral_stake, _) = pda_referral_stake(&market_pda, &referrer);
+    let stake: onchain::state::ReferralStake = program
+        .account(referral_stake)
+        .context("no referred volume in this market")?;
+    if stake.claimed {
+        anyhow::bail!("referral share already claimed");
+    }
+    if stake.volume == 0 {
+        anyhow::bail!("no referred volume in this market");
+    }
+
+    let mint = market.mint;
+    let token_program = token_program_for_mint(ctx, &mint)?;
+    let (referral_authority, _) = pda_escrow_auth(&market_pda, SIDE_REFERRAL);
+    let referral_vault = ata(&referral_authority, &mint, &token_program);
+    let referrer_ata = ata(&referrer, &mint, &token_program);
+
+    let ixs = program
+        .request()
+        .accounts(onchain::accounts::ClaimReferral {
+            referrer,
+            market: market_pda,
+            mint,
+            referrer_ata,
+            referral_stake,
+            referral_authority,
+            referral_vault,
+            config: pda_config().0,
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::ClaimReferral {})
+        .instructions()?;
+
+    let bh = latest_blockhash(&program)?;
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&referrer));
+    tx.message.recent_blockhash = bh;
+    encode_unsigned_tx(&tx)
+}
@@ -0,0 +1,99 @@
+use super::{
+    context::{AnchorCtx, program},
+    pda::{pda_config, pda_escrow_auth, pda_market_series, pda_series, SIDE_YES, SIDE_NO},
+    encoding::encode_unsigned_tx,
+    token::{ata, token_program_for_mint},
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    transaction::Transaction,
+    signature::{Signature, Signer},
+    system_program,
+    hash::Hash,
+};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
+use anyhow::Result;
+use prediction_market_program as onchain;
+
+/// Fetch a market series account
+pub fn get_series_account(ctx: &AnchorCtx, series_pda: Pubkey) -> Result<onchain::state::series::Series> {
+    let program = program(ctx)?;
+    let acc: onchain::state::series::Series = program
+        .account(series_pda)
+        .map_err(|e| anyhow::anyhow!("series account fetch failed: {e}"))?;
+    Ok(acc)
+}
+
+/// Unsigned create_series tx for `authority`; returns it with the series PDA
+pub fn build_create_series_unsigned(
+    ctx: &AnchorCtx,
+    authority: Pubkey,
+    mint: Pubkey,
+    p: onchain::instructions::series::CreateSeriesParams,
+    memo_opt: Option<&[u8]>,
+    recent_blockhash: Hash,
+) -> Result<(String, Pubkey)> {
+    let program = program(ctx)?;
+    let (series_pda, _) = pda_series(&authority, p.id);
+
+    let mut ixs = program
+        .request()
+        .accounts(onchain::accounts::CreateSeries {
+            authority,
+            series: series_pda,
+            mint,
+            config: pda_config().0,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::CreateSeries { p })
+        .instructions()?;
+
+    if let Some(memo_bytes) = memo_opt {
+        ixs.push(spl_memo::build_memo(memo_bytes, &[]));
+    }
+
+    let mut tx = Transaction::new_with_payer(&ixs, Some(&authority));
+    tx.message.recent_blockhash = recent_blockhash;
+
+    Ok((encode_unsigned_tx(&tx)?, series_pda))
+}
+
+/// Open the next round of a series, signed and paid by the backend keypair.
+/// `price_update` is the Pyth price feed account of the series feed. Returns the new market.
+pub fn roll_series(
+    ctx: &AnchorCtx,
+    series_pda: Pubkey,
+    price_update: Pubkey,
+) -> Result<(Signature, Pubkey)> {
+    let program = program(ctx)?;
+    let payer = &*ctx.payer;
+    let series = get_series_account(ctx, series_pda)?;
+    let token_program = token_program_for_mint(ctx, &series.mint)?;
+
+    let (market_pda, _) = pda_market_series(&series_pda, series.next_round);
+    let (escrow_yes, _) = pda_escrow_auth(&market_pda, SIDE_YES);
+    let (escrow_no, _) = pda_escrow_auth(&market_pda, SIDE_NO);
+
+    let sig = program
+        .request()
+        .accounts(onchain::accounts::RollSeries {
+            payer: payer.pubkey(),
+            series: series_pda,
+            market: market_pda,
+            mint: series.mint,
+            config: pda_config().0,
+            price_update,
+            escrow_authority_yes: escrow_yes,
+            escrow_authority_no: escrow_no,
+            escrow_vault_yes: ata(&escrow_yes, &series.mint, &token_program),
+            escrow_vault_no: ata(&escrow_no, &series.mint, &token_program),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(onchain::instruction::RollSeries {})
+        .signer(payer)
+        .send()?;
+
+    Ok((sig, market_pda))
+}
@@ -0,0 +1,46 @@
+use super::context::{AnchorCtx, program};
+use anchor_client::{anchor_lang::AccountDeserialize, solana_sdk::pubkey::Pubkey};
+use anchor_spl::{
+    token::ID as TOKEN_PROGRAM_ID,
+    token_2022::ID as TOKEN_2022_PROGRAM_ID,
+    token_interface::Mint,
+    associated_token::get_associated_token_address_with_program_id,
+};
+use anyhow::Result;
+
+/// Collateral mint details needed to build token instructions
+#[derive(Debug, Clone, Copy)]
+pub struct MintInfo {
+    pub token_program: Pubkey,  // SPL Token or Token-2022
+    pub decimals: u8,
+}
+
+/// Fetch the owning token program and decimals of `mint`
+pub fn get_mint_info(ctx: &AnchorCtx, mint: &Pubkey) -> Result<MintInfo> {
+    let program = program(ctx)?;
+    let acc = program
+        .rpc()
+        .get_account(mint)
+        .map_err(|e| anyhow::anyhow!("mint account fetch failed: {e}"))?;
+    if acc.owner != TOKEN_PROGRAM_ID && acc.owner != TOKEN_2022_PROGRAM_ID {
+        anyhow::bail!("{mint} is not owned by a token program");
+    }
+    let m = Mint::try_deserialize(&mut &acc.data[..])
+        .map_err(|e| anyhow::anyhow!("mint account decode failed: {e}"))?;
+    Ok(MintInfo { token_program: acc.owner, decimals: m.decimals })
+}
+
+/// Token program owning `mint`
+pub fn token_program_for_mint(ctx: &AnchorCtx, mint: &Pubkey) -> Result<Pubkey> {
+    Ok(get_mint_info(ctx, mint)?.token_program)
+}
+
+/// Associated token address of `owner` for `mint` under `token_program`
+pub fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// UI amount to base units for a mint with `decimals`
+pub fn ui_to_base_units(x: f64, decimals: u8) -> u64 {
+    (x * 10f64.powi(decimals as i32)).round() as u64
+}
@@ -24,6 +24,7 @@ pub struct AppState {
     // ===== Solana/Program config =====
     pub program_id: String,
     pub ai_oracle_pubkey: String,
+    pub ai_oracle_committee: Option<String>,
     pub memo_program: String, 
     pub usdc_mint: String, 
 
@@ -54,6 +55,9 @@ impl AppState {
         let ai_oracle_pubkey = std::env::var("AI_ORACLE_PUBKEY")
             .unwrap_or_else(|_| "3c5XAeXx8dCxn3KAVJuu4mXadcZJNFez3xeXKeq8tyTZ".to_string());
 
+        // M-of-N committee for new AI markets; unset keeps the single oracle key
+        let ai_oracle_committee = std::env::var("AI_ORACLE_COMMITTEE").ok();
+
         let memo_program = std::env::var("MEMO_PROGRAM")
             .ok()
             .unwrap_or_else(|| spl_memo::id().to_string());
@@ -72,6 +76,7 @@ impl AppState {
             rpc_url,
             program_id,
             ai_oracle_pubkey,
+            ai_oracle_committee,
             memo_program,
             usdc_mint,
             anchor,
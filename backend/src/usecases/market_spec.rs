@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::usecases::ai_jobs::ProposalWithId;
+
+/// Question an AI market resolves, in the form committed on-chain as `question_hash`.
+/// Field order is the canonical order; changing it changes every hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketSpecV1 {
+    pub v: u8,
+    pub topic: String,
+    pub description: String,
+    pub criteria: String,
+    pub accepted_sources: Vec<String>,
+    pub end_ts: i64,
+}
+
+impl MarketSpecV1 {
+    pub fn from_proposal(p: &ProposalWithId, end_ts: i64) -> Self {
+        Self::new(&p.topic, &p.description, &p.criteria, &p.accepted_sources, end_ts)
+    }
+
+    pub fn new(topic: &str, description: &str, criteria: &str, sources: &[String], end_ts: i64) -> Self {
+        Self {
+            v: 1,
+            topic: topic.to_owned(),
+            description: description.to_owned(),
+            criteria: criteria.to_owned(),
+            accepted_sources: sources.to_vec(),
+            end_ts,
+        }
+    }
+
+    /// Compact JSON with fields in declaration order
+    pub fn canonical_json(&self) -> String {
+        serde_json::to_string(self).expect("market spec serializes")
+    }
+
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.canonical_json().as_bytes()).into()
+    }
+}
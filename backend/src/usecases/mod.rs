@@ -2,4 +2,5 @@ pub mod market_category;
 pub mod perplexity;
 pub mod webhooks;
 pub mod ai_jobs;
-pub mod bets;
\ No newline at end of file
+pub mod bets;
+pub mod market_spec;
@@ -2,10 +2,11 @@ use anchor_client::solana_sdk::pubkey::Pubkey;
 use serde_json::Value;
 use std::str::FromStr;
 use anyhow::anyhow;
+use prediction_market_program as onchain;
 
 use crate::{
     usecases::webhooks::shared::{extract_sig, extract_fee_payer, accounts_str_and_idx},
-    solana as anchor_client_,
+    solana::{self as anchor_client_, pda_position, pda_position_multi},
     repo::bets as bets_repo,
     error::AppError,
     state,
@@ -14,6 +15,15 @@ use crate::{
 const IXI_USER:   usize = 0;
 const IXI_MARKET: usize = 1;
 
+// claim_many: fixed accounts, then (market, position, vault, escrow authority) groups
+const IXI_MANY_FIRST_GROUP: usize = 7;
+const CLAIM_GROUP_ACCOUNTS: usize = 4;
+
+// crank_payout: fixed accounts, then (position, owner, owner ATA) triples
+const IXI_CRANK_MARKET: usize = 1;
+const IXI_CRANK_FIRST_GROUP: usize = 10;
+const CRANK_GROUP_ACCOUNTS: usize = 3;
+
 pub async fn handle(
     item: &Value,
     this_ix: &Value,
@@ -91,3 +101,89 @@ pub async fn handle(
 
     Ok(())
 }
+
+/// `claim_many`: record every position in the batch as claimed. The program rejects the
+/// whole tx on any bad group, so a landed tx means each listed position was paid.
+pub async fn handle_many(
+    item: &Value,
+    this_ix: &Value,
+    msg_keys_opt: Option<&[Value]>,
+) -> Result<(), AppError> {
+    tracing::info!("handle claim_many");
+    let state = state::global();
+
+    let signature = extract_sig(item).unwrap_or("<no-sig>");
+    let fee_payer = extract_fee_payer(item).unwrap_or("<no-fee-payer>");
+    let (acc_str, _acc_idx) = accounts_str_and_idx(this_ix, msg_keys_opt);
+
+    let user_str = *acc_str.get(IXI_USER).unwrap_or(&fee_payer);
+    let user_pk = Pubkey::from_str(user_str)
+        .map_err(|e| AppError::bad_request(format!("invalid user pubkey: {e}")))?;
+
+    let groups = acc_str.get(IXI_MANY_FIRST_GROUP..).unwrap_or(&[]);
+    for g in groups.chunks_exact(CLAIM_GROUP_ACCOUNTS) {
+        let (Ok(market_pda), Ok(position)) = (Pubkey::from_str(g[0]), Pubkey::from_str(g[1])) else {
+            tracing::warn!("claim_many: unreadable group {:?}", g);
+            continue;
+        };
+
+        let Some(outcome_idx) = position_outcome(&market_pda, &user_pk, &position) else {
+            tracing::warn!("claim_many: position {} not owned by {}", position, user_str);
+            continue;
+        };
+
+        bets_repo::mark_claimed_in_batch(&state.db.pool(), g[0], user_str, outcome_idx, signature)
+            .await
+            .map_err(AppError::Other)?;
+    }
+
+    tracing::info!("claim_many recorded: user={} groups={} sig={}", user_str, groups.len() / CLAIM_GROUP_ACCOUNTS, signature);
+    Ok(())
+}
+
+/// `crank_payout`: the owners in a landed batch were all paid, record them as claimed
+pub async fn handle_crank(
+    item: &Value,
+    this_ix: &Value,
+    msg_keys_opt: Option<&[Value]>,
+) -> Result<(), AppError> {
+    tracing::info!("handle crank_payout");
+    let state = state::global();
+
+    let signature = extract_sig(item).unwrap_or("<no-sig>");
+    let (acc_str, _acc_idx) = accounts_str_and_idx(this_ix, msg_keys_opt);
+
+    let market_str = *acc_str.get(IXI_CRANK_MARKET).unwrap_or(&"<unknown>");
+    let market_pda = Pubkey::from_str(market_str)
+        .map_err(|e| AppError::bad_request(format!("invalid market pubkey: {e}")))?;
+
+    let groups = acc_str.get(IXI_CRANK_FIRST_GROUP..).unwrap_or(&[]);
+    for g in groups.chunks_exact(CRANK_GROUP_ACCOUNTS) {
+        let (Ok(position), Ok(owner)) = (Pubkey::from_str(g[0]), Pubkey::from_str(g[1])) else {
+            tracing::warn!("crank_payout: unreadable group {:?}", g);
+            continue;
+        };
+        let Some(outcome_idx) = position_outcome(&market_pda, &owner, &position) else {
+            tracing::warn!("crank_payout: position {} not owned by {}", position, owner);
+            continue;
+        };
+
+        bets_repo::mark_claimed_in_batch(&state.db.pool(), market_str, g[1], outcome_idx, signature)
+            .await
+            .map_err(AppError::Other)?;
+    }
+
+    tracing::info!("crank_payout recorded: market={} paid={} sig={}", market_str, groups.len() / CRANK_GROUP_ACCOUNTS, signature);
+    Ok(())
+}
+
+/// Which of `user`'s positions on `market` this is: Some(None) for the binary position,
+/// Some(Some(idx)) for a multi-outcome one, None if it is not theirs
+fn position_outcome(market: &Pubkey, user: &Pubkey, position: &Pubkey) -> Option<Option<i16>> {
+    if pda_position(market, user).0 == *position {
+        return Some(None);
+    }
+    (0..onchain::constants::MAX_OUTCOMES as u8)
+        .find(|&i| pda_position_multi(market, user, i).0 == *position)
+        .map(|i| Some(i as i16))
+}
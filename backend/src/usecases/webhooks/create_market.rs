@@ -7,10 +7,10 @@ use serde_json::Value;
 use crate::{
     usecases::webhooks::shared::{
         extract_sig, extract_slot, extract_fee_payer, extract_instructions,
-        accounts_str_and_idx, extract_memo,
+        accounts_str_and_idx, extract_memo, ix_data_bytes,
     },
     handlers::market::types::{
-        MarketType, MarketCategory, SeedSide, Comparator, CreateMarketRequest,
+        MarketType, MarketCategory, SeedSide, Comparator, CreateMarketRequest, PricingKind,
         resolve_price_feed_account_from_hex, usd_to_1e6
     },
     usecases::market_category::MarketCategory as MarketCategoryAi,
@@ -19,9 +19,17 @@ use crate::{
     error::AppError,
     state,
 };
+use anchor_client::anchor_lang::AnchorDeserialize;
+use prediction_market_program as onchain;
 
 const IXI_USER: usize = 0;
 const IXI_MARKET: usize = 1;
+const IXI_MINT: usize = 2;
+
+// create_market_ladder: fixed accounts, then 5 per strike starting with the market
+const IXI_LADDER_USER: usize = 0;
+const IXI_LADDER_MINT: usize = 1;
+const IXI_LADDER_FIRST_MARKET: usize = 6;
 
 #[derive(Debug, Deserialize)]
 struct AiMemoMin {
@@ -46,6 +54,8 @@ impl FromStr for MarketType {
         match s {
             "threshold" => Ok(MarketType::PriceThreshold),
             "range" => Ok(MarketType::PriceRange),
+            "buckets" => Ok(MarketType::PriceBuckets),
+            "scalar" => Ok(MarketType::PriceScalar),
             _ => Err(()),
         }
     }
@@ -57,6 +67,7 @@ impl FromStr for SeedSide {
         match s.to_ascii_lowercase().as_str() {
             "yes" => Ok(SeedSide::Yes),
             "no" => Ok(SeedSide::No),
+            "both" => Ok(SeedSide::Both),
             _ => Err(()),
         }
     }
@@ -123,6 +134,8 @@ struct MemoData {
     initial_liquidity: Option<f64>,
     #[serde(rename = "iS", default, deserialize_with = "de_opt_from_str")]
     initial_side: Option<SeedSide>,
+    #[serde(default)]
+    pk: Option<String>, // pricing kind: "pari" | "cpmm"
     #[serde(default, deserialize_with = "de_opt_from_str")]
     mt: Option<MarketType>,
     #[serde(default)]
@@ -133,6 +146,10 @@ struct MemoData {
     upper_bound: Option<f64>,
     #[serde(default)]
     th: Option<f64>,
+    #[serde(default)]
+    bk: Option<String>, // comma-separated bucket bounds
+    #[serde(rename = "iB", default)]
+    initial_bucket: Option<u8>,
 }
 
 fn parse_create_market_memo(memo: &str) -> Result<MemoData, AppError> {
@@ -180,21 +197,43 @@ pub async fn handle(
     let end_date    = m.end_date.ok_or_else(|| AppError::bad_request("memo.eD required"))?;
     let initial_liquidity = m.initial_liquidity.unwrap_or(0.0);
     let initial_side      = m.initial_side.unwrap_or(SeedSide::Yes);
+    let pricing = match m.pk.as_deref() {
+        Some("cpmm") => PricingKind::Cpmm,
+        _ => PricingKind::Parimutuel,
+    };
     let feed_id = m.f.ok_or_else(|| AppError::bad_request("memo.f (feed_id) required"))?;
     let symbol  = m.s.unwrap_or_else(|| "Crypto.???/USD".to_string());
 
     let authority = user_from_accounts;
 
+    let buckets = match market_type {
+        MarketType::PriceBuckets => {
+            let raw = m.bk.as_deref().unwrap_or_default();
+            let bounds = raw
+                .split(',')
+                .filter(|x| !x.is_empty())
+                .map(|x| x.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| AppError::bad_request("memo.bk has a bad bound"))?;
+            if bounds.is_empty() {
+                return Err(AppError::bad_request("memo.bk required for buckets"));
+            }
+            Some(bounds)
+        }
+        _ => None,
+    };
+
     let (threshold, lower_bound, upper_bound) = match market_type {
         MarketType::PriceThreshold => {
             let th = m.th.ok_or_else(|| AppError::bad_request("memo.th required for threshold"))?;
             (Some(th), None, None)
         }
-        MarketType::PriceRange => {
+        MarketType::PriceRange | MarketType::PriceScalar => {
             let lo = m.lower_bound.ok_or_else(|| AppError::bad_request("memo.lB required for range"))?;
             let hi = m.upper_bound.ok_or_else(|| AppError::bad_request("memo.uB required for range"))?;
             (None, Some(lo), Some(hi))
         }
+        MarketType::PriceBuckets => (None, None, None),
     };
 
     let req_create = CreateMarketRequest {
@@ -203,12 +242,22 @@ pub async fn handle(
         end_date,
         initial_liquidity,
         initial_side,
+        pricing,
         feed_id: feed_id.clone(),
         symbol: symbol.clone(),
         comparator,
         threshold,
         lower_bound,
         upper_bound,
+        buckets: buckets.clone(),
+        initial_bucket: m.initial_bucket,
+        collateral_mint: acc_str.get(IXI_MINT).map(|s| s.to_string()),
+        min_bet: None,
+        max_bet: None,
+        max_per_wallet: None,
+        max_conf_bps: None,
+        void_on_wide_conf: false,
+        use_ema: false,
     };
 
     let (exp_lo, exp_hi) = match market_type {
@@ -216,17 +265,23 @@ pub async fn handle(
             let t = threshold.ok_or_else(|| AppError::bad_request("threshold missing"))?;
             (usd_to_1e6(t), 0)
         }
-        MarketType::PriceRange => {
+        MarketType::PriceRange | MarketType::PriceScalar => {
             let lo = lower_bound.ok_or_else(|| AppError::bad_request("lower_bound missing"))?;
             let hi = upper_bound.ok_or_else(|| AppError::bad_request("upper_bound missing"))?;
             (usd_to_1e6(lo), usd_to_1e6(hi))
         }
+        MarketType::PriceBuckets => {
+            let b = buckets.as_deref().unwrap_or_default();
+            let lo = b.first().copied().unwrap_or_default();
+            let hi = b.last().copied().unwrap_or_default();
+            (usd_to_1e6(lo), usd_to_1e6(hi))
+        }
     };
 
     let price_feed_pubkey = resolve_price_feed_account_from_hex(&feed_id)
         .map_err(|e| AppError::bad_request(&format!("Cannot resolve price account: {e}")))?;
     let price_feed_b58 = price_feed_pubkey.to_string();
-    let mint_b58       = usdc_mint.to_string();
+    let mint_b58       = acc_str.get(IXI_MINT).copied().unwrap_or(usdc_mint).to_string();
 
     let market_id = market_repo::insert_confirmed_market(
         state.db.pool(),
@@ -250,6 +305,21 @@ pub async fn handle(
     .await
     .map_err(AppError::Other)?;
 
+    if let Some(b) = buckets.as_deref() {
+        let bounds_1e6: Vec<i64> = b.iter().map(|&x| usd_to_1e6(x)).collect();
+        market_repo::set_bucket_bounds(state.db.pool(), market_id, &bounds_1e6)
+            .await
+            .map_err(AppError::Other)?;
+    }
+
+    // seed_cpmm opens the curve at 50/50 with initial_liquidity on both sides
+    if pricing == PricingKind::Cpmm {
+        let l_1e6 = (initial_liquidity * 1_000_000.0).round() as i64;
+        market_repo::update_cpmm_reserves(state.db.pool(), market_id, l_1e6, l_1e6)
+            .await
+            .map_err(AppError::Other)?;
+    }
+
     tracing::info!(
         "market (pyth) inserted id={} pda={} creator={} sig={} slot={}",
         market_id,
@@ -262,6 +332,124 @@ pub async fn handle(
     Ok(())
 }
 
+/// === PYTH strike ladder (CreateMarketLadder) ===
+pub async fn handle_ladder(
+    item: &Value,
+    this_ix: &Value,
+    msg_keys_opt: Option<&[Value]>,
+    memo_program: &str,
+    usdc_mint: &str,
+) -> Result<(), AppError> {
+    tracing::info!("handle create_market_ladder (pyth)");
+
+    let state = state::global();
+    let signature = extract_sig(item).unwrap_or("<no-sig>");
+    let slot      = extract_slot(item).unwrap_or_default();
+    let fee_payer = extract_fee_payer(item).unwrap_or("<no-fee-payer>");
+
+    let (acc_str, _) = accounts_str_and_idx(this_ix, msg_keys_opt);
+    let authority = *acc_str.get(IXI_LADDER_USER).unwrap_or(&fee_payer);
+    let mint_b58  = acc_str.get(IXI_LADDER_MINT).copied().unwrap_or(usdc_mint).to_string();
+
+    // Feed, comparator, end date and strikes come from the instruction args
+    let data = ix_data_bytes(this_ix)
+        .ok_or_else(|| AppError::bad_request("create_market_ladder: no ix data"))?;
+    let p = onchain::instruction::CreateMarketLadder::try_from_slice(&data[8..])
+        .map_err(|e| AppError::bad_request(&format!("create_market_ladder: bad ix data: {e}")))?
+        .p;
+
+    let (ixs, msg_keys_opt2) = extract_instructions(item);
+    let m: MemoData = extract_memo(&ixs, msg_keys_opt2, memo_program)
+        .as_deref()
+        .map(serde_urlencoded::from_str)
+        .transpose()
+        .map_err(|e| AppError::Other(anyhow!("bad memo: {e}")))?
+        .ok_or_else(|| AppError::bad_request("create_market_ladder: memo is missing"))?;
+    let category = m.ca.unwrap_or(MarketCategory::Crypto);
+    let symbol   = m.s.unwrap_or_else(|| "Crypto.???/USD".to_string());
+
+    let feed_id = hex::encode(p.feed_id);
+    let end_date = OffsetDateTime::from_unix_timestamp(p.end_ts)
+        .map_err(|e| AppError::bad_request(&format!("create_market_ladder: bad end_ts: {e}")))?;
+    let comparator = match p.comparator {
+        0 => Comparator::Gt,
+        1 => Comparator::Lt,
+        2 => Comparator::Gte,
+        3 => Comparator::Lte,
+        _ => Comparator::Empty,
+    };
+    let price_feed_b58 = resolve_price_feed_account_from_hex(&feed_id)
+        .map_err(|e| AppError::bad_request(&format!("Cannot resolve price account: {e}")))?
+        .to_string();
+
+    let root_pda = *acc_str
+        .get(IXI_LADDER_FIRST_MARKET)
+        .ok_or_else(|| AppError::bad_request("create_market_ladder: market accounts missing"))?;
+
+    for (i, &strike_1e6) in p.strikes.iter().enumerate() {
+        let market_pda = *acc_str
+            .get(IXI_LADDER_FIRST_MARKET + i * onchain::constants::LADDER_ACCOUNTS_PER_STRIKE)
+            .ok_or_else(|| AppError::bad_request("create_market_ladder: market accounts missing"))?;
+
+        let req_create = CreateMarketRequest {
+            market_type: MarketType::PriceThreshold,
+            category,
+            end_date,
+            initial_liquidity: 0.0,
+            initial_side: SeedSide::Yes,
+            pricing: PricingKind::Parimutuel,
+            feed_id: feed_id.clone(),
+            symbol: symbol.clone(),
+            comparator,
+            threshold: Some(strike_1e6 as f64 / 1_000_000.0),
+            lower_bound: None,
+            upper_bound: None,
+            buckets: None,
+            initial_bucket: None,
+            collateral_mint: Some(mint_b58.clone()),
+            min_bet: None,
+            max_bet: None,
+            max_per_wallet: None,
+            max_conf_bps: None,
+            void_on_wide_conf: false,
+            use_ema: false,
+        };
+
+        let market_id = market_repo::insert_confirmed_market(
+            state.db.pool(),
+            &req_create,
+            market_pda,
+            authority,
+            signature,
+            &price_feed_b58,
+            &mint_b58,
+            strike_1e6,
+            0,
+        )
+        .await
+        .map_err(AppError::Other)?;
+
+        market_repo::upsert_initial_state(state.db.pool(), market_id, 0, 0, 0)
+            .await
+            .map_err(AppError::Other)?;
+
+        market_repo::set_ladder(state.db.pool(), market_id, root_pda, i as i16)
+            .await
+            .map_err(AppError::Other)?;
+    }
+
+    tracing::info!(
+        "ladder (pyth) inserted root={} strikes={} creator={} sig={} slot={}",
+        root_pda,
+        p.strikes.len(),
+        authority,
+        signature,
+        slot
+    );
+
+    Ok(())
+}
+
 /// === AI (CreateMarketMulti) ===
 pub async fn handle_multi(
     item: &Value,
@@ -0,0 +1,66 @@
+use serde_json::Value;
+
+use crate::{
+    usecases::webhooks::shared::{extract_sig, extract_fee_payer, accounts_str_and_idx, ix_data_bytes},
+    repo::{bets as bets_repo, market as market_repo},
+    error::AppError,
+    state,
+};
+
+const IXI_USER:   usize = 0;
+const IXI_MARKET: usize = 1;
+
+/// `exit_position(side, amount)` / `exit_position_multi(outcome_idx, amount)`:
+/// both encode a u8 at [8] and a little-endian u64 at [9..17].
+fn outcome_and_amount_from_exit_ix(ix: &Value) -> Option<(u8, u64)> {
+    let data = ix_data_bytes(ix)?;
+    if data.len() < 17 { return None; }
+
+    let idx = data[8]; // binary: 0=YES, 1=NO
+    let amount = u64::from_le_bytes(data[9..17].try_into().ok()?);
+    Some((idx, amount))
+}
+
+pub async fn handle(
+    item: &Value,
+    this_ix: &Value,
+    msg_keys_opt: Option<&[Value]>,
+) -> Result<(), AppError> {
+    tracing::info!("handle exit_position");
+    let state = state::global();
+
+    let signature = extract_sig(item).unwrap_or("<no-sig>");
+    let fee_payer = extract_fee_payer(item).unwrap_or("<no-fee-payer>");
+    let (acc_str, _acc_idx) = accounts_str_and_idx(this_ix, msg_keys_opt);
+
+    let user_str       = *acc_str.get(IXI_USER).unwrap_or(&fee_payer);
+    let market_pda_str = *acc_str.get(IXI_MARKET).unwrap_or(&"<unknown>");
+
+    let Some((outcome_idx, amount_1e6)) = outcome_and_amount_from_exit_ix(this_ix) else {
+        tracing::warn!("exit_position ix data not decodable; sig={}", signature);
+        return Ok(());
+    };
+
+    let m = market_repo::fetch_by_pda(state.db.pool(), market_pda_str)
+        .await
+        .map_err(|e| AppError::Other(e.into()))?
+        .ok_or_else(|| AppError::NotFound)?;
+
+    bets_repo::apply_exit_to_position(
+        state.db.pool(),
+        m.id,
+        user_str,
+        outcome_idx,
+        amount_1e6 as i64,
+        signature,
+    )
+    .await
+    .map_err(AppError::Other)?;
+
+    tracing::info!(
+        "exit recorded: market={} user={} outcome_idx={} amount_1e6={} sig={}",
+        market_pda_str, user_str, outcome_idx, amount_1e6, signature
+    );
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use serde_json::Value;
+
+use crate::{
+    usecases::webhooks::shared::{extract_sig, extract_fee_payer, accounts_str_and_idx, ix_data_bytes},
+    repo::{liquidity as lp_repo, market as market_repo},
+    error::AppError,
+    state,
+};
+
+const IXI_USER:   usize = 0;
+const IXI_MARKET: usize = 1;
+
+#[derive(Clone, Copy, Debug)]
+pub enum LpAction {
+    Add,
+    Remove,
+    Claim,
+}
+
+/// `add_liquidity(amount)` / `remove_liquidity(shares)`: a little-endian u64 at [8..16]
+fn amount_from_lp_ix(ix: &Value) -> Option<u64> {
+    let data = ix_data_bytes(ix)?;
+    if data.len() < 16 { return None; }
+    Some(u64::from_le_bytes(data[8..16].try_into().ok()?))
+}
+
+pub async fn handle(
+    item: &Value,
+    this_ix: &Value,
+    msg_keys_opt: Option<&[Value]>,
+    action: LpAction,
+) -> Result<(), AppError> {
+    tracing::info!("handle liquidity {:?}", action);
+    let state = state::global();
+
+    let signature = extract_sig(item).unwrap_or("<no-sig>");
+    let fee_payer = extract_fee_payer(item).unwrap_or("<no-fee-payer>");
+    let (acc_str, _acc_idx) = accounts_str_and_idx(this_ix, msg_keys_opt);
+
+    let user_str       = *acc_str.get(IXI_USER).unwrap_or(&fee_payer);
+    let market_pda_str = *acc_str.get(IXI_MARKET).unwrap_or(&"<unknown>");
+
+    let m = market_repo::fetch_by_pda(state.db.pool(), market_pda_str)
+        .await
+        .map_err(|e| AppError::Other(e.into()))?
+        .ok_or_else(|| AppError::NotFound)?;
+
+    let pool = state.db.pool();
+    match action {
+        LpAction::Add | LpAction::Remove => {
+            let Some(amount_1e6) = amount_from_lp_ix(this_ix) else {
+                tracing::warn!("liquidity ix data not decodable; sig={}", signature);
+                return Ok(());
+            };
+            let res = if matches!(action, LpAction::Add) {
+                lp_repo::apply_add_liquidity(pool, m.id, user_str, amount_1e6 as i64, signature).await
+            } else {
+                lp_repo::apply_remove_liquidity(pool, m.id, user_str, amount_1e6 as i64, signature).await
+            };
+            res.map_err(AppError::Other)?;
+        }
+        LpAction::Claim => {
+            lp_repo::mark_lp_claimed(pool, m.id, user_str, signature)
+                .await
+                .map_err(AppError::Other)?;
+        }
+    }
+
+    tracing::info!(
+        "liquidity {:?} recorded: market={} user={} sig={}",
+        action, market_pda_str, user_str, signature
+    );
+
+    Ok(())
+}
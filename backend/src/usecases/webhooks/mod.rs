@@ -3,6 +3,8 @@ pub mod create_market;
 pub mod place_bet;
 pub mod shared;
 pub mod claim;
+pub mod exit_position;
+pub mod liquidity;
 
 use serde_json::Value;
 
@@ -10,7 +12,11 @@ use shared::{anchor_sighash, extract_instructions, ix_program_id};
 use crate::{error::AppError, state};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Method { PlaceBet, PlaceBetMulti, CreateMarket, CreateMarketMulti, ResolveMarket, Claim, Unknown }
+enum Method {
+    PlaceBet, PlaceBetMulti, CreateMarket, CreateMarketMulti, CreateMarketBuckets, CreateMarketLadder,
+    ResolveMarket, ResolveMarketBuckets, Claim, ClaimMany, CrankPayout, ExitPosition, ExitPositionMulti,
+    AddLiquidity, RemoveLiquidity, ClaimLp, Unknown,
+}
 
 fn detect_method(ix: &Value) -> Method {
     let bytes = match shared::ix_data_bytes(ix) { Some(b) => b, None => return Method::Unknown };
@@ -23,10 +29,30 @@ fn detect_method(ix: &Value) -> Method {
         Method::CreateMarket
     } else if discr == anchor_sighash("create_market_multi") {
         Method::CreateMarketMulti
+    } else if discr == anchor_sighash("create_market_buckets") {
+        Method::CreateMarketBuckets
+    } else if discr == anchor_sighash("create_market_ladder") {
+        Method::CreateMarketLadder
     } else if discr == anchor_sighash("resolve_market") {
         Method::ResolveMarket
+    } else if discr == anchor_sighash("resolve_market_buckets") {
+        Method::ResolveMarketBuckets
     } else if discr == anchor_sighash("claim") {
         Method::Claim
+    } else if discr == anchor_sighash("claim_many") {
+        Method::ClaimMany
+    } else if discr == anchor_sighash("crank_payout") {
+        Method::CrankPayout
+    } else if discr == anchor_sighash("exit_position") {
+        Method::ExitPosition
+    } else if discr == anchor_sighash("exit_position_multi") {
+        Method::ExitPositionMulti
+    } else if discr == anchor_sighash("add_liquidity") {
+        Method::AddLiquidity
+    } else if discr == anchor_sighash("remove_liquidity") {
+        Method::RemoveLiquidity
+    } else if discr == anchor_sighash("claim_lp") {
+        Method::ClaimLp
     } else {
          Method::Unknown
         }
@@ -54,6 +80,16 @@ pub async fn handle_helius_raw_item(item: &Value) -> Result<(), AppError> {
                     tracing::error!("create_market_multi error: {e:#?}");
                 }
             }
+            Method::CreateMarketBuckets => {
+                if let Err(e) = create_market::handle(item, ix, msg_keys_opt, &state.memo_program, &state.usdc_mint).await {
+                    tracing::error!("create_market_buckets error: {e:#?}");
+                }
+            }
+            Method::CreateMarketLadder => {
+                if let Err(e) = create_market::handle_ladder(item, ix, msg_keys_opt, &state.memo_program, &state.usdc_mint).await {
+                    tracing::error!("create_market_ladder error: {e:#?}");
+                }
+            }
             Method::PlaceBet => {
                 if let Err(e) = place_bet::handle(item, ix, msg_keys_opt, &state.memo_program, &state.usdc_mint).await {
                     tracing::error!("place_bet error: {e:#?}");
@@ -69,11 +105,51 @@ pub async fn handle_helius_raw_item(item: &Value) -> Result<(), AppError> {
                     tracing::error!("resolve_market error: {e:#?}");
                 }
             }
+            Method::ResolveMarketBuckets => {
+                if let Err(e) = resolve_market::handle(item, ix, msg_keys_opt).await {
+                    tracing::error!("resolve_market_buckets error: {e:#?}");
+                }
+            }
             Method::Claim => {
                 if let Err(e) = claim::handle(item, ix, msg_keys_opt).await {
                     tracing::error!("claim error: {e:#?}");
                 }
             }
+            Method::ClaimMany => {
+                if let Err(e) = claim::handle_many(item, ix, msg_keys_opt).await {
+                    tracing::error!("claim_many error: {e:#?}");
+                }
+            }
+            Method::CrankPayout => {
+                if let Err(e) = claim::handle_crank(item, ix, msg_keys_opt).await {
+                    tracing::error!("crank_payout error: {e:#?}");
+                }
+            }
+            Method::ExitPosition => {
+                if let Err(e) = exit_position::handle(item, ix, msg_keys_opt).await {
+                    tracing::error!("exit_position error: {e:#?}");
+                }
+            }
+            Method::ExitPositionMulti => {
+                if let Err(e) = exit_position::handle(item, ix, msg_keys_opt).await {
+                    tracing::error!("exit_position_multi error: {e:#?}");
+                }
+            }
+            Method::AddLiquidity => {
+                if let Err(e) = liquidity::handle(item, ix, msg_keys_opt, liquidity::LpAction::Add).await {
+                    tracing::error!("add_liquidity error: {e:#?}");
+                }
+            }
+            Method::RemoveLiquidity => {
+                if let Err(e) = liquidity::handle(item, ix, msg_keys_opt, liquidity::LpAction::Remove).await {
+                    tracing::error!("remove_liquidity error: {e:#?}");
+                }
+            }
+            Method::ClaimLp => {
+                if let Err(e) = liquidity::handle(item, ix, msg_keys_opt, liquidity::LpAction::Claim).await {
+                    tracing::error!("claim_lp error: {e:#?}");
+                }
+            }
             Method::Unknown => {}
         }
     }
@@ -1,4 +1,7 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use prediction_market_program as onchain;
 use serde_json::Value;
+use std::str::FromStr;
 
 use crate::{
     usecases::webhooks::shared::{
@@ -9,11 +12,13 @@ use crate::{
 	repo::market as market_repo,
 	error::{AppError},
 	usecases::bets,
+	solana as anchor_client_,
 	state
 };
 
 const IXI_USER: usize       = 0;
 const IXI_MARKET: usize     = 1;
+const IXI_MINT: usize       = 2;
 const IXI_USER_ATA: usize   = 3;
 
 // DEPRECATED: backward compat for legacy side parsing
@@ -69,6 +74,7 @@ pub async fn handle(
     let (acc_str, acc_idx) = accounts_str_and_idx(this_ix, msg_keys_opt);
     let market_pda = *acc_str.get(IXI_MARKET).unwrap_or(&"<unknown>");
     let user_from_accounts = *acc_str.get(IXI_USER).unwrap_or(&fee_payer);
+    let mint = acc_str.get(IXI_MINT).copied().unwrap_or(usdc_mint);
 
     let (ixs, msg_keys_opt) = extract_instructions(item);
     let memo = extract_memo(&ixs, msg_keys_opt, memo_program);
@@ -86,12 +92,12 @@ pub async fn handle(
         .unwrap_or(0);
 
     let amount_and_user: Option<(f64, &str)> =
-        if let Some((amount_ui, from)) = amount_user_from_token_transfers(item, usdc_mint) {
+        if let Some((amount_ui, from)) = amount_user_from_token_transfers(item, mint) {
             let user = if !from.is_empty() { from } else { user_from_accounts };
             Some((amount_ui, user))
         } else if let Some(user_ata_idx) = acc_idx.get(IXI_USER_ATA).copied() {
             if user_ata_idx != usize::MAX {
-                if let Some(amount_ui) = amount_from_meta_delta(item, user_ata_idx, usdc_mint) {
+                if let Some(amount_ui) = amount_from_meta_delta(item, user_ata_idx, mint) {
                     Some((amount_ui, user_from_accounts))
                 } else {
                     None
@@ -125,6 +131,23 @@ pub async fn handle(
         .await
         .map_err(|e| AppError::Other(e.into()))?;
 
+        // CPMM markets: mirror the curve reserves so price_yes_bp stays executable
+        let market_pk = Pubkey::from_str(market_pda)
+            .map_err(|e| AppError::bad_request(format!("invalid market pubkey: {e}")))?;
+        let acc = anchor_client_::fetch_market_account(state.anchor.clone(), market_pk)
+            .await
+            .map_err(AppError::Other)?;
+        if acc.pricing_kind == onchain::types::PricingKind::Cpmm as u8 {
+            market_repo::update_cpmm_reserves(
+                state.db.pool(),
+                m.id,
+                acc.pool_yes as i64,
+                acc.pool_no as i64,
+            )
+            .await
+            .map_err(AppError::Other)?;
+        }
+
         return Ok(());
     }
 
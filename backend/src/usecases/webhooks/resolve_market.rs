@@ -54,6 +54,7 @@ pub async fn handle(
         winning_side: snap_onchain.winning_side,
         resolved_price_1e6: snap_onchain.resolved_price_1e6,
         payout_pool_1e6: snap_onchain.payout_pool_1e6,
+        winning_outcome_idx: snap_onchain.outcome_idx,
         resolver_pubkey: resolver.to_string(),
         tx_sig_resolve: signature.to_string(),
     };
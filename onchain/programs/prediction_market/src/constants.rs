@@ -1,6 +1,30 @@
 use anchor_lang::prelude::*;
 
+// Test USDC mint handed out by the devnet faucet
 pub const USDC_MINT: Pubkey = pubkey!("5WVkLTcYYSKaYG7hFc69ysioBRGPxA4KgreQDQ7wJTMh");
+pub const USDC_DECIMALS: u8 = 6;
+
+// Account layout versions; bump when appending fields and give them defaults in migrate_market
+pub const MARKET_VERSION: u8 = 3; // 2: payout_per_outcome, rent_payer; 3: positions_untracked
+pub const CONFIG_VERSION: u8 = 1; // 1: everything after resolver_tip_cap, see migrate_config
+pub const POSITION_VERSION: u8 = 1;
+
+// Switchboard on-demand program owning pull-feed accounts
+pub const SWITCHBOARD_ON_DEMAND_PID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+pub const SWITCHBOARD_ON_DEMAND_DEVNET_PID: Pubkey = pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+// Collateral
+pub const MAX_COLLATERAL_MINTS: usize = 8;
+
+// Config.paused bits
+pub const PAUSE_BETTING: u8 = 1 << 0;
+pub const PAUSE_CREATION: u8 = 1 << 1;
+pub const PAUSE_CLAIMS: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_BETTING | PAUSE_CREATION | PAUSE_CLAIMS;
+
+// Resolver tip curves
+pub const TIP_CURVE_FLAT: u8 = 0;
+pub const TIP_CURVE_LINEAR: u8 = 1; // decays to zero at the resolve horizon
 
 // Basis points
 pub const BPS_DENOM: u64 = 10_000;
@@ -10,21 +34,64 @@ pub const ESCROW_SEED: &[u8] = b"escrow-auth";
 pub const SIDE_YES: &[u8] = b"yes";
 pub const SIDE_NO: &[u8] = b"no";
 pub const OUTCOME_PREFIX: &[u8] = b"o";
+pub const SIDE_DISPUTE: &[u8] = b"dispute";
+pub const SIDE_REFERRAL: &[u8] = b"referral"; // escrow holding a market's referral pool
+pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const LP_SEED: &[u8] = b"lp";
 
 // Market outcomes
 pub const MAX_OUTCOMES: usize = 5;
 pub const OUTCOME_YES: u8 = 0;
 pub const OUTCOME_NO: u8 = 1;
 pub const OUTCOME_NONE: u8 = u8::MAX; // 255 = no single winner
+pub const WINNING_SIDE_VOID: u8 = 3;
+pub const WINNING_SIDE_SCALAR: u8 = 4; // both sides paid by where the price landed
+
+// Off-chain market spec: sha256 of its canonical JSON plus where it is served
+pub const MAX_METADATA_URI_LEN: usize = 128;
+
+// Strike ladders; each strike passes 5 accounts, which keeps the batch under the tx size limit
+pub const MAX_LADDER_STRIKES: usize = 4;
+pub const LADDER_ACCOUNTS_PER_STRIKE: usize = 5;
+
+// Recurring series: each round's strike is the live price rounded to a multiple of strike_step
+pub const STRIKE_NEAREST: u8 = 0;
+pub const STRIKE_FLOOR: u8 = 1;
+pub const STRIKE_CEIL: u8 = 2;
+pub const MIN_SERIES_CADENCE_SECS: i64 = 300; // 5 minutes
+pub const MIN_SERIES_ROUND_SECS: i64 = 120; // shorter rounds skip to the following boundary
+pub const SERIES_STRIKE_MAX_AGE: u64 = 60; // price used for the strike, in seconds
+
+// Batch claims pass (market, position, vault, escrow authority) per claimed position
+pub const CLAIM_GROUP_ACCOUNTS: usize = 4;
+
+// Payout crank passes (position, owner, owner ATA) per paid position; fee is at most 1%
+pub const CRANK_GROUP_ACCOUNTS: usize = 3;
+pub const MAX_CRANK_FEE_BPS: u16 = 100;
+
+// close_market only sweeps floor-division leftovers; a vault holding more is still owed
+pub const MAX_CLOSE_DUST: u64 = 1_000;
+
+// Dispute status
+pub const DISPUTE_NONE: u8 = 0;
+pub const DISPUTE_OPEN: u8 = 1;
+pub const DISPUTE_OVERTURNED: u8 = 2;
+pub const DISPUTE_CONFIRMED: u8 = 3;
+pub const DISPUTE_EXPIRED: u8 = 4; // never ruled; market voided
 
-// Attestation
-pub const DOMAIN: &[u8] = b"SOLPREDICT_ATTESTATION_v1";
+// Attestation; v2 signs over the market's question hash
+pub const DOMAIN: &[u8] = b"SOLPREDICT_ATTESTATION_v2";
+pub const MAX_COMMITTEE_MEMBERS: usize = 8;
 
 // Airdrop
-pub const AIRDROP_AMOUNT: u64 = 3_000 * 1_000_000; // 3000 USDC
+pub const AIRDROP_AMOUNT: u64 = 3_000 * 1_000_000; // 3000 USDC, default faucet drip
+pub const FAUCET_SEED: &[u8] = b"faucet";
+pub const FAUCET_CLAIM_SEED: &[u8] = b"faucet-claim"; // replaces the one-shot b"claim" records
 
 // Resolution timeouts
 pub const RESOLVE_HORIZON_SECS: i64 = 15 * 86_400; // 15 days
+pub const TIP_FULL_WINDOW_SECS: i64 = 3600; // full resolver tip for 1 hour after end_ts
+pub const DISPUTE_RULING_SECS: i64 = 7 * 86_400; // after the dispute window, before expire_dispute
 pub const MAX_PRICE_STALENESS: i64 = 86_400; // 1 day
 pub const PRICE_FETCH_GRACE_PERIOD: i64 = 300; // 5 minutes
 pub const ATTESTATION_TIME_TOLERANCE: i64 = 3600; // 1 hour
@@ -2,10 +2,14 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum AirdropError {
-    #[msg("Airdrop already claimed for this wallet")]
-    AlreadyClaimed,
+    #[msg("Faucet cooldown has not elapsed for this wallet")]
+    CooldownActive,
     #[msg("Wrong mint")]
     WrongMint,
+    #[msg("Faucet is disabled")]
+    FaucetDisabled,
+    #[msg("Faucet cooldown must be >= 0")]
+    BadCooldown,
 }
 
 #[error_code]
@@ -40,4 +44,108 @@ pub enum ErrorCode {
     BadBps,
     #[msg("Pyth price data is stale")]
     StalePrice,
-}
\ No newline at end of file
+    #[msg("Invalid dispute window")]
+    BadDisputeWindow,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Market has an open dispute")]
+    DisputeOpen,
+    #[msg("Market already disputed")]
+    AlreadyDisputed,
+    #[msg("No open dispute on this market")]
+    NoOpenDispute,
+    #[msg("Only bettors can dispute")]
+    NotABettor,
+    #[msg("Market already has a pending proposal")]
+    AlreadyProposed,
+    #[msg("Market is not void")]
+    NotVoid,
+    #[msg("Invalid exit lock")]
+    BadExitLock,
+    #[msg("Exits are locked for this market")]
+    ExitLocked,
+    #[msg("Exit amount exceeds position")]
+    ExitTooLarge,
+    #[msg("Operation not supported for this pricing mode")]
+    WrongPricingKind,
+    #[msg("Received fewer shares than min_shares_out")]
+    SlippageExceeded,
+    #[msg("CPMM pool has no liquidity")]
+    EmptyPool,
+    #[msg("Market already has bets")]
+    MarketHasBets,
+    #[msg("Bucket bounds must be strictly increasing")]
+    BadBucketBounds,
+    #[msg("Position still has an unclaimed payout or refund")]
+    PositionNotClaimed,
+    #[msg("Market still has open positions")]
+    OpenPositions,
+    #[msg("Mint is not an allowed collateral")]
+    MintNotAllowed,
+    #[msg("Collateral mint allowlist is full")]
+    MintAllowlistFull,
+    #[msg("Invalid oracle committee members or threshold")]
+    BadCommittee,
+    #[msg("Not enough oracle committee signatures")]
+    NotEnoughAttestations,
+    #[msg("Betting is paused")]
+    BettingPaused,
+    #[msg("Market creation is paused")]
+    CreationPaused,
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+    #[msg("Invalid pause flags")]
+    BadPauseFlags,
+    #[msg("Invalid stake limits")]
+    BadStakeLimits,
+    #[msg("Bet is below the market minimum")]
+    BetTooSmall,
+    #[msg("Bet is above the market maximum")]
+    BetTooLarge,
+    #[msg("Position would exceed the per-wallet cap")]
+    WalletCapExceeded,
+    #[msg("Unknown resolver tip curve")]
+    BadTipCurve,
+    #[msg("Price confidence interval is too wide")]
+    ConfidenceTooWide,
+    #[msg("Account is not a Switchboard on-demand pull feed")]
+    InvalidSwitchboardFeed,
+    #[msg("Ladder strikes must be ascending and match the strike accounts")]
+    BadLadder,
+    #[msg("Batch claim accounts must come in (market, position, vault, escrow authority) groups")]
+    BadClaimGroup,
+    #[msg("Crank accounts must come in (position, owner, owner ATA) triples")]
+    BadCrankBatch,
+    #[msg("Crank delay must be >= 0")]
+    BadCrankDelay,
+    #[msg("Referrer does not match the position or the referral stake")]
+    BadReferrer,
+    #[msg("Referral vault is required to settle a market with referred volume")]
+    ReferralVaultRequired,
+    #[msg("Nothing to claim for this referrer")]
+    NoReferralVolume,
+    #[msg("Metadata URI is too long")]
+    MetadataUriTooLong,
+    #[msg("Scalar markets need bound_lo < bound_hi")]
+    BadScalarBounds,
+    #[msg("Series cadence, strike rule or strike step is invalid")]
+    BadSeries,
+    #[msg("Series is paused")]
+    SeriesInactive,
+    #[msg("Current series round has not ended")]
+    SeriesRoundOpen,
+    #[msg("Not enough LP shares")]
+    InsufficientLpShares,
+    #[msg("Account is not this market or one of its positions")]
+    NotMigratable,
+    #[msg("Dispute can still be ruled")]
+    RulingWindowOpen,
+    #[msg("Dispute ruling deadline has passed")]
+    RulingWindowClosed,
+    #[msg("Escrow vault still holds unclaimed funds")]
+    VaultNotEmpty,
+    #[msg("Market predates position counting and cannot be closed")]
+    PositionsUntracked,
+}
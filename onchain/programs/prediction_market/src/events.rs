@@ -5,6 +5,13 @@ pub struct Airdropped {
     pub user: Pubkey,
     pub ata: Pubkey,
     pub amount: u64,
+    pub total: u64, // cumulative for this wallet
+}
+
+#[event]
+pub struct FaucetUpdated {
+    pub amount: u64,
+    pub cooldown_secs: i64,
 }
 
 #[event]
@@ -15,6 +22,32 @@ pub struct MarketProposedAi {
     pub pot: u128,
 }
 
+#[event]
+pub struct MarketDisputedAi {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    pub proposed_outcome_idx: u8,
+    pub proposed_winners_mask: u8,
+}
+
+#[event]
+pub struct DisputeRuled {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub overturned: bool,
+    pub outcome_idx: u8,
+    pub winners_mask: u8,
+    pub bond: u64,
+}
+
+#[event]
+pub struct DisputeExpired {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64,
+}
+
 #[event]
 pub struct MarketResolvedAi {
     pub market: Pubkey,
@@ -26,13 +59,221 @@ pub struct MarketResolvedAi {
     pub payout_pool: u128,
 }
 
+#[event]
+pub struct MarketVoidedAi {
+    pub market: Pubkey,
+    pub pot: u128,
+    pub by_attestation: bool,
+}
+
 #[event]
 pub struct MarketResolved {
     pub market: Pubkey,
     pub winning_side: u8, // 1=YES, 2=NO, 3=VOID
     pub resolved_price_1e6: i64,
+    pub conf_1e6: u64,
+    pub publish_time: i64,
     pub pot: u128,
     pub fee: u128,
     pub tip: u128,
     pub payout_pool: u128,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MarketResolvedScalar {
+    pub market: Pubkey,
+    pub resolved_price_1e6: i64,
+    pub conf_1e6: u64,
+    pub publish_time: i64,
+    pub long_bps: u16, // LONG share of the payout pool
+    pub pot: u128,
+    pub fee: u128,
+    pub tip: u128,
+    pub long_pool: u64,
+    pub short_pool: u64,
+}
+
+#[event]
+pub struct MarketResolvedBuckets {
+    pub market: Pubkey,
+    pub outcome_idx: u8, // 255 = VOID
+    pub resolved_price_1e6: i64,
+    pub conf_1e6: u64,
+    pub publish_time: i64,
+    pub pot: u128,
+    pub fee: u128,
+    pub tip: u128,
+    pub payout_pool: u128,
+}
+
+#[event]
+pub struct MarketClosed {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub dust_swept: u64,
+    pub vaults_closed: u8,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub oracle_kind: u8,
+    pub market_type: u8,
+    pub num_outcomes: u8,
+    pub feed_id: [u8; 32],
+    pub question_hash: [u8; 32],
+    pub comparator: u8,
+    pub bound_lo: i64,
+    pub bound_hi: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub side: u8, // 1=YES, 2=NO
+    pub amount: u64,
+    pub shares_out: u64, // CPMM shares bought; equals amount for parimutuel
+    pub yes_total: u64,
+    pub no_total: u64,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct BetPlacedMulti {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_idx: u8,
+    pub amount: u64,
+    pub position_stake: u64,
+    pub outcome_tvl: u64,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct PositionExited {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_idx: u8, // binary: 0=YES, 1=NO
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct Claimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_idx: u8, // binary: 0=YES, 1=NO
+    pub amount: u64,
+    pub crank_fee: u64, // kept by the cranker when pushed by crank_payout
+}
+
+#[event]
+pub struct Refunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome_idx: u8, // binary: 0=YES, 1=NO
+    pub amount: u64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub yes_amount: u64,
+    pub no_amount: u64,
+    pub shares: u64,
+    pub yes_total: u64,
+    pub no_total: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub yes_amount: u64,
+    pub no_amount: u64,
+    pub shares: u64,
+    pub fee: u64, // exit fee left in the vaults
+}
+
+#[event]
+pub struct LpClaimed {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub residual: u64,  // winning-side payout plus the losing-side stake, or both stakes on a void
+    pub fee_share: u64, // slice of the protocol fee
+}
+
+#[event]
+pub struct SeriesCreated {
+    pub series: Pubkey,
+    pub authority: Pubkey,
+    pub id: u64,
+    pub feed_id: [u8; 32],
+    pub comparator: u8,
+    pub cadence_secs: i64,
+    pub strike_rule: u8,
+    pub strike_step: i64,
+}
+
+#[event]
+pub struct SeriesRolled {
+    pub series: Pubkey,
+    pub market: Pubkey,
+    pub round: u64,
+    pub strike: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct ReferralClaimed {
+    pub market: Pubkey,
+    pub referrer: Pubkey,
+    pub volume: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MarketMigrated {
+    pub market: Pubkey,
+    pub from_version: u8,
+    pub version: u8,
+    pub positions: u16, // positions brought up to date alongside the market
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub from_version: u8,
+    pub version: u8,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub treasury_wallet: Pubkey,
+    pub fee_bps: u16,
+    pub resolver_bps: u16,
+    pub creator_bps: u16,
+    pub referral_bps: u16,
+    pub lp_fee_share_bps: u16,
+    pub resolver_tip_cap: u64,
+    pub tip_curve: u8,
+    pub dispute_window_secs: i64,
+    pub dispute_bond: u64,
+    pub dispute_reward_bps: u16,
+    pub dispute_authority: Pubkey,
+    pub exit_fee_bps: u16,
+    pub exit_lock_secs: i64,
+    pub max_bet_cap: u64,
+    pub max_per_wallet_cap: u64,
+    pub crank_fee_bps: u16,
+    pub crank_fee_cap: u64,
+    pub crank_delay_secs: i64,
+    pub allowed_mints_len: u8,
+    pub paused: u8,
+}
@@ -1,40 +1,64 @@
 use anchor_lang::prelude::*;
-use crate::{constants::BPS_DENOM, errors::ErrorCode, state::Config};
-
-pub fn init_config(
-    ctx: Context<InitConfig>,
-    fee_bps: u16,
-    resolver_bps: u16,
-    creator_bps: u16,
-    resolver_tip_cap: u64,
-) -> Result<()> {
-    require!((fee_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
-    require!((resolver_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
-    require!((creator_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+use anchor_spl::token_interface::Mint;
+use crate::{
+    constants::{BPS_DENOM, CONFIG_VERSION, MAX_COLLATERAL_MINTS, MAX_CRANK_FEE_BPS, PAUSE_ALL, TIP_CURVE_LINEAR},
+    errors::ErrorCode,
+    events::ConfigUpdated,
+    state::Config,
+};
+
+pub fn init_config(ctx: Context<InitConfig>, p: InitConfigParams) -> Result<()> {
+    require!((p.fee_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    require!((p.resolver_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    require!((p.creator_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    require!((p.referral_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
     require!(
-        (fee_bps as u64) + (resolver_bps as u64) + (creator_bps as u64) <= BPS_DENOM,
+        (p.fee_bps as u64) + (p.resolver_bps as u64) + (p.creator_bps as u64)
+            + (p.referral_bps as u64)
+            <= BPS_DENOM,
         ErrorCode::BadBps
     );
+    require!((p.dispute_reward_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    require!(p.dispute_window_secs >= 0, ErrorCode::BadDisputeWindow);
+    require!((p.exit_fee_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    require!(p.exit_lock_secs >= 0, ErrorCode::BadExitLock);
+    require!(p.tip_curve <= TIP_CURVE_LINEAR, ErrorCode::BadTipCurve);
+    require!(p.crank_fee_bps <= MAX_CRANK_FEE_BPS, ErrorCode::BadBps);
+    require!(p.crank_delay_secs >= 0, ErrorCode::BadCrankDelay);
+    require!((p.lp_fee_share_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
 
     let cfg = &mut ctx.accounts.config;
     cfg.admin = ctx.accounts.admin.key();
     cfg.treasury_wallet = ctx.accounts.treasury_wallet.key();
-    cfg.fee_bps = fee_bps;
-    cfg.resolver_bps = resolver_bps;
-    cfg.creator_bps = creator_bps;
-    cfg.resolver_tip_cap = resolver_tip_cap;
-    
+    cfg.fee_bps = p.fee_bps;
+    cfg.resolver_bps = p.resolver_bps;
+    cfg.creator_bps = p.creator_bps;
+    cfg.resolver_tip_cap = p.resolver_tip_cap;
+    cfg.dispute_window_secs = p.dispute_window_secs;
+    cfg.dispute_bond = p.dispute_bond;
+    cfg.dispute_reward_bps = p.dispute_reward_bps;
+    cfg.dispute_authority = p.dispute_authority;
+    cfg.exit_fee_bps = p.exit_fee_bps;
+    cfg.exit_lock_secs = p.exit_lock_secs;
+    cfg.allowed_mints = [Pubkey::default(); MAX_COLLATERAL_MINTS];
+    cfg.allowed_mints_len = 0;
+    cfg.pending_admin = Pubkey::default();
+    cfg.paused = 0;
+    cfg.max_bet_cap = p.max_bet_cap;
+    cfg.max_per_wallet_cap = p.max_per_wallet_cap;
+    cfg.tip_curve = p.tip_curve;
+    cfg.crank_fee_bps = p.crank_fee_bps;
+    cfg.crank_fee_cap = p.crank_fee_cap;
+    cfg.crank_delay_secs = p.crank_delay_secs;
+    cfg.referral_bps = p.referral_bps;
+    cfg.lp_fee_share_bps = p.lp_fee_share_bps;
+    cfg.version = CONFIG_VERSION;
+
+    emit_config_updated(cfg);
     Ok(())
 }
 
-pub fn update_config(
-    ctx: Context<UpdateConfig>,
-    fee_bps: Option<u16>,
-    resolver_bps: Option<u16>,
-    creator_bps: Option<u16>,
-    resolver_tip_cap: Option<u64>,
-    new_treasury: Option<Pubkey>,
-) -> Result<()> {
+pub fn update_config(ctx: Context<UpdateConfig>, p: UpdateConfigParams) -> Result<()> {
     require_keys_eq!(
         ctx.accounts.config.admin,
         ctx.accounts.admin.key(),
@@ -45,38 +69,276 @@ pub fn update_config(
     let cur_fee = cfg.fee_bps as u64;
     let cur_res = cfg.resolver_bps as u64;
     let cur_creator = cfg.creator_bps as u64;
+    let cur_referral = cfg.referral_bps as u64;
 
-    let next_fee = fee_bps.map(|v| v as u64).unwrap_or(cur_fee);
-    let next_res = resolver_bps.map(|v| v as u64).unwrap_or(cur_res);
-    let next_creator = creator_bps.map(|v| v as u64).unwrap_or(cur_creator);
+    let next_fee = p.fee_bps.map(|v| v as u64).unwrap_or(cur_fee);
+    let next_res = p.resolver_bps.map(|v| v as u64).unwrap_or(cur_res);
+    let next_creator = p.creator_bps.map(|v| v as u64).unwrap_or(cur_creator);
+    let next_referral = p.referral_bps.map(|v| v as u64).unwrap_or(cur_referral);
 
     require!(next_fee <= BPS_DENOM, ErrorCode::BadBps);
     require!(next_res <= BPS_DENOM, ErrorCode::BadBps);
     require!(next_creator <= BPS_DENOM, ErrorCode::BadBps);
-    require!(next_fee + next_res + next_creator <= BPS_DENOM, ErrorCode::BadBps);
+    require!(next_referral <= BPS_DENOM, ErrorCode::BadBps);
+    require!(
+        next_fee + next_res + next_creator + next_referral <= BPS_DENOM,
+        ErrorCode::BadBps
+    );
+    if let Some(v) = p.dispute_reward_bps {
+        require!((v as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    }
+    if let Some(v) = p.dispute_window_secs {
+        require!(v >= 0, ErrorCode::BadDisputeWindow);
+    }
+    if let Some(v) = p.exit_fee_bps {
+        require!((v as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    }
+    if let Some(v) = p.exit_lock_secs {
+        require!(v >= 0, ErrorCode::BadExitLock);
+    }
+    if let Some(v) = p.tip_curve {
+        require!(v <= TIP_CURVE_LINEAR, ErrorCode::BadTipCurve);
+    }
+    if let Some(v) = p.crank_fee_bps {
+        require!(v <= MAX_CRANK_FEE_BPS, ErrorCode::BadBps);
+    }
+    if let Some(v) = p.crank_delay_secs {
+        require!(v >= 0, ErrorCode::BadCrankDelay);
+    }
+    if let Some(v) = p.lp_fee_share_bps {
+        require!((v as u64) <= BPS_DENOM, ErrorCode::BadBps);
+    }
 
     let cfg = &mut ctx.accounts.config;
-    if let Some(v) = fee_bps {
+    if let Some(v) = p.fee_bps {
         cfg.fee_bps = v;
     }
-    if let Some(v) = resolver_bps {
+    if let Some(v) = p.resolver_bps {
         cfg.resolver_bps = v;
     }
-    if let Some(v) = creator_bps {
+    if let Some(v) = p.creator_bps {
         cfg.creator_bps = v;
     }
-    if let Some(v) = resolver_tip_cap {
+    if let Some(v) = p.resolver_tip_cap {
         cfg.resolver_tip_cap = v;
     }
-    if let Some(v) = new_treasury {
+    if let Some(v) = p.new_treasury {
         cfg.treasury_wallet = v;
     }
-    
+    if let Some(v) = p.dispute_window_secs {
+        cfg.dispute_window_secs = v;
+    }
+    if let Some(v) = p.dispute_bond {
+        cfg.dispute_bond = v;
+    }
+    if let Some(v) = p.dispute_reward_bps {
+        cfg.dispute_reward_bps = v;
+    }
+    if let Some(v) = p.dispute_authority {
+        cfg.dispute_authority = v;
+    }
+    if let Some(v) = p.exit_fee_bps {
+        cfg.exit_fee_bps = v;
+    }
+    if let Some(v) = p.exit_lock_secs {
+        cfg.exit_lock_secs = v;
+    }
+    if let Some(v) = p.max_bet_cap {
+        cfg.max_bet_cap = v;
+    }
+    if let Some(v) = p.max_per_wallet_cap {
+        cfg.max_per_wallet_cap = v;
+    }
+    if let Some(v) = p.tip_curve {
+        cfg.tip_curve = v;
+    }
+    if let Some(v) = p.crank_fee_bps {
+        cfg.crank_fee_bps = v;
+    }
+    if let Some(v) = p.crank_fee_cap {
+        cfg.crank_fee_cap = v;
+    }
+    if let Some(v) = p.crank_delay_secs {
+        cfg.crank_delay_secs = v;
+    }
+    if let Some(v) = p.referral_bps {
+        cfg.referral_bps = v;
+    }
+    if let Some(v) = p.lp_fee_share_bps {
+        cfg.lp_fee_share_bps = v;
+    }
+
+    emit_config_updated(cfg);
+    Ok(())
+}
+
+/// Allow markets to be created with `mint` as collateral (SPL Token or Token-2022)
+pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let mint = ctx.accounts.mint.key();
+    let cfg = &mut ctx.accounts.config;
+    if cfg.is_mint_allowed(&mint) {
+        return Ok(());
+    }
+
+    let len = cfg.allowed_mints_len as usize;
+    require!(len < MAX_COLLATERAL_MINTS, ErrorCode::MintAllowlistFull);
+    cfg.allowed_mints[len] = mint;
+    cfg.allowed_mints_len += 1;
+
+    emit_config_updated(cfg);
     Ok(())
 }
 
+/// Stop new markets from using `mint`; existing markets keep settling in it
+pub fn remove_collateral_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let cfg = &mut ctx.accounts.config;
+    let len = cfg.allowed_mints_len as usize;
+    let i = cfg
+        .allowed_mints()
+        .iter()
+        .position(|m| *m == mint)
+        .ok_or(ErrorCode::MintNotAllowed)?;
+
+    // swap-remove keeps the live entries packed at the front
+    cfg.allowed_mints[i] = cfg.allowed_mints[len - 1];
+    cfg.allowed_mints[len - 1] = Pubkey::default();
+    cfg.allowed_mints_len -= 1;
+
+    emit_config_updated(cfg);
+    Ok(())
+}
+
+/// First step of an admin handover; `Pubkey::default()` cancels a pending one
+pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    ctx.accounts.config.pending_admin = new_admin;
+    emit_config_updated(&ctx.accounts.config);
+    Ok(())
+}
+
+/// Second step: the proposed key signs to take over
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let cfg = &mut ctx.accounts.config;
+    require!(cfg.pending_admin != Pubkey::default(), ErrorCode::Unauthorized);
+    require_keys_eq!(
+        cfg.pending_admin,
+        ctx.accounts.new_admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    cfg.admin = cfg.pending_admin;
+    cfg.pending_admin = Pubkey::default();
+    emit_config_updated(cfg);
+    Ok(())
+}
+
+/// Replace the pause bits (PAUSE_BETTING | PAUSE_CREATION | PAUSE_CLAIMS); 0 resumes everything
+pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(paused & !PAUSE_ALL == 0, ErrorCode::BadPauseFlags);
+
+    ctx.accounts.config.paused = paused;
+    emit_config_updated(&ctx.accounts.config);
+    Ok(())
+}
+
+/// Full config snapshot after any admin change, so indexers never need to read the account
+fn emit_config_updated(cfg: &Config) {
+    emit!(ConfigUpdated {
+        admin: cfg.admin,
+        pending_admin: cfg.pending_admin,
+        treasury_wallet: cfg.treasury_wallet,
+        fee_bps: cfg.fee_bps,
+        resolver_bps: cfg.resolver_bps,
+        creator_bps: cfg.creator_bps,
+        referral_bps: cfg.referral_bps,
+        lp_fee_share_bps: cfg.lp_fee_share_bps,
+        resolver_tip_cap: cfg.resolver_tip_cap,
+        tip_curve: cfg.tip_curve,
+        dispute_window_secs: cfg.dispute_window_secs,
+        dispute_bond: cfg.dispute_bond,
+        dispute_reward_bps: cfg.dispute_reward_bps,
+        dispute_authority: cfg.dispute_authority,
+        exit_fee_bps: cfg.exit_fee_bps,
+        exit_lock_secs: cfg.exit_lock_secs,
+        max_bet_cap: cfg.max_bet_cap,
+        max_per_wallet_cap: cfg.max_per_wallet_cap,
+        crank_fee_bps: cfg.crank_fee_bps,
+        crank_fee_cap: cfg.crank_fee_cap,
+        crank_delay_secs: cfg.crank_delay_secs,
+        allowed_mints_len: cfg.allowed_mints_len,
+        paused: cfg.paused,
+    });
+}
+
 // ============ Accounts ============
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitConfigParams {
+    pub fee_bps: u16,
+    pub resolver_bps: u16,
+    pub creator_bps: u16,
+    pub resolver_tip_cap: u64,
+    pub dispute_window_secs: i64,
+    pub dispute_bond: u64,
+    pub dispute_reward_bps: u16,
+    pub dispute_authority: Pubkey,
+    pub exit_fee_bps: u16,
+    pub exit_lock_secs: i64, // exits close this long before end_ts
+    pub max_bet_cap: u64,        // 0 = uncapped
+    pub max_per_wallet_cap: u64, // 0 = uncapped
+    pub tip_curve: u8,           // TIP_CURVE_FLAT or TIP_CURVE_LINEAR
+    pub crank_fee_bps: u16,      // <= MAX_CRANK_FEE_BPS
+    pub crank_fee_cap: u64,      // 0 = uncapped
+    pub crank_delay_secs: i64,   // crank opens this long after end_ts
+    pub referral_bps: u16,       // of referred volume; counts toward the fee bps sum
+    pub lp_fee_share_bps: u16,   // of the protocol fee, paid to liquidity providers
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct UpdateConfigParams {
+    pub fee_bps: Option<u16>,
+    pub resolver_bps: Option<u16>,
+    pub creator_bps: Option<u16>,
+    pub resolver_tip_cap: Option<u64>,
+    pub new_treasury: Option<Pubkey>,
+    pub dispute_window_secs: Option<i64>,
+    pub dispute_bond: Option<u64>,
+    pub dispute_reward_bps: Option<u16>,
+    pub dispute_authority: Option<Pubkey>,
+    pub exit_fee_bps: Option<u16>,
+    pub exit_lock_secs: Option<i64>,
+    pub max_bet_cap: Option<u64>,
+    pub max_per_wallet_cap: Option<u64>,
+    pub tip_curve: Option<u8>,
+    pub crank_fee_bps: Option<u16>,
+    pub crank_fee_cap: Option<u64>,
+    pub crank_delay_secs: Option<i64>,
+    pub referral_bps: Option<u16>,
+    pub lp_fee_share_bps: Option<u16>,
+}
+
 #[derive(Accounts)]
 pub struct InitConfig<'info> {
     #[account(mut)]
@@ -106,7 +368,34 @@ pub struct UpdateConfig<'info> {
     pub config: Account<'info, Config>,
 }
 
+#[derive(Accounts)]
+pub struct AddCollateralMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+}
+
 pub fn close_config(ctx: Context<CloseConfig>) -> Result<()> {
+    // Read the admin straight from the data so configs with an older layout can still be closed
+    let stored_admin = {
+        let data = ctx.accounts.config.try_borrow_data()?;
+        require!(data.len() >= 8 + 32, ErrorCode::Unauthorized);
+        Pubkey::try_from(&data[8..40]).map_err(|_| error!(ErrorCode::Unauthorized))?
+    };
+    require_keys_eq!(stored_admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
     let config_lamports = ctx.accounts.config.lamports();
     **ctx.accounts.config.try_borrow_mut_lamports()? -= config_lamports;
     **ctx.accounts.admin.try_borrow_mut_lamports()? += config_lamports;
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, MintTo, Token, TokenAccount},
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
 };
 use mpl_token_metadata::{
     instructions::CreateMetadataAccountV3CpiBuilder,
@@ -10,20 +10,34 @@ use mpl_token_metadata::{
 };
 
 use crate::{
-    constants::{AIRDROP_AMOUNT, USDC_MINT},
-    errors::AirdropError,
-    events::Airdropped,
-    state::AirdropClaim,
+    constants::{FAUCET_CLAIM_SEED, FAUCET_SEED, USDC_MINT},
+    errors::{AirdropError, ErrorCode},
+    events::{Airdropped, FaucetUpdated},
+    state::{AirdropClaim, Config, FaucetConfig},
 };
 
-pub fn airdrop_once(ctx: Context<AirdropOnce>) -> Result<()> {
+/// Mint `faucet.amount` test USDC to `user`; refillable once `faucet.cooldown_secs` has
+/// passed since the wallet's last draw
+pub fn airdrop(ctx: Context<Airdrop>) -> Result<()> {
     require_keys_eq!(ctx.accounts.mint.key(), USDC_MINT, AirdropError::WrongMint);
-    require!(!ctx.accounts.claim.claimed, AirdropError::AlreadyClaimed);
+
+    let amount = ctx.accounts.faucet.amount;
+    require!(amount > 0, AirdropError::FaucetDisabled);
+
+    let now = Clock::get()?.unix_timestamp;
+    let claim = &ctx.accounts.claim;
+    if claim.claimed {
+        let next = claim
+            .when
+            .checked_add(ctx.accounts.faucet.cooldown_secs)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(now >= next, AirdropError::CooldownActive);
+    }
 
     let bump = ctx.bumps.mint_authority;
     let signer_seeds: &[&[u8]] = &[b"mint-auth", &[bump]];
 
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             MintTo {
@@ -33,21 +47,67 @@ pub fn airdrop_once(ctx: Context<AirdropOnce>) -> Result<()> {
             },
             &[signer_seeds],
         ),
-        AIRDROP_AMOUNT,
+        amount,
     )?;
 
-    ctx.accounts.claim.claimed = true;
-    ctx.accounts.claim.when = Clock::get()?.unix_timestamp;
+    let claim = &mut ctx.accounts.claim;
+    claim.claimed = true;
+    claim.when = now;
+    claim.total = claim.total.checked_add(amount).ok_or(ErrorCode::Overflow)?;
 
     emit!(Airdropped {
         user: ctx.accounts.user.key(),
         ata: ctx.accounts.user_ata.key(),
-        amount: AIRDROP_AMOUNT,
+        amount,
+        total: claim.total,
     });
 
     Ok(())
 }
 
+pub fn init_faucet(ctx: Context<InitFaucet>, amount: u64, cooldown_secs: i64) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(cooldown_secs >= 0, AirdropError::BadCooldown);
+
+    let faucet = &mut ctx.accounts.faucet;
+    faucet.amount = amount;
+    faucet.cooldown_secs = cooldown_secs;
+
+    emit!(FaucetUpdated { amount, cooldown_secs });
+    Ok(())
+}
+
+pub fn update_faucet(
+    ctx: Context<UpdateFaucet>,
+    amount: Option<u64>,
+    cooldown_secs: Option<i64>,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let faucet = &mut ctx.accounts.faucet;
+    if let Some(v) = amount {
+        faucet.amount = v;
+    }
+    if let Some(v) = cooldown_secs {
+        require!(v >= 0, AirdropError::BadCooldown);
+        faucet.cooldown_secs = v;
+    }
+
+    emit!(FaucetUpdated {
+        amount: faucet.amount,
+        cooldown_secs: faucet.cooldown_secs,
+    });
+    Ok(())
+}
+
 pub fn set_metadata(ctx: Context<SetMetadata>, uri: String) -> Result<()> {
     require_keys_eq!(
         ctx.accounts.token_metadata_program.key(),
@@ -105,7 +165,7 @@ pub fn set_metadata(ctx: Context<SetMetadata>, uri: String) -> Result<()> {
 // ============ Accounts ============
 
 #[derive(Accounts)]
-pub struct AirdropOnce<'info> {
+pub struct Airdrop<'info> {
     /// CHECK: User receiving airdrop
     pub user: UncheckedAccount<'info>,
     
@@ -113,41 +173,76 @@ pub struct AirdropOnce<'info> {
     pub payer: Signer<'info>,
     
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: PDA
     #[account(seeds = [b"mint-auth"], bump)]
     pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(seeds = [FAUCET_SEED], bump)]
+    pub faucet: Account<'info, FaucetConfig>,
     
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_ata: Account<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = payer,
         space = 8 + AirdropClaim::SIZE,
-        seeds = [b"claim", user.key().as_ref()],
+        seeds = [FAUCET_CLAIM_SEED, user.key().as_ref()],
         bump
     )]
     pub claim: Account<'info, AirdropClaim>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct InitFaucet<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = FaucetConfig::SPACE,
+        seeds = [FAUCET_SEED],
+        bump
+    )]
+    pub faucet: Account<'info, FaucetConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFaucet<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [FAUCET_SEED], bump)]
+    pub faucet: Account<'info, FaucetConfig>,
+}
+
 #[derive(Accounts)]
 pub struct SetMetadata<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: PDA
     #[account(seeds = [b"mint-auth"], bump)]
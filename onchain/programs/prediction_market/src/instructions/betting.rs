@@ -1,20 +1,30 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::{
-    constants::{ESCROW_SEED, OUTCOME_PREFIX, SIDE_NO, SIDE_YES},
+    constants::{
+        BPS_DENOM, ESCROW_SEED, OUTCOME_PREFIX, PAUSE_BETTING, POSITION_VERSION, SIDE_NO, SIDE_YES,
+    },
     errors::ErrorCode,
-    state::{Market, PositionBin, PositionMulti},
-    types::{OracleKind, Side},
+    events::{BetPlaced, BetPlacedMulti, PositionExited},
+    instructions::referral::{accrue_referral, release_referral},
+    state::{Config, Market, PositionBin, PositionMulti},
+    types::{PricingKind, Side},
+    utils::{cpmm_buy, escrow_signer_seeds, mul_div_u64, outcome_signer_seeds},
 };
 
-pub fn place_bet(ctx: Context<PlaceBet>, side: Side, amount: u64) -> Result<()> {
+pub fn place_bet(
+    ctx: Context<PlaceBet>,
+    side: Side,
+    amount: u64,
+    min_shares_out: u64,
+) -> Result<()> {
     require!(amount > 0, ErrorCode::InvalidAmount);
     require!(
-        ctx.accounts.market.oracle_kind == OracleKind::Pyth as u8,
+        ctx.accounts.market.is_price_binary(),
         ErrorCode::BadMarketType
     );
 
@@ -22,34 +32,63 @@ pub fn place_bet(ctx: Context<PlaceBet>, side: Side, amount: u64) -> Result<()>
     require!(now < ctx.accounts.market.end_ts, ErrorCode::TooLateToBet);
     require!(!ctx.accounts.market.settled, ErrorCode::AlreadySettled);
 
+    let pos = &ctx.accounts.position;
+    let pos_total = pos.yes_bet
+        .checked_add(pos.no_bet)
+        .and_then(|v| v.checked_add(amount))
+        .ok_or(ErrorCode::Overflow)?;
+    ctx.accounts.market.check_stake_limits(amount, pos_total)?;
+
     let (vault_ai, is_yes) = match side {
         Side::Yes => (&ctx.accounts.escrow_vault_yes, true),
         Side::No => (&ctx.accounts.escrow_vault_no, false),
     };
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_ata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: vault_ai.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount,
+        ctx.accounts.mint.decimals,
     )?;
 
     let pos = &mut ctx.accounts.position;
-    if pos.user == Pubkey::default() {
+    let fresh = pos.user == Pubkey::default();
+    if fresh {
         pos.user = ctx.accounts.user.key();
         pos.market = ctx.accounts.market.key();
         pos.yes_bet = 0;
         pos.no_bet = 0;
         pos.claimed = false;
+        pos.yes_shares = 0;
+        pos.no_shares = 0;
+        pos.referrer = Pubkey::default();
+        pos.version = POSITION_VERSION;
+        ctx.accounts.market.open_positions = ctx.accounts.market.open_positions
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
     }
     require_keys_eq!(pos.user, ctx.accounts.user.key(), ErrorCode::Unauthorized);
     require_keys_eq!(pos.market, ctx.accounts.market.key(), ErrorCode::Unauthorized);
 
+    accrue_referral(
+        &mut pos.referrer,
+        fresh,
+        &mut ctx.accounts.market,
+        &ctx.accounts.user,
+        ctx.accounts.referrer.as_ref(),
+        ctx.accounts.referral_stake.as_ref(),
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        amount,
+    )?;
+
     let m = &mut ctx.accounts.market;
     if is_yes {
         m.yes_total = m.yes_total.checked_add(amount).ok_or(ErrorCode::Overflow)?;
@@ -59,30 +98,191 @@ pub fn place_bet(ctx: Context<PlaceBet>, side: Side, amount: u64) -> Result<()>
         pos.no_bet = pos.no_bet.checked_add(amount).ok_or(ErrorCode::Overflow)?;
     }
 
+    // Parimutuel stake counts 1:1; CPMM buys shares off the curve
+    let shares_out = if m.pricing_kind == PricingKind::Cpmm as u8 {
+        let shares_out = if is_yes {
+            let (out, pool_yes, pool_no) = cpmm_buy(m.pool_yes, m.pool_no, amount)?;
+            m.pool_yes = pool_yes;
+            m.pool_no = pool_no;
+            m.yes_shares = m.yes_shares.checked_add(out).ok_or(ErrorCode::Overflow)?;
+            pos.yes_shares = pos.yes_shares.checked_add(out).ok_or(ErrorCode::Overflow)?;
+            out
+        } else {
+            let (out, pool_no, pool_yes) = cpmm_buy(m.pool_no, m.pool_yes, amount)?;
+            m.pool_yes = pool_yes;
+            m.pool_no = pool_no;
+            m.no_shares = m.no_shares.checked_add(out).ok_or(ErrorCode::Overflow)?;
+            pos.no_shares = pos.no_shares.checked_add(out).ok_or(ErrorCode::Overflow)?;
+            out
+        };
+        require!(shares_out >= min_shares_out, ErrorCode::SlippageExceeded);
+        shares_out
+    } else {
+        require!(amount >= min_shares_out, ErrorCode::SlippageExceeded);
+        amount
+    };
+
+    emit!(BetPlaced {
+        market: m.key(),
+        user: pos.user,
+        side: if is_yes { 1 } else { 2 },
+        amount,
+        shares_out,
+        yes_total: m.yes_total,
+        no_total: m.no_total,
+        referrer: pos.referrer,
+    });
+    Ok(())
+}
+
+/// Switches a fresh Pyth market to CPMM pricing. The creator's liquidity is split
+/// across both vaults and seeds the curve at 50/50; it is tracked as the creator's
+/// stake so a void refunds it like any other bet.
+pub fn seed_cpmm(ctx: Context<SeedCpmm>, initial_liquidity: u64) -> Result<()> {
+    require!(initial_liquidity > 0, ErrorCode::InvalidAmount);
+
+    let m = &ctx.accounts.market;
+    // Scalar payouts split the pot, so there is no per-share redemption to price
+    require!(m.is_price_binary() && !m.is_scalar(), ErrorCode::BadMarketType);
+    require_keys_eq!(ctx.accounts.user.key(), m.authority, ErrorCode::Unauthorized);
+    require!(
+        m.pricing_kind == PricingKind::Parimutuel as u8,
+        ErrorCode::WrongPricingKind
+    );
+    require!(m.yes_total == 0 && m.no_total == 0, ErrorCode::MarketHasBets);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < m.end_ts, ErrorCode::TooLateToBet);
+
+    let half_yes = initial_liquidity - initial_liquidity / 2;
+    let half_no = initial_liquidity / 2;
+
+    for (vault, amt) in [
+        (&ctx.accounts.escrow_vault_yes, half_yes),
+        (&ctx.accounts.escrow_vault_no, half_no),
+    ] {
+        if amt == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amt,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let pos = &mut ctx.accounts.position;
+    if pos.user == Pubkey::default() {
+        pos.user = ctx.accounts.user.key();
+        pos.market = ctx.accounts.market.key();
+        pos.claimed = false;
+        pos.yes_shares = 0;
+        pos.no_shares = 0;
+        pos.referrer = Pubkey::default();
+        pos.version = POSITION_VERSION;
+        ctx.accounts.market.open_positions = ctx.accounts.market.open_positions
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    pos.yes_bet = half_yes;
+    pos.no_bet = half_no;
+
+    let m = &mut ctx.accounts.market;
+    m.pricing_kind = PricingKind::Cpmm as u8;
+    m.pool_yes = initial_liquidity;
+    m.pool_no = initial_liquidity;
+    m.yes_total = half_yes;
+    m.no_total = half_no;
+
     Ok(())
 }
 
+/// Stake `user` holds on every outcome of `market` other than `outcome_idx`.
+/// `accounts` must be the wallet's multi position PDA for each of those outcomes, in index
+/// order, so none can be left out; positions never opened count as zero.
+fn other_outcomes_stake(
+    accounts: &[AccountInfo],
+    market: &Pubkey,
+    user: &Pubkey,
+    outcome_idx: u8,
+    num_outcomes: u8,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    let others = (0..num_outcomes).filter(|&i| i != outcome_idx);
+    require!(
+        accounts.len() == num_outcomes.saturating_sub(1) as usize,
+        ErrorCode::Unauthorized
+    );
+
+    let mut total: u64 = 0;
+    for (ai, i) in accounts.iter().zip(others) {
+        let (pda, _) = Pubkey::find_program_address(
+            &[b"position", market.as_ref(), user.as_ref(), &[i]],
+            program_id,
+        );
+        require_keys_eq!(ai.key(), pda, ErrorCode::Unauthorized);
+        if ai.owner != program_id {
+            continue;
+        }
+        let data = ai.try_borrow_data()?;
+        require!(
+            data.len() >= PositionMulti::STAKE_OFFSET + 8
+                && &data[..8] == PositionMulti::DISCRIMINATOR,
+            ErrorCode::Unauthorized
+        );
+        let mut stake = [0u8; 8];
+        stake.copy_from_slice(&data[PositionMulti::STAKE_OFFSET..PositionMulti::STAKE_OFFSET + 8]);
+        total = total.checked_add(u64::from_le_bytes(stake)).ok_or(ErrorCode::Overflow)?;
+    }
+    Ok(total)
+}
+
 pub fn place_bet_multi(ctx: Context<PlaceBetMulti>, outcome_idx: u8, amount: u64) -> Result<()> {
     require!(amount > 0, ErrorCode::InvalidAmount);
     
     let m = &mut ctx.accounts.market;
-    require!(m.oracle_kind == OracleKind::Ai as u8, ErrorCode::BadMarketType);
+    require!(m.uses_outcome_vaults(), ErrorCode::BadMarketType);
     require!(!m.settled, ErrorCode::AlreadySettled);
     require!(outcome_idx < m.num_outcomes, ErrorCode::BadMarketType);
     
     let now = Clock::get()?.unix_timestamp;
     require!(now < m.end_ts, ErrorCode::TooLateToBet);
 
-    token::transfer(
+    // The wallet cap covers every outcome, not just this position
+    let mut wallet_total = ctx.accounts.position.stake.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    if m.max_per_wallet > 0 {
+        let others = other_outcomes_stake(
+            ctx.remaining_accounts,
+            &m.key(),
+            &ctx.accounts.user.key(),
+            outcome_idx,
+            m.num_outcomes,
+            ctx.program_id,
+        )?;
+        wallet_total = wallet_total.checked_add(others).ok_or(ErrorCode::Overflow)?;
+    }
+    m.check_stake_limits(amount, wallet_total)?;
+
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_ata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.escrow_vault_for_outcome.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount,
+        ctx.accounts.mint.decimals,
     )?;
 
     let idx = outcome_idx as usize;
@@ -91,19 +291,207 @@ pub fn place_bet_multi(ctx: Context<PlaceBetMulti>, outcome_idx: u8, amount: u64
         .ok_or(ErrorCode::Overflow)?;
 
     let pos = &mut ctx.accounts.position;
-    if pos.user == Pubkey::default() {
+    let fresh = pos.user == Pubkey::default();
+    if fresh {
         pos.user = ctx.accounts.user.key();
         pos.market = m.key();
         pos.outcome_idx = outcome_idx;
         pos.claimed = false;
         pos.stake = 0;
+        pos.referrer = Pubkey::default();
+        pos.version = POSITION_VERSION;
+        m.open_positions = m.open_positions.checked_add(1).ok_or(ErrorCode::Overflow)?;
     }
     require_keys_eq!(pos.user, ctx.accounts.user.key(), ErrorCode::Unauthorized);
     require_keys_eq!(pos.market, m.key(), ErrorCode::Unauthorized);
     require!(pos.outcome_idx == outcome_idx, ErrorCode::Unauthorized);
 
     pos.stake = pos.stake.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-    
+
+    accrue_referral(
+        &mut pos.referrer,
+        fresh,
+        m,
+        &ctx.accounts.user,
+        ctx.accounts.referrer.as_ref(),
+        ctx.accounts.referral_stake.as_ref(),
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        amount,
+    )?;
+
+    emit!(BetPlacedMulti {
+        market: m.key(),
+        user: pos.user,
+        outcome_idx,
+        amount,
+        position_stake: pos.stake,
+        outcome_tvl: m.tvl_per_outcome[idx],
+        referrer: pos.referrer,
+    });
+    Ok(())
+}
+
+pub fn exit_position(ctx: Context<ExitPosition>, side: Side, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let m = &ctx.accounts.market;
+    require!(m.is_price_binary(), ErrorCode::BadMarketType);
+    require!(
+        m.pricing_kind == PricingKind::Parimutuel as u8,
+        ErrorCode::WrongPricingKind
+    );
+    require!(!m.settled, ErrorCode::AlreadySettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < m.exit_lock_ts, ErrorCode::ExitLocked);
+
+    let pos = &ctx.accounts.position;
+    let staked = match side {
+        Side::Yes => pos.yes_bet,
+        Side::No => pos.no_bet,
+    };
+    require!(amount <= staked, ErrorCode::ExitTooLarge);
+
+    // Exit fee stays in the vault for the remaining bettors
+    let fee = mul_div_u64(amount, m.exit_fee_bps_snapshot as u64, BPS_DENOM)?;
+    let out = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+
+    let market_key = m.key();
+    let (side_seed, bump, vault, auth) = match side {
+        Side::Yes => (
+            SIDE_YES,
+            ctx.bumps.escrow_authority_yes,
+            &ctx.accounts.escrow_vault_yes,
+            ctx.accounts.escrow_authority_yes.to_account_info(),
+        ),
+        Side::No => (
+            SIDE_NO,
+            ctx.bumps.escrow_authority_no,
+            &ctx.accounts.escrow_vault_no,
+            ctx.accounts.escrow_authority_no.to_account_info(),
+        ),
+    };
+
+    if out > 0 {
+        let bump_arr = [bump];
+        let seeds = escrow_signer_seeds(&market_key, side_seed, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: auth,
+                },
+                &[&seeds],
+            ),
+            out,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let pos = &mut ctx.accounts.position;
+    let m = &mut ctx.accounts.market;
+    match side {
+        Side::Yes => {
+            pos.yes_bet -= amount;
+            m.yes_total = m.yes_total.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        }
+        Side::No => {
+            pos.no_bet -= amount;
+            m.no_total = m.no_total.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        }
+    }
+    m.exit_fee_pool = m.exit_fee_pool.checked_add(fee).ok_or(ErrorCode::Overflow)?;
+
+    release_referral(
+        pos.referrer,
+        m,
+        ctx.accounts.referral_stake.as_ref(),
+        ctx.program_id,
+        amount,
+    )?;
+
+    emit!(PositionExited {
+        market: m.key(),
+        user: pos.user,
+        outcome_idx: if side == Side::Yes { 0 } else { 1 },
+        amount,
+        fee,
+    });
+    Ok(())
+}
+
+pub fn exit_position_multi(
+    ctx: Context<ExitPositionMulti>,
+    outcome_idx: u8,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let m = &ctx.accounts.market;
+    require!(m.uses_outcome_vaults(), ErrorCode::BadMarketType);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+    require!(outcome_idx < m.num_outcomes, ErrorCode::BadMarketType);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < m.exit_lock_ts, ErrorCode::ExitLocked);
+
+    let pos = &ctx.accounts.position;
+    require!(pos.outcome_idx == outcome_idx, ErrorCode::Unauthorized);
+    require!(amount <= pos.stake, ErrorCode::ExitTooLarge);
+
+    let fee = mul_div_u64(amount, m.exit_fee_bps_snapshot as u64, BPS_DENOM)?;
+    let out = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+
+    let market_key = m.key();
+    if out > 0 {
+        let idx_arr = [outcome_idx];
+        let bump_arr = [ctx.bumps.escrow_authority_outcome];
+        let seeds = outcome_signer_seeds(&market_key, &idx_arr, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_vault_for_outcome.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: ctx.accounts.escrow_authority_outcome.to_account_info(),
+                },
+                &[&seeds],
+            ),
+            out,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let pos = &mut ctx.accounts.position;
+    pos.stake -= amount;
+
+    let m = &mut ctx.accounts.market;
+    let idx = outcome_idx as usize;
+    m.tvl_per_outcome[idx] = m.tvl_per_outcome[idx]
+        .checked_sub(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    m.exit_fee_pool = m.exit_fee_pool.checked_add(fee).ok_or(ErrorCode::Overflow)?;
+
+    release_referral(
+        pos.referrer,
+        m,
+        ctx.accounts.referral_stake.as_ref(),
+        ctx.program_id,
+        amount,
+    )?;
+
+    emit!(PositionExited {
+        market: m.key(),
+        user: pos.user,
+        outcome_idx,
+        amount,
+        fee,
+    });
     Ok(())
 }
 
@@ -117,15 +505,17 @@ pub struct PlaceBet<'info> {
     #[account(mut, constraint = !market.settled @ ErrorCode::AlreadySettled)]
     pub market: Account<'info, Market>,
 
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_ata: Account<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA derived in constraint
     #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
@@ -135,11 +525,21 @@ pub struct PlaceBet<'info> {
     #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
     pub escrow_authority_no: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = escrow_vault_yes.mint == mint.key())]
-    pub escrow_vault_yes: Account<'info, TokenAccount>,
-    
-    #[account(mut, constraint = escrow_vault_no.mint == mint.key())]
-    pub escrow_vault_no: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
@@ -150,10 +550,87 @@ pub struct PlaceBet<'info> {
     )]
     pub position: Account<'info, PositionBin>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: referrer wallet; fixed on the position by its first bet
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: [REFERRAL_SEED, market, referrer] PDA, verified and created in instruction
+    #[account(mut)]
+    pub referral_stake: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SeedCpmm<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, constraint = !market.settled @ ErrorCode::AlreadySettled)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PositionBin::SPACE,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, PositionBin>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -165,15 +642,17 @@ pub struct PlaceBetMulti<'info> {
     #[account(mut, constraint = !market.settled @ ErrorCode::AlreadySettled)]
     pub market: Account<'info, Market>,
 
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_ata: Account<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA derived in constraint
     #[account(seeds = [ESCROW_SEED, market.key().as_ref(), OUTCOME_PREFIX, &[outcome_idx]], bump)]
@@ -183,9 +662,10 @@ pub struct PlaceBetMulti<'info> {
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = escrow_authority_outcome
+        associated_token::authority = escrow_authority_outcome,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault_for_outcome: Account<'info, TokenAccount>,
+    pub escrow_vault_for_outcome: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
@@ -196,8 +676,139 @@ pub struct PlaceBetMulti<'info> {
     )]
     pub position: Account<'info, PositionMulti>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
-}
\ No newline at end of file
+
+    /// CHECK: referrer wallet; fixed on the position by its first bet
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: [REFERRAL_SEED, market, referrer] PDA, verified and created in instruction
+    #[account(mut)]
+    pub referral_stake: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ExitPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, PositionBin>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: referrer's stake PDA, verified in instruction; required if the position has a referrer
+    #[account(mut)]
+    pub referral_stake: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_idx: u8)]
+pub struct ExitPositionMulti<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), OUTCOME_PREFIX, &[outcome_idx]], bump)]
+    pub escrow_authority_outcome: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_outcome,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_for_outcome: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref(), &[outcome_idx]],
+        bump
+    )]
+    pub position: Account<'info, PositionMulti>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: referrer's stake PDA, verified in instruction; required if the position has a referrer
+    #[account(mut)]
+    pub referral_stake: Option<UncheckedAccount<'info>>,
+}
@@ -1,20 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::{
-    constants::{ESCROW_SEED, SIDE_NO, SIDE_YES},
+    constants::{
+        BPS_DENOM, CLAIM_GROUP_ACCOUNTS, CRANK_GROUP_ACCOUNTS, ESCROW_SEED, PAUSE_CLAIMS, SIDE_NO,
+        SIDE_YES, WINNING_SIDE_SCALAR, WINNING_SIDE_VOID,
+    },
     errors::ErrorCode,
-    state::{Market, PositionBin, PositionMulti},
-    types::OracleKind,
-    utils::{escrow_signer_seeds, mul_div_u64, outcome_signer_seeds, pda_escrow_auth_outcome},
+    events::{Claimed, Refunded},
+    state::{Config, Market, PositionBin, PositionMulti},
+    types::PricingKind,
+    utils::{
+        escrow_signer_seeds, mul_div_u64, outcome_signer_seeds, pda_escrow_auth,
+        pda_escrow_auth_outcome, read_token_account,
+    },
 };
 
 pub fn claim(ctx: Context<Claim>) -> Result<()> {
     let m = &ctx.accounts.market;
-    require!(m.oracle_kind == OracleKind::Pyth as u8, ErrorCode::BadMarketType);
+    require!(m.is_price_binary(), ErrorCode::BadMarketType);
     require!(m.settled, ErrorCode::MarketNotResolved);
 
     let pos = &mut ctx.accounts.position;
@@ -22,54 +29,58 @@ pub fn claim(ctx: Context<Claim>) -> Result<()> {
 
     let market_key = m.key();
 
-    if m.winning_side == 3 {
+    if m.winning_side == WINNING_SIDE_VOID {
         // VOID - refund 1:1
         return handle_void_refund(ctx, market_key);
     }
+    if m.winning_side == WINNING_SIDE_SCALAR {
+        return handle_scalar_claim(ctx, market_key);
+    }
 
-    let (user_side_amt, total_side_amt, win_side, win_bump, win_vault, win_auth_ai) =
-        match m.winning_side {
-            1 => (
-                pos.yes_bet,
-                m.yes_total,
-                SIDE_YES,
-                ctx.bumps.escrow_authority_yes,
-                &ctx.accounts.escrow_vault_yes,
-                ctx.accounts.escrow_authority_yes.to_account_info(),
-            ),
-            2 => (
-                pos.no_bet,
-                m.no_total,
-                SIDE_NO,
-                ctx.bumps.escrow_authority_no,
-                &ctx.accounts.escrow_vault_no,
-                ctx.accounts.escrow_authority_no.to_account_info(),
-            ),
-            _ => return Err(error!(ErrorCode::MarketNotResolved)),
-        };
-
-    require!(user_side_amt > 0, ErrorCode::NoWinningBet);
+    let (win_side, win_bump, win_vault, win_auth_ai) = match m.winning_side {
+        1 => (
+            SIDE_YES,
+            ctx.bumps.escrow_authority_yes,
+            &ctx.accounts.escrow_vault_yes,
+            ctx.accounts.escrow_authority_yes.to_account_info(),
+        ),
+        2 => (
+            SIDE_NO,
+            ctx.bumps.escrow_authority_no,
+            &ctx.accounts.escrow_vault_no,
+            ctx.accounts.escrow_authority_no.to_account_info(),
+        ),
+        _ => return Err(error!(ErrorCode::MarketNotResolved)),
+    };
+    let payout = binary_win_payout(m, pos, win_vault.amount)?;
 
     let win_bump_arr = [win_bump];
     let seeds = escrow_signer_seeds(&market_key, win_side, &win_bump_arr);
-
-    let payout = mul_div_u64(m.payout_pool, user_side_amt, total_side_amt)?;
     if payout > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.user_ata.to_account_info(),
                     authority: win_auth_ai,
                 },
                 &[&seeds],
             ),
             payout,
+            ctx.accounts.mint.decimals,
         )?;
     }
     
     pos.claimed = true;
+    emit!(Claimed {
+        market: market_key,
+        user: pos.user,
+        outcome_idx: m.winning_side - 1,
+        amount: payout,
+        crank_fee: 0,
+    });
     Ok(())
 }
 
@@ -83,19 +94,27 @@ fn handle_void_refund(ctx: Context<Claim>, market_key: Pubkey) -> Result<()> {
         let amt_yes = core::cmp::min(pos.yes_bet, ctx.accounts.escrow_vault_yes.amount);
         
         if amt_yes > 0 {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: ctx.accounts.escrow_vault_yes.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
                         to: ctx.accounts.user_ata.to_account_info(),
                         authority: ctx.accounts.escrow_authority_yes.to_account_info(),
                     },
                     &[&seeds_yes],
                 ),
                 amt_yes,
+                ctx.accounts.mint.decimals,
             )?;
         }
+        emit!(Refunded {
+            market: market_key,
+            user: pos.user,
+            outcome_idx: 0,
+            amount: amt_yes,
+        });
     }
     
     if pos.no_bet > 0 {
@@ -105,43 +124,107 @@ fn handle_void_refund(ctx: Context<Claim>, market_key: Pubkey) -> Result<()> {
         let amt_no = core::cmp::min(pos.no_bet, ctx.accounts.escrow_vault_no.amount);
         
         if amt_no > 0 {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: ctx.accounts.escrow_vault_no.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
                         to: ctx.accounts.user_ata.to_account_info(),
                         authority: ctx.accounts.escrow_authority_no.to_account_info(),
                     },
                     &[&seeds_no],
                 ),
                 amt_no,
+                ctx.accounts.mint.decimals,
             )?;
         }
+        emit!(Refunded {
+            market: market_key,
+            user: pos.user,
+            outcome_idx: 1,
+            amount: amt_no,
+        });
     }
     
     pos.claimed = true;
     Ok(())
 }
 
+/// Scalar payout: the LONG stake's share of the long pool from the yes vault plus the
+/// SHORT stake's share of the short pool from the no vault
+fn handle_scalar_claim(ctx: Context<Claim>, market_key: Pubkey) -> Result<()> {
+    let m = &ctx.accounts.market;
+    let pos = &mut ctx.accounts.position;
+    require!(pos.yes_bet > 0 || pos.no_bet > 0, ErrorCode::NoWinningBet);
+
+    let legs = [
+        (
+            0u8,
+            SIDE_YES,
+            ctx.bumps.escrow_authority_yes,
+            &ctx.accounts.escrow_vault_yes,
+            ctx.accounts.escrow_authority_yes.to_account_info(),
+            pos.yes_bet,
+            m.yes_total,
+            m.scalar_long_pool,
+        ),
+        (
+            1u8,
+            SIDE_NO,
+            ctx.bumps.escrow_authority_no,
+            &ctx.accounts.escrow_vault_no,
+            ctx.accounts.escrow_authority_no.to_account_info(),
+            pos.no_bet,
+            m.no_total,
+            m.scalar_short_pool,
+        ),
+    ];
+    for (outcome_idx, side, bump, vault, auth, bet, side_total, pool) in legs {
+        if bet == 0 {
+            continue;
+        }
+        let payout = core::cmp::min(mul_div_u64(pool, bet, side_total)?, vault.amount);
+        if payout > 0 {
+            let bump_arr = [bump];
+            let seeds = escrow_signer_seeds(&market_key, side, &bump_arr);
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.user_ata.to_account_info(),
+                        authority: auth,
+                    },
+                    &[&seeds],
+                ),
+                payout,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+        emit!(Claimed {
+            market: market_key,
+            user: pos.user,
+            outcome_idx,
+            amount: payout,
+            crank_fee: 0,
+        });
+    }
+
+    pos.claimed = true;
+    Ok(())
+}
+
 pub fn claim_multi(ctx: Context<ClaimMulti>, outcome_idx: u8) -> Result<()> {
     let m = &ctx.accounts.market;
-    require!(m.oracle_kind == OracleKind::Ai as u8, ErrorCode::BadMarketType);
+    require!(m.uses_outcome_vaults(), ErrorCode::BadMarketType);
     require!(m.settled, ErrorCode::MarketNotResolved);
 
     let pos = &mut ctx.accounts.position;
     require!(!pos.claimed, ErrorCode::AlreadyClaimed);
     require!(pos.outcome_idx == outcome_idx, ErrorCode::NoWinningBet);
 
-    // Check if user is winner
-    let is_single = m.winners_mask == 0 && m.outcome_idx != u8::MAX;
-    let user_is_winner = if is_single {
-        m.outcome_idx == outcome_idx
-    } else {
-        (m.winners_mask & (1 << outcome_idx)) != 0
-    };
-    require!(user_is_winner, ErrorCode::NoWinningBet);
-
     // Verify win authority PDA
     let market_key = m.key();
     let (win_pda, bump) = pda_escrow_auth_outcome(&market_key, outcome_idx, ctx.program_id);
@@ -149,41 +232,415 @@ pub fn claim_multi(ctx: Context<ClaimMulti>, outcome_idx: u8) -> Result<()> {
     require_keys_eq!(ctx.accounts.win_vault.owner, win_pda, ErrorCode::Unauthorized);
     require_keys_eq!(ctx.accounts.win_vault.mint, ctx.accounts.mint.key(), ErrorCode::WrongMint);
 
-    let total = m.tvl_per_outcome[outcome_idx as usize];
-    require!(total > 0, ErrorCode::NoWinningBet);
-
-    // Determine payout pool
-    let pool_u64 = if is_single {
-        core::cmp::min(m.payout_pool, ctx.accounts.win_vault.amount)
-    } else {
-        ctx.accounts.win_vault.amount
-    };
-
-    // Calculate proportional payout
-    let payout = mul_div_u64(pool_u64, pos.stake, total)?;
+    let payout = multi_win_payout(m, pos, ctx.accounts.win_vault.amount)?;
     if payout > 0 {
         let outcome_idx_arr = [outcome_idx];
         let bump_arr = [bump];
         let seeds = outcome_signer_seeds(&market_key, &outcome_idx_arr, &bump_arr);
         
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.user_ata.to_account_info(),
                     authority: ctx.accounts.win_authority.to_account_info(),
                 },
                 &[&seeds],
             ),
             payout,
+            ctx.accounts.mint.decimals,
         )?;
     }
     
     pos.claimed = true;
+    emit!(Claimed {
+        market: market_key,
+        user: pos.user,
+        outcome_idx,
+        amount: payout,
+        crank_fee: 0,
+    });
     Ok(())
 }
 
+/// Refund a multi-outcome stake 1:1 from its outcome vault once the market is void
+pub fn refund_multi(ctx: Context<ClaimMulti>, outcome_idx: u8) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.uses_outcome_vaults(), ErrorCode::BadMarketType);
+    require!(m.settled, ErrorCode::MarketNotResolved);
+    require!(m.winning_side == WINNING_SIDE_VOID, ErrorCode::NotVoid);
+
+    let pos = &mut ctx.accounts.position;
+    require!(!pos.claimed, ErrorCode::AlreadyClaimed);
+    require!(pos.outcome_idx == outcome_idx, ErrorCode::NoWinningBet);
+    require!(pos.stake > 0, ErrorCode::NoWinningBet);
+
+    let market_key = m.key();
+    let (vault_pda, bump) = pda_escrow_auth_outcome(&market_key, outcome_idx, ctx.program_id);
+    require_keys_eq!(vault_pda, ctx.accounts.win_authority.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(ctx.accounts.win_vault.owner, vault_pda, ErrorCode::Unauthorized);
+    require_keys_eq!(ctx.accounts.win_vault.mint, ctx.accounts.mint.key(), ErrorCode::WrongMint);
+
+    let amt = core::cmp::min(pos.stake, ctx.accounts.win_vault.amount);
+    if amt > 0 {
+        let outcome_idx_arr = [outcome_idx];
+        let bump_arr = [bump];
+        let seeds = outcome_signer_seeds(&market_key, &outcome_idx_arr, &bump_arr);
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: ctx.accounts.win_authority.to_account_info(),
+                },
+                &[&seeds],
+            ),
+            amt,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    pos.claimed = true;
+    emit!(Refunded {
+        market: market_key,
+        user: pos.user,
+        outcome_idx,
+        amount: amt,
+    });
+    Ok(())
+}
+
+/// Claim winnings or void refunds on many markets sharing one collateral mint.
+/// `remaining_accounts` holds (market, position, vault, escrow authority) groups; the vault
+/// is the one the position is paid from. A void binary position with stakes on both sides,
+/// or any settled scalar position, needs both vaults and has to go through `claim`.
+pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+    let groups = ctx.remaining_accounts;
+    require!(
+        !groups.is_empty() && groups.len() % CLAIM_GROUP_ACCOUNTS == 0,
+        ErrorCode::BadClaimGroup
+    );
+
+    let program_id = ctx.program_id;
+    let user_key = ctx.accounts.user.key();
+    let mint_key = ctx.accounts.mint.key();
+
+    for g in groups.chunks(CLAIM_GROUP_ACCOUNTS) {
+        let (market_ai, position_ai, vault_ai, auth_ai) = (&g[0], &g[1], &g[2], &g[3]);
+
+        let m: Account<'info, Market> = Account::try_from(market_ai)?;
+        require_keys_eq!(m.mint, mint_key, ErrorCode::WrongMint);
+        require!(m.settled, ErrorCode::MarketNotResolved);
+        let market_key = market_ai.key();
+
+        let vault = read_token_account(vault_ai)?;
+        require_keys_eq!(vault.mint, mint_key, ErrorCode::WrongMint);
+        require_keys_eq!(vault.owner, auth_ai.key(), ErrorCode::Unauthorized);
+
+        let void = m.winning_side == WINNING_SIDE_VOID;
+        if m.uses_outcome_vaults() {
+            let mut pos: Account<'info, PositionMulti> = Account::try_from(position_ai)?;
+            let (pos_pda, _) = Pubkey::find_program_address(
+                &[b"position", market_key.as_ref(), user_key.as_ref(), &[pos.outcome_idx]],
+                program_id,
+            );
+            require_keys_eq!(pos_pda, position_ai.key(), ErrorCode::Unauthorized);
+            require!(!pos.claimed, ErrorCode::AlreadyClaimed);
+
+            let (auth_pda, bump) = pda_escrow_auth_outcome(&market_key, pos.outcome_idx, program_id);
+            require_keys_eq!(auth_pda, auth_ai.key(), ErrorCode::Unauthorized);
+
+            let amount = if void {
+                require!(pos.stake > 0, ErrorCode::NoWinningBet);
+                core::cmp::min(pos.stake, vault.amount)
+            } else {
+                multi_win_payout(&m, &pos, vault.amount)?
+            };
+            let idx_arr = [pos.outcome_idx];
+            let bump_arr = [bump];
+            let seeds = outcome_signer_seeds(&market_key, &idx_arr, &bump_arr);
+            pay_from_escrow(ctx.accounts, vault_ai, auth_ai, &seeds, amount)?;
+
+            pos.claimed = true;
+            pos.exit(program_id)?;
+            emit_claim(market_key, user_key, pos.outcome_idx, amount, void);
+        } else {
+            require!(m.is_price_binary(), ErrorCode::BadMarketType);
+            let mut pos: Account<'info, PositionBin> = Account::try_from(position_ai)?;
+            let (pos_pda, _) = Pubkey::find_program_address(
+                &[b"position", market_key.as_ref(), user_key.as_ref()],
+                program_id,
+            );
+            require_keys_eq!(pos_pda, position_ai.key(), ErrorCode::Unauthorized);
+            require!(!pos.claimed, ErrorCode::AlreadyClaimed);
+
+            // Scalar positions are paid from both vaults, like two-sided void refunds
+            require!(m.winning_side != WINNING_SIDE_SCALAR, ErrorCode::BadClaimGroup);
+            let side = match m.winning_side {
+                1 => SIDE_YES,
+                2 => SIDE_NO,
+                WINNING_SIDE_VOID => match (pos.yes_bet > 0, pos.no_bet > 0) {
+                    (true, false) => SIDE_YES,
+                    (false, true) => SIDE_NO,
+                    _ => return Err(error!(ErrorCode::BadClaimGroup)),
+                },
+                _ => return Err(error!(ErrorCode::MarketNotResolved)),
+            };
+            let (auth_pda, bump) = pda_escrow_auth(&market_key, side, program_id);
+            require_keys_eq!(auth_pda, auth_ai.key(), ErrorCode::Unauthorized);
+
+            let amount = if void {
+                let bet = if side == SIDE_YES { pos.yes_bet } else { pos.no_bet };
+                core::cmp::min(bet, vault.amount)
+            } else {
+                binary_win_payout(&m, &pos, vault.amount)?
+            };
+            let bump_arr = [bump];
+            let seeds = escrow_signer_seeds(&market_key, side, &bump_arr);
+            pay_from_escrow(ctx.accounts, vault_ai, auth_ai, &seeds, amount)?;
+
+            pos.claimed = true;
+            pos.exit(program_id)?;
+            let outcome_idx = if side == SIDE_YES { 0 } else { 1 };
+            emit_claim(market_key, user_key, outcome_idx, amount, void);
+        }
+    }
+
+    Ok(())
+}
+
+/// Void markets refund the stake; anything else pays winnings
+fn emit_claim(market: Pubkey, user: Pubkey, outcome_idx: u8, amount: u64, void: bool) {
+    if void {
+        emit!(Refunded { market, user, outcome_idx, amount });
+    } else {
+        emit!(Claimed { market, user, outcome_idx, amount, crank_fee: 0 });
+    }
+}
+
+/// Transfer `amount` from an escrow vault to the claimer's ATA, signed by the escrow PDA
+fn pay_from_escrow<'info>(
+    accs: &ClaimMany<'info>,
+    vault_ai: &AccountInfo<'info>,
+    auth_ai: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accs.token_program.to_account_info(),
+            TransferChecked {
+                from: vault_ai.clone(),
+                mint: accs.mint.to_account_info(),
+                to: accs.user_ata.to_account_info(),
+                authority: auth_ai.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+        accs.mint.decimals,
+    )
+}
+
+/// Permissionless push of winnings into the owners' ATAs once the crank delay has passed.
+/// `remaining_accounts` holds (position, owner, owner ATA) triples, all paid from the one
+/// winning vault passed in: the winning side for binary markets, `outcome_idx` for multi.
+/// The cranker keeps `crank_fee_bps` of each payout, capped at `crank_fee_cap`.
+pub fn crank_payout<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankPayout<'info>>,
+    outcome_idx: u8,
+) -> Result<()> {
+    let triples = ctx.remaining_accounts;
+    require!(
+        !triples.is_empty() && triples.len() % CRANK_GROUP_ACCOUNTS == 0,
+        ErrorCode::BadCrankBatch
+    );
+
+    let m = &ctx.accounts.market;
+    require!(m.settled, ErrorCode::MarketNotResolved);
+    require!(m.winning_side != WINNING_SIDE_VOID, ErrorCode::NoWinningBet);
+    // Scalar payouts span both vaults; holders claim them directly
+    require!(m.winning_side != WINNING_SIDE_SCALAR, ErrorCode::BadMarketType);
+    let cfg = &ctx.accounts.config;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= m.end_ts.saturating_add(cfg.crank_delay_secs),
+        ErrorCode::TooEarly
+    );
+
+    // Escrow PDA of the vault being paid from
+    let program_id = ctx.program_id;
+    let market_key = m.key();
+    let multi = m.uses_outcome_vaults();
+    let side = if m.winning_side == 1 { SIDE_YES } else { SIDE_NO };
+    let (auth_pda, bump) = if multi {
+        pda_escrow_auth_outcome(&market_key, outcome_idx, program_id)
+    } else {
+        require!(m.is_price_binary(), ErrorCode::BadMarketType);
+        pda_escrow_auth(&market_key, side, program_id)
+    };
+    require_keys_eq!(auth_pda, ctx.accounts.escrow_authority.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(ctx.accounts.escrow_vault.owner, auth_pda, ErrorCode::Unauthorized);
+
+    let idx_arr = [outcome_idx];
+    let bump_arr = [bump];
+    let seeds = if multi {
+        outcome_signer_seeds(&market_key, &idx_arr, &bump_arr).to_vec()
+    } else {
+        escrow_signer_seeds(&market_key, side, &bump_arr).to_vec()
+    };
+
+    let mint_key = ctx.accounts.mint.key();
+    let token_program_key = ctx.accounts.token_program.key();
+    let claimed_idx = if multi { outcome_idx } else { m.winning_side - 1 };
+
+    for t in triples.chunks(CRANK_GROUP_ACCOUNTS) {
+        let (position_ai, owner_ai, owner_ata_ai) = (&t[0], &t[1], &t[2]);
+        let owner = owner_ai.key();
+        let vault_amount = ctx.accounts.escrow_vault.amount;
+
+        let payout = if multi {
+            let mut pos: Account<'info, PositionMulti> = Account::try_from(position_ai)?;
+            let (pos_pda, _) = Pubkey::find_program_address(
+                &[b"position", market_key.as_ref(), owner.as_ref(), &[outcome_idx]],
+                program_id,
+            );
+            require_keys_eq!(pos_pda, position_ai.key(), ErrorCode::Unauthorized);
+            require!(!pos.claimed, ErrorCode::AlreadyClaimed);
+
+            let payout = multi_win_payout(m, &pos, vault_amount)?;
+            pos.claimed = true;
+            pos.exit(program_id)?;
+            payout
+        } else {
+            let mut pos: Account<'info, PositionBin> = Account::try_from(position_ai)?;
+            let (pos_pda, _) = Pubkey::find_program_address(
+                &[b"position", market_key.as_ref(), owner.as_ref()],
+                program_id,
+            );
+            require_keys_eq!(pos_pda, position_ai.key(), ErrorCode::Unauthorized);
+            require!(!pos.claimed, ErrorCode::AlreadyClaimed);
+
+            let payout = binary_win_payout(m, &pos, vault_amount)?;
+            pos.claimed = true;
+            pos.exit(program_id)?;
+            payout
+        };
+
+        require_keys_eq!(
+            owner_ata_ai.key(),
+            get_associated_token_address_with_program_id(&owner, &mint_key, &token_program_key),
+            ErrorCode::Unauthorized
+        );
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.cranker.to_account_info(),
+                associated_token: owner_ata_ai.clone(),
+                authority: owner_ai.clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+
+        let fee = crank_fee(cfg, payout)?;
+        let to_owner = payout.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+        for (to, amount) in [(owner_ata_ai.clone(), to_owner), (ctx.accounts.cranker_ata.to_account_info(), fee)] {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow_vault.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to,
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+        ctx.accounts.escrow_vault.reload()?;
+
+        emit!(Claimed {
+            market: market_key,
+            user: owner,
+            outcome_idx: claimed_idx,
+            amount: to_owner,
+            crank_fee: fee,
+        });
+    }
+
+    Ok(())
+}
+
+/// Cranker's cut of one payout: `crank_fee_bps`, capped at `crank_fee_cap` (0 = uncapped)
+fn crank_fee(cfg: &Config, payout: u64) -> Result<u64> {
+    let fee = mul_div_u64(payout, cfg.crank_fee_bps as u64, BPS_DENOM)?;
+    Ok(if cfg.crank_fee_cap > 0 { fee.min(cfg.crank_fee_cap) } else { fee })
+}
+
+/// Winnings on a settled binary position, given the winning vault's balance
+fn binary_win_payout(m: &Market, pos: &PositionBin, win_vault_amount: u64) -> Result<u64> {
+    let (side_amt, shares, side_total) = match m.winning_side {
+        1 => (pos.yes_bet, pos.yes_shares, m.yes_total),
+        2 => (pos.no_bet, pos.no_shares, m.no_total),
+        _ => return Err(error!(ErrorCode::MarketNotResolved)),
+    };
+
+    if m.pricing_kind == PricingKind::Cpmm as u8 {
+        // 1 USDC per winning share; the creator also collects the LP residual
+        let lp = if pos.user == m.authority { m.lp_residual } else { 0 };
+        let payout = shares.checked_add(lp).ok_or(ErrorCode::Overflow)?;
+        require!(payout > 0, ErrorCode::NoWinningBet);
+        Ok(core::cmp::min(payout, win_vault_amount))
+    } else {
+        require!(side_amt > 0, ErrorCode::NoWinningBet);
+        mul_div_u64(m.payout_pool, side_amt, side_total)
+    }
+}
+
+/// Winnings on a settled multi-outcome position, given its outcome vault's balance
+fn multi_win_payout(m: &Market, pos: &PositionMulti, vault_amount: u64) -> Result<u64> {
+    let idx = pos.outcome_idx;
+    let is_single = m.winners_mask == 0 && m.outcome_idx != u8::MAX;
+    let user_is_winner = if is_single {
+        m.outcome_idx == idx
+    } else {
+        (m.winners_mask & (1 << idx)) != 0
+    };
+    require!(user_is_winner, ErrorCode::NoWinningBet);
+
+    let total = m.tvl_per_outcome[idx as usize];
+    require!(total > 0, ErrorCode::NoWinningBet);
+
+    // Single winner pays from the recorded pool, split winners from their outcome's snapshot.
+    // Split markets finalized before the snapshot existed still share the live vault.
+    let pool_u64 = if is_single {
+        m.payout_pool
+    } else {
+        match m.payout_per_outcome[idx as usize] {
+            0 => vault_amount,
+            pool => pool,
+        }
+    };
+    let payout = mul_div_u64(pool_u64, pos.stake, total)?;
+    Ok(core::cmp::min(payout, vault_amount))
+}
+
 // ============ Accounts ============
 
 #[derive(Accounts)]
@@ -194,15 +651,17 @@ pub struct Claim<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_ata: Account<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -220,12 +679,19 @@ pub struct Claim<'info> {
     pub escrow_authority_no: UncheckedAccount<'info>,
 
     #[account(mut, constraint = escrow_vault_yes.mint == mint.key())]
-    pub escrow_vault_yes: Account<'info, TokenAccount>,
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut, constraint = escrow_vault_no.mint == mint.key())]
-    pub escrow_vault_no: Account<'info, TokenAccount>,
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -239,15 +705,17 @@ pub struct ClaimMulti<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_ata: Account<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -260,9 +728,80 @@ pub struct ClaimMulti<'info> {
     pub win_authority: UncheckedAccount<'info>,
     
     #[account(mut)]
-    pub win_vault: Account<'info, TokenAccount>,
+    pub win_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankPayout<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub market: Account<'info, Market>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = mint,
+        associated_token::authority = cranker,
+        associated_token::token_program = token_program
+    )]
+    pub cranker_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: escrow PDA of the winning vault, verified in the handler
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = escrow_vault.mint == mint.key() @ ErrorCode::WrongMint)]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{
+        MAX_CLOSE_DUST, OUTCOME_NONE, SIDE_DISPUTE, SIDE_NO, SIDE_REFERRAL, SIDE_YES, WINNING_SIDE_SCALAR, WINNING_SIDE_VOID,
+    },
+    errors::ErrorCode,
+    events::MarketClosed,
+    state::{Market, PositionBin, PositionMulti},
+    types::PricingKind,
+    utils::{
+        escrow_signer_seeds, outcome_signer_seeds, pda_escrow_auth, pda_escrow_auth_outcome,
+        read_token_account,
+    },
+};
+
+/// Escrow authorities a market can own
+#[derive(Clone, Copy)]
+enum Escrow {
+    Side(&'static [u8]),
+    Outcome(u8),
+}
+
+/// Close a binary position once nothing is owed on it; rent goes back to the bettor
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.settled, ErrorCode::MarketNotResolved);
+    require!(!m.uses_outcome_vaults(), ErrorCode::BadMarketType);
+
+    let pos = &ctx.accounts.position;
+    let cpmm = m.pricing_kind == PricingKind::Cpmm as u8;
+    let owed = match m.winning_side {
+        WINNING_SIDE_VOID | WINNING_SIDE_SCALAR => pos.yes_bet > 0 || pos.no_bet > 0,
+        1 if cpmm => pos.yes_shares > 0 || (pos.user == m.authority && m.lp_residual > 0),
+        2 if cpmm => pos.no_shares > 0 || (pos.user == m.authority && m.lp_residual > 0),
+        1 => pos.yes_bet > 0,
+        2 => pos.no_bet > 0,
+        _ => return Err(error!(ErrorCode::MarketNotResolved)),
+    };
+    require!(pos.claimed || !owed, ErrorCode::PositionNotClaimed);
+
+    let m = &mut ctx.accounts.market;
+    m.open_positions = m.open_positions.saturating_sub(1);
+    Ok(())
+}
+
+/// Close a multi-outcome position once nothing is owed on it; rent goes back to the bettor
+pub fn close_position_multi(ctx: Context<ClosePositionMulti>, outcome_idx: u8) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.settled, ErrorCode::MarketNotResolved);
+    require!(m.uses_outcome_vaults(), ErrorCode::BadMarketType);
+
+    let pos = &ctx.accounts.position;
+    require!(pos.outcome_idx == outcome_idx, ErrorCode::Unauthorized);
+
+    let owed = if m.winning_side == WINNING_SIDE_VOID {
+        pos.stake > 0
+    } else if m.winners_mask == 0 && m.outcome_idx != OUTCOME_NONE {
+        pos.stake > 0 && m.outcome_idx == outcome_idx
+    } else {
+        pos.stake > 0 && (m.winners_mask & (1 << outcome_idx)) != 0
+    };
+    require!(pos.claimed || !owed, ErrorCode::PositionNotClaimed);
+
+    let m = &mut ctx.accounts.market;
+    m.open_positions = m.open_positions.saturating_sub(1);
+    Ok(())
+}
+
+/// Close a settled market after every position is closed. `remaining_accounts` holds an
+/// (escrow authority, vault) pair for every escrow the market can own, in `escrows` order;
+/// vaults never created are skipped. A vault may only hold rounding dust (at most
+/// MAX_CLOSE_DUST), which is swept to the treasury before the vault is closed. Anything
+/// more is still owed to someone and blocks the close. Void refunds return stakes only, so on
+/// a void market the stake vaults may also hold up to `exit_fee_pool` between them, which is
+/// swept with the dust. Rent goes back to whoever paid it.
+/// Markets migrated from before position counting can't prove they're empty and never close.
+pub fn close_market<'info>(ctx: Context<'_, '_, 'info, 'info, CloseMarket<'info>>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.settled, ErrorCode::MarketNotResolved);
+    require!(!m.positions_untracked, ErrorCode::PositionsUntracked);
+    require!(m.open_positions == 0, ErrorCode::OpenPositions);
+
+    let market_key = m.key();
+    let mint_key = ctx.accounts.mint.key();
+    let token_program_key = ctx.accounts.token_program.key();
+    let program_id = ctx.program_id;
+
+    // yes/no or one vault per outcome, plus the dispute bond and referral pool vaults.
+    // claim_referral needs the market, so an unclaimed referral pool blocks the close.
+    let mut escrows = vec![Escrow::Side(SIDE_DISPUTE), Escrow::Side(SIDE_REFERRAL)];
+    if m.uses_outcome_vaults() {
+        escrows.extend((0..m.num_outcomes).map(Escrow::Outcome));
+    } else {
+        escrows.push(Escrow::Side(SIDE_YES));
+        escrows.push(Escrow::Side(SIDE_NO));
+    }
+    // Every vault must be accounted for, or funds left in a skipped one are stranded
+    require!(
+        ctx.remaining_accounts.len() == escrows.len() * 2,
+        ErrorCode::Unauthorized
+    );
+
+    // Exit fees nobody can claim once the market is void
+    let mut unclaimable_fees = if m.winning_side == WINNING_SIDE_VOID {
+        m.exit_fee_pool
+    } else {
+        0
+    };
+    let mut dust_swept: u64 = 0;
+    let mut vaults_closed: u8 = 0;
+    for (pair, &escrow) in ctx.remaining_accounts.chunks(2).zip(&escrows) {
+        let auth_ai = &pair[0];
+        let vault_ai = &pair[1];
+
+        let (pda, bump) = match escrow {
+            Escrow::Side(side) => pda_escrow_auth(&market_key, side, program_id),
+            Escrow::Outcome(idx) => pda_escrow_auth_outcome(&market_key, idx, program_id),
+        };
+        require_keys_eq!(auth_ai.key(), pda, ErrorCode::Unauthorized);
+        require_keys_eq!(
+            vault_ai.key(),
+            get_associated_token_address_with_program_id(&pda, &mint_key, &token_program_key),
+            ErrorCode::Unauthorized
+        );
+        if vault_ai.data_is_empty() {
+            continue;
+        }
+
+        let st = read_token_account(vault_ai)?;
+        require_keys_eq!(st.mint, mint_key, ErrorCode::WrongMint);
+        require_keys_eq!(st.owner, auth_ai.key(), ErrorCode::Unauthorized);
+        let over = st.amount.saturating_sub(MAX_CLOSE_DUST);
+        let stake_vault = !matches!(
+            escrow,
+            Escrow::Side(s) if s == SIDE_DISPUTE || s == SIDE_REFERRAL
+        );
+        require!(
+            over == 0 || (stake_vault && over <= unclaimable_fees),
+            ErrorCode::VaultNotEmpty
+        );
+        unclaimable_fees -= over;
+
+        let bump_arr = [bump];
+        let idx_arr = [match escrow {
+            Escrow::Outcome(idx) => idx,
+            Escrow::Side(_) => 0,
+        }];
+        let side_seeds;
+        let outcome_seeds;
+        let seeds: &[&[u8]] = match escrow {
+            Escrow::Side(side) => {
+                side_seeds = escrow_signer_seeds(&market_key, side, &bump_arr);
+                &side_seeds
+            }
+            Escrow::Outcome(_) => {
+                outcome_seeds = outcome_signer_seeds(&market_key, &idx_arr, &bump_arr);
+                &outcome_seeds
+            }
+        };
+
+        if st.amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault_ai.clone(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.treasury_ata.to_account_info(),
+                        authority: auth_ai.clone(),
+                    },
+                    &[seeds],
+                ),
+                st.amount,
+                ctx.accounts.mint.decimals,
+            )?;
+            dust_swept = dust_swept.checked_add(st.amount).ok_or(ErrorCode::Overflow)?;
+        }
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: vault_ai.clone(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority: auth_ai.clone(),
+            },
+            &[seeds],
+        ))?;
+        vaults_closed = vaults_closed.saturating_add(1);
+    }
+
+    emit!(MarketClosed {
+        market: market_key,
+        authority: ctx.accounts.authority.key(),
+        dust_swept,
+        vaults_closed,
+    });
+
+    Ok(())
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, PositionBin>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_idx: u8)]
+pub struct ClosePositionMulti<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref(), &[outcome_idx]],
+        bump
+    )]
+    pub position: Account<'info, PositionMulti>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = market.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: receives the rent; must be the market's recorded rent payer
+    #[account(mut, address = market.rent_refund() @ ErrorCode::Unauthorized)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = treasury_ata.mint == mint.key(),
+        constraint = treasury_ata.owner == market.treasury_wallet_snapshot
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_COMMITTEE_MEMBERS,
+    errors::ErrorCode,
+    state::{Config, OracleCommittee},
+};
+
+pub fn create_committee(
+    ctx: Context<CreateCommittee>,
+    id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let c = &mut ctx.accounts.committee;
+    c.id = id;
+    set_members(c, &members, threshold)
+}
+
+/// Rotate members or change the threshold; markets pick this up on their next proposal
+pub fn update_committee(
+    ctx: Context<UpdateCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    set_members(&mut ctx.accounts.committee, &members, threshold)
+}
+
+fn set_members(c: &mut OracleCommittee, members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COMMITTEE_MEMBERS,
+        ErrorCode::BadCommittee
+    );
+    require!(
+        threshold > 0 && (threshold as usize) <= members.len(),
+        ErrorCode::BadCommittee
+    );
+    for (i, m) in members.iter().enumerate() {
+        require!(*m != Pubkey::default(), ErrorCode::BadCommittee);
+        require!(!members[..i].contains(m), ErrorCode::BadCommittee);
+    }
+
+    c.members = [Pubkey::default(); MAX_COMMITTEE_MEMBERS];
+    c.members[..members.len()].copy_from_slice(members);
+    c.members_len = members.len() as u8;
+    c.threshold = threshold;
+    Ok(())
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateCommittee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = OracleCommittee::SPACE,
+        seeds = [b"committee".as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, OracleCommittee>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCommittee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"committee", &committee.id.to_le_bytes()], bump)]
+    pub committee: Account<'info, OracleCommittee>,
+}
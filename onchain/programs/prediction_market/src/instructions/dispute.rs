@@ -0,0 +1,332 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{
+        DISPUTE_CONFIRMED, DISPUTE_EXPIRED, DISPUTE_NONE, DISPUTE_OPEN, DISPUTE_OVERTURNED,
+        DISPUTE_RULING_SECS, ESCROW_SEED, OUTCOME_NONE, SIDE_DISPUTE, WINNING_SIDE_VOID,
+    },
+    errors::ErrorCode,
+    events::{DisputeExpired, DisputeRuled, MarketDisputedAi, MarketVoidedAi},
+    state::{Config, Market, PositionMulti},
+    types::OracleKind,
+    utils::pda::escrow_signer_seeds,
+};
+
+pub fn dispute_ai(ctx: Context<DisputeAi>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.oracle_kind == OracleKind::Ai as u8, ErrorCode::BadMarketType);
+    require!(m.tentative, ErrorCode::MarketNotResolved);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+    require!(m.dispute_status == DISPUTE_NONE, ErrorCode::AlreadyDisputed);
+
+    let now = Clock::get()?.unix_timestamp;
+    let deadline = m
+        .proposed_at
+        .checked_add(m.dispute_window_secs)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(now < deadline, ErrorCode::DisputeWindowClosed);
+
+    // Only bettors with stake in this market can challenge
+    let pos = &ctx.accounts.position;
+    require_keys_eq!(pos.market, m.key(), ErrorCode::NotABettor);
+    require_keys_eq!(pos.user, ctx.accounts.disputer.key(), ErrorCode::NotABettor);
+    require!(pos.stake > 0, ErrorCode::NotABettor);
+
+    let bond = m.dispute_bond_snapshot;
+    if bond > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.disputer_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.bond_vault.to_account_info(),
+                    authority: ctx.accounts.disputer.to_account_info(),
+                },
+            ),
+            bond,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let m = &mut ctx.accounts.market;
+    m.dispute_status = DISPUTE_OPEN;
+    m.disputer = ctx.accounts.disputer.key();
+
+    emit!(MarketDisputedAi {
+        market: m.key(),
+        disputer: m.disputer,
+        bond,
+        proposed_outcome_idx: m.outcome_idx,
+        proposed_winners_mask: m.winners_mask,
+    });
+
+    Ok(())
+}
+
+pub fn rule_dispute(
+    ctx: Context<RuleDispute>,
+    overturn: bool,
+    outcome_idx: u8,
+    winners_mask: u8,
+) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    let ruler = ctx.accounts.authority.key();
+    require!(
+        ruler == cfg.admin || ruler == cfg.dispute_authority,
+        ErrorCode::Unauthorized
+    );
+
+    let m = &ctx.accounts.market;
+    require!(m.dispute_status == DISPUTE_OPEN, ErrorCode::NoOpenDispute);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ruling_deadline(m)?, ErrorCode::RulingWindowClosed);
+
+    let market_key = m.key();
+    let bond = m.dispute_bond_snapshot;
+    let bump_arr = [ctx.bumps.bond_authority];
+    let signer = escrow_signer_seeds(&market_key, SIDE_DISPUTE, &bump_arr);
+
+    // Overturned: bond goes back to the disputer. Confirmed: bond is forfeited to treasury.
+    let dest = if overturn {
+        ctx.accounts.disputer_ata.to_account_info()
+    } else {
+        ctx.accounts.treasury_ata.to_account_info()
+    };
+    if bond > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: dest,
+                    authority: ctx.accounts.bond_authority.to_account_info(),
+                },
+                &[&signer],
+            ),
+            bond,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let m = &mut ctx.accounts.market;
+    if overturn {
+        let num_outcomes = m.num_outcomes;
+        m.winning_side = 0;
+        if winners_mask == 0 && outcome_idx == OUTCOME_NONE {
+            // Overturn to void
+            m.outcome_idx = OUTCOME_NONE;
+            m.winners_mask = 0;
+            m.winning_side = WINNING_SIDE_VOID;
+        } else if winners_mask == 0 {
+            require!(outcome_idx < num_outcomes, ErrorCode::BadMarketType);
+            m.outcome_idx = outcome_idx;
+            m.winners_mask = 0;
+        } else {
+            require!(
+                (winners_mask as u16) < (1u16 << num_outcomes),
+                ErrorCode::BadMarketType
+            );
+            let winners_total: u128 = (0..num_outcomes)
+                .filter(|i| (winners_mask & (1 << i)) != 0)
+                .map(|i| m.tvl_per_outcome[i as usize] as u128)
+                .sum();
+            require!(winners_total > 0, ErrorCode::NoWinningBet);
+            m.outcome_idx = OUTCOME_NONE;
+            m.winners_mask = winners_mask;
+        }
+        m.dispute_status = DISPUTE_OVERTURNED;
+    } else {
+        m.dispute_status = DISPUTE_CONFIRMED;
+    }
+
+    emit!(DisputeRuled {
+        market: market_key,
+        disputer: m.disputer,
+        overturned: overturn,
+        outcome_idx: m.outcome_idx,
+        winners_mask: m.winners_mask,
+        bond,
+    });
+
+    Ok(())
+}
+
+/// Permissionless: a dispute nobody ruled on by the deadline voids the market. The
+/// proposal was challenged and never upheld, so bettors are refunded and the bond returned.
+pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.dispute_status == DISPUTE_OPEN, ErrorCode::NoOpenDispute);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ruling_deadline(m)?, ErrorCode::RulingWindowOpen);
+
+    let market_key = m.key();
+    let bond = m.dispute_bond_snapshot;
+    if bond > 0 {
+        let bump_arr = [ctx.bumps.bond_authority];
+        let signer = escrow_signer_seeds(&market_key, SIDE_DISPUTE, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.disputer_ata.to_account_info(),
+                    authority: ctx.accounts.bond_authority.to_account_info(),
+                },
+                &[&signer],
+            ),
+            bond,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let m = &mut ctx.accounts.market;
+    m.dispute_status = DISPUTE_EXPIRED;
+    m.outcome_idx = OUTCOME_NONE;
+    m.winners_mask = 0;
+    m.winning_side = WINNING_SIDE_VOID;
+    m.payout_pool = 0;
+    m.tentative = false;
+    m.settled = true;
+
+    emit!(DisputeExpired {
+        market: market_key,
+        disputer: m.disputer,
+        bond,
+    });
+    emit!(MarketVoidedAi {
+        market: market_key,
+        pot: m.tvl_per_outcome.iter().map(|&x| x as u128).sum(),
+        by_attestation: false,
+    });
+
+    Ok(())
+}
+
+/// Disputes are filed before the challenge window closes; rulings get a fixed period after it
+fn ruling_deadline(m: &Market) -> Result<i64> {
+    m.proposed_at
+        .checked_add(m.dispute_window_secs)
+        .and_then(|t| t.checked_add(DISPUTE_RULING_SECS))
+        .ok_or_else(|| error!(ErrorCode::Overflow))
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct DisputeAi<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub position: Account<'info, PositionMulti>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = disputer_ata.owner == disputer.key() @ ErrorCode::Unauthorized,
+        constraint = disputer_ata.mint == mint.key() @ ErrorCode::WrongMint
+    )]
+    pub disputer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_DISPUTE], bump)]
+    pub bond_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = disputer,
+        associated_token::mint = mint,
+        associated_token::authority = bond_authority,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RuleDispute<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_DISPUTE], bump)]
+    pub bond_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bond_authority,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = disputer_ata.owner == market.disputer @ ErrorCode::Unauthorized,
+        constraint = disputer_ata.mint == mint.key() @ ErrorCode::WrongMint
+    )]
+    pub disputer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_ata.owner == market.treasury_wallet_snapshot @ ErrorCode::Unauthorized,
+        constraint = treasury_ata.mint == mint.key() @ ErrorCode::WrongMint
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_DISPUTE], bump)]
+    pub bond_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bond_authority,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = disputer_ata.owner == market.disputer @ ErrorCode::Unauthorized,
+        constraint = disputer_ata.mint == mint.key() @ ErrorCode::WrongMint
+    )]
+    pub disputer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,451 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{
+        BPS_DENOM, ESCROW_SEED, LP_SEED, PAUSE_BETTING, PAUSE_CLAIMS, POSITION_VERSION, SIDE_NO,
+        SIDE_YES, WINNING_SIDE_VOID,
+    },
+    errors::ErrorCode,
+    events::{LiquidityAdded, LiquidityRemoved, LpClaimed},
+    state::{Config, LpPosition, Market},
+    utils::{escrow_signer_seeds, mul_div_u64},
+};
+
+/// Deposit `amount` split evenly across both sides of a parimutuel binary market.
+/// The stake counts toward yes_total/no_total like any bet, and the provider gets LP
+/// shares 1:1 with the deposit.
+pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+    require!(amount >= 2, ErrorCode::InvalidAmount);
+
+    let m = &ctx.accounts.market;
+    require!(m.accepts_liquidity(), ErrorCode::BadMarketType);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < m.exit_lock_ts, ErrorCode::TooLateToBet);
+
+    let yes_amount = amount - amount / 2;
+    let no_amount = amount / 2;
+
+    for (vault, amt) in [
+        (&ctx.accounts.escrow_vault_yes, yes_amount),
+        (&ctx.accounts.escrow_vault_no, no_amount),
+    ] {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amt,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let lp = &mut ctx.accounts.lp_position;
+    if lp.owner == Pubkey::default() {
+        lp.market = ctx.accounts.market.key();
+        lp.owner = ctx.accounts.user.key();
+        lp.yes_amount = 0;
+        lp.no_amount = 0;
+        lp.shares = 0;
+        lp.version = POSITION_VERSION;
+        ctx.accounts.market.open_positions = ctx.accounts.market.open_positions
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    require_keys_eq!(lp.owner, ctx.accounts.user.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(lp.market, ctx.accounts.market.key(), ErrorCode::Unauthorized);
+
+    lp.yes_amount = lp.yes_amount.checked_add(yes_amount).ok_or(ErrorCode::Overflow)?;
+    lp.no_amount = lp.no_amount.checked_add(no_amount).ok_or(ErrorCode::Overflow)?;
+    lp.shares = lp.shares.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+    let m = &mut ctx.accounts.market;
+    m.yes_total = m.yes_total.checked_add(yes_amount).ok_or(ErrorCode::Overflow)?;
+    m.no_total = m.no_total.checked_add(no_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_yes = m.lp_yes.checked_add(yes_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_no = m.lp_no.checked_add(no_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_shares = m.lp_shares.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+    emit!(LiquidityAdded {
+        market: m.key(),
+        provider: lp.owner,
+        yes_amount,
+        no_amount,
+        shares: amount,
+        yes_total: m.yes_total,
+        no_total: m.no_total,
+    });
+    Ok(())
+}
+
+/// Burn `shares` for their pro-rata slice of the provider's stake on each side. Closes
+/// with bet exits at exit_lock_ts and pays the same exit fee, which stays in the vaults.
+pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64) -> Result<()> {
+    require!(shares > 0, ErrorCode::InvalidAmount);
+
+    let m = &ctx.accounts.market;
+    require!(m.accepts_liquidity(), ErrorCode::BadMarketType);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < m.exit_lock_ts, ErrorCode::ExitLocked);
+
+    let lp = &ctx.accounts.lp_position;
+    require!(shares <= lp.shares, ErrorCode::InsufficientLpShares);
+
+    let yes_amount = mul_div_u64(lp.yes_amount, shares, lp.shares)?;
+    let no_amount = mul_div_u64(lp.no_amount, shares, lp.shares)?;
+    let yes_fee = mul_div_u64(yes_amount, m.exit_fee_bps_snapshot as u64, BPS_DENOM)?;
+    let no_fee = mul_div_u64(no_amount, m.exit_fee_bps_snapshot as u64, BPS_DENOM)?;
+
+    let market_key = m.key();
+    let legs = [
+        (
+            SIDE_YES,
+            ctx.bumps.escrow_authority_yes,
+            &ctx.accounts.escrow_vault_yes,
+            ctx.accounts.escrow_authority_yes.to_account_info(),
+            yes_amount - yes_fee,
+        ),
+        (
+            SIDE_NO,
+            ctx.bumps.escrow_authority_no,
+            &ctx.accounts.escrow_vault_no,
+            ctx.accounts.escrow_authority_no.to_account_info(),
+            no_amount - no_fee,
+        ),
+    ];
+    for (side, bump, vault, auth, out) in legs {
+        if out == 0 {
+            continue;
+        }
+        let bump_arr = [bump];
+        let seeds = escrow_signer_seeds(&market_key, side, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: auth,
+                },
+                &[&seeds],
+            ),
+            out,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let lp = &mut ctx.accounts.lp_position;
+    lp.yes_amount -= yes_amount;
+    lp.no_amount -= no_amount;
+    lp.shares -= shares;
+
+    let fee = yes_fee + no_fee;
+    let m = &mut ctx.accounts.market;
+    m.yes_total = m.yes_total.checked_sub(yes_amount).ok_or(ErrorCode::Overflow)?;
+    m.no_total = m.no_total.checked_sub(no_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_yes = m.lp_yes.checked_sub(yes_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_no = m.lp_no.checked_sub(no_amount).ok_or(ErrorCode::Overflow)?;
+    m.lp_shares = m.lp_shares.checked_sub(shares).ok_or(ErrorCode::Overflow)?;
+    m.exit_fee_pool = m.exit_fee_pool.checked_add(fee).ok_or(ErrorCode::Overflow)?;
+
+    emit!(LiquidityRemoved {
+        market: market_key,
+        provider: lp.owner,
+        yes_amount,
+        no_amount,
+        shares,
+        fee,
+    });
+    Ok(())
+}
+
+/// Pay out an LP position after settlement and close it. The winning-side stake is paid
+/// like a bet, plus the position's share of `lp_fee_pool`. The losing-side stake was kept
+/// out of the pot at settlement and is refunded from the losing vault; a void refunds both.
+pub fn claim_lp(ctx: Context<ClaimLp>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.is_price_binary(), ErrorCode::BadMarketType);
+    require!(m.settled, ErrorCode::MarketNotResolved);
+
+    let lp = &ctx.accounts.lp_position;
+    let market_key = m.key();
+
+    let mut legs: Vec<(&[u8], u8, u64)> = Vec::with_capacity(2);
+    let (residual, fee_share) = match m.winning_side {
+        WINNING_SIDE_VOID => {
+            legs.push((SIDE_YES, ctx.bumps.escrow_authority_yes, lp.yes_amount));
+            legs.push((SIDE_NO, ctx.bumps.escrow_authority_no, lp.no_amount));
+            (lp.yes_amount + lp.no_amount, 0)
+        }
+        1 | 2 => {
+            let (yes_bump, no_bump) = (ctx.bumps.escrow_authority_yes, ctx.bumps.escrow_authority_no);
+            let (win, lose) = if m.winning_side == 1 {
+                ((SIDE_YES, yes_bump, lp.yes_amount, m.yes_total), (SIDE_NO, no_bump, lp.no_amount))
+            } else {
+                ((SIDE_NO, no_bump, lp.no_amount, m.no_total), (SIDE_YES, yes_bump, lp.yes_amount))
+            };
+            let (side, bump, stake, side_total) = win;
+            let winnings = if stake > 0 {
+                mul_div_u64(m.payout_pool, stake, side_total)?
+            } else {
+                0
+            };
+            let fee_share = if lp.shares > 0 {
+                mul_div_u64(m.lp_fee_pool, lp.shares, m.lp_shares)?
+            } else {
+                0
+            };
+            let total = winnings.checked_add(fee_share).ok_or(ErrorCode::Overflow)?;
+            legs.push((side, bump, total));
+            legs.push(lose);
+            (winnings.checked_add(lose.2).ok_or(ErrorCode::Overflow)?, fee_share)
+        }
+        _ => return Err(error!(ErrorCode::MarketNotResolved)),
+    };
+
+    for (side, bump, amount) in legs {
+        let (vault, auth) = if side == SIDE_YES {
+            (&ctx.accounts.escrow_vault_yes, ctx.accounts.escrow_authority_yes.to_account_info())
+        } else {
+            (&ctx.accounts.escrow_vault_no, ctx.accounts.escrow_authority_no.to_account_info())
+        };
+        let amount = core::cmp::min(amount, vault.amount);
+        if amount == 0 {
+            continue;
+        }
+        let bump_arr = [bump];
+        let seeds = escrow_signer_seeds(&market_key, side, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: auth,
+                },
+                &[&seeds],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let m = &mut ctx.accounts.market;
+    m.open_positions = m.open_positions.saturating_sub(1);
+
+    emit!(LpClaimed {
+        market: market_key,
+        provider: ctx.accounts.user.key(),
+        residual,
+        fee_share,
+    });
+    Ok(())
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, constraint = !market.settled @ ErrorCode::AlreadySettled)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LpPosition::SPACE,
+        seeds = [LP_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [LP_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump,
+        has_one = market @ ErrorCode::Unauthorized,
+        constraint = lp_position.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_BETTING) @ ErrorCode::BettingPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [LP_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump,
+        has_one = market @ ErrorCode::Unauthorized,
+        constraint = lp_position.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,19 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::{ESCROW_SEED, MAX_OUTCOMES, SIDE_NO, SIDE_YES, USDC_MINT},
+    constants::{
+        DISPUTE_NONE, ESCROW_SEED, LADDER_ACCOUNTS_PER_STRIKE, MARKET_VERSION, MAX_LADDER_STRIKES,
+        MAX_METADATA_URI_LEN, MAX_OUTCOMES, PAUSE_CREATION, SIDE_NO, SIDE_YES,
+    },
     errors::ErrorCode,
-    state::{Config, Market},
-    types::{MarketType, OracleKind},
+    events::MarketCreated,
+    state::{Config, Market, OracleCommittee},
+    types::{MarketType, OracleKind, PricingKind, ResolutionRules, StakeLimits},
+    utils::{pda_escrow_auth, read_switchboard_feed},
 };
 
 pub fn create_market(
@@ -19,47 +24,33 @@ pub fn create_market(
     bound_hi_usd_6: i64,
     end_ts: i64,
     feed_id: [u8; 32],
+    limits: StakeLimits,
+    rules: ResolutionRules,
 ) -> Result<()> {
-    require_keys_eq!(ctx.accounts.mint.key(), USDC_MINT, ErrorCode::WrongMint);
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
 
-    let cfg = &ctx.accounts.config;
     let m = &mut ctx.accounts.market;
-    
-    m.authority = ctx.accounts.authority.key();
-    m.oracle_kind = OracleKind::Pyth as u8;
-    m.ai_oracle_authority = Pubkey::default();
-    m.end_ts = end_ts;
-    m.settled = false;
-
-    m.fee_bps_snapshot = cfg.fee_bps;
-    m.resolver_bps_snapshot = cfg.resolver_bps;
-    m.creator_bps_snapshot = cfg.creator_bps;
-    m.resolver_tip_cap_snapshot = 0;
-    m.treasury_wallet_snapshot = cfg.treasury_wallet;
-
+    reset_market(
+        m,
+        &ctx.accounts.config,
+        ctx.accounts.authority.key(),
+        &ctx.accounts.mint,
+        OracleKind::Pyth as u8,
+        end_ts,
+    )?;
     m.feed_id = feed_id;
-    m.market_type = match market_type {
-        MarketType::PriceThreshold => 0,
-        MarketType::PriceRange => 1,
-    };
+    m.market_type = price_binary_type(market_type)?;
     m.comparator = comparator;
     m.bound_lo = bound_lo_usd_6;
     m.bound_hi = bound_hi_usd_6;
-    m.resolved_price_1e6 = 0;
-
-    // Multi-outcome defaults
-    m.num_outcomes = 2;
-    m.tvl_per_outcome = [0; MAX_OUTCOMES];
-    m.outcome_idx = u8::MAX;
-    m.payout_pool = 0;
-    m.winners_mask = 0;
-    m.tentative = false;
-
-    // Legacy binary totals
-    m.yes_total = 0;
-    m.no_total = 0;
-    m.winning_side = 0;
+    check_scalar_bounds(m)?;
+    apply_stake_limits(m, &ctx.accounts.config, limits)?;
+    m.apply_resolution_rules(rules)?;
 
+    emit_market_created(m.key(), m);
     Ok(())
 }
 
@@ -71,23 +62,271 @@ pub fn create_market_multi(
         p.num_outcomes >= 2 && (p.num_outcomes as usize) <= MAX_OUTCOMES,
         ErrorCode::BadMarketType
     );
+    // Switchboard markets are binary only, see create_market_switchboard
+    require!(
+        p.oracle_kind != OracleKind::Switchboard as u8,
+        ErrorCode::BadMarketType
+    );
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
+    let uri = p.metadata_uri.as_bytes();
+    require!(uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
 
+    let cfg = &ctx.accounts.config;
     let m = &mut ctx.accounts.market;
-    m.authority = ctx.accounts.authority.key();
-    m.oracle_kind = p.oracle_kind;
+    reset_market(
+        m,
+        cfg,
+        ctx.accounts.authority.key(),
+        &ctx.accounts.mint,
+        p.oracle_kind,
+        p.end_ts,
+    )?;
     m.ai_oracle_authority = p.ai_oracle_authority;
-    m.end_ts = p.end_ts;
-    m.settled = false;
+    m.num_outcomes = p.num_outcomes;
+
+    // Dispute snapshot
+    m.dispute_window_secs = cfg.dispute_window_secs;
+    m.dispute_bond_snapshot = cfg.dispute_bond;
+    m.dispute_reward_bps_snapshot = cfg.dispute_reward_bps;
+
+    m.question_hash = p.question_hash;
+    m.metadata_uri[..uri.len()].copy_from_slice(uri);
+    m.metadata_uri_len = uri.len() as u8;
+
+    // AI markets may hand proposals to an M-of-N committee instead of ai_oracle_authority
+    m.oracle_committee = match &ctx.accounts.oracle_committee {
+        Some(c) => {
+            require!(p.oracle_kind == OracleKind::Ai as u8, ErrorCode::BadMarketType);
+            c.key()
+        }
+        None => Pubkey::default(),
+    };
+    apply_stake_limits(m, cfg, p.limits)?;
+    m.apply_resolution_rules(ResolutionRules::default())?;
+
+    emit_market_created(m.key(), m);
+    Ok(())
+}
+
+/// Pyth multi-outcome market: `bounds` are strictly increasing USD (1e6) boundaries
+/// splitting the price line into `bounds.len() + 1` buckets
+pub fn create_market_buckets(
+    ctx: Context<CreateMarketBuckets>,
+    p: CreateMarketBucketsParams,
+) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
+    require!(
+        !p.bounds.is_empty() && p.bounds.len() < MAX_OUTCOMES,
+        ErrorCode::BadMarketType
+    );
+    require!(
+        p.bounds.windows(2).all(|w| w[0] < w[1]),
+        ErrorCode::BadBucketBounds
+    );
 
-    // Fee snapshot
     let cfg = &ctx.accounts.config;
+    let m = &mut ctx.accounts.market;
+    reset_market(
+        m,
+        cfg,
+        ctx.accounts.authority.key(),
+        &ctx.accounts.mint,
+        OracleKind::Pyth as u8,
+        p.end_ts,
+    )?;
+    m.feed_id = p.feed_id;
+    m.market_type = MarketType::PriceBuckets as u8;
+    m.bound_lo = p.bounds[0];
+    m.bound_hi = p.bounds[p.bounds.len() - 1];
+    m.bucket_bounds[..p.bounds.len()].copy_from_slice(&p.bounds);
+    m.num_outcomes = (p.bounds.len() + 1) as u8;
+    apply_stake_limits(m, cfg, p.limits)?;
+    m.apply_resolution_rules(p.rules)?;
+
+    emit_market_created(m.key(), m);
+    Ok(())
+}
+
+/// Switchboard binary market: same threshold/range/scalar semantics as `create_market`, settled
+/// from a Switchboard on-demand pull feed. The feed account key is stored in `feed_id`.
+pub fn create_market_switchboard(
+    ctx: Context<CreateMarketSwitchboard>,
+    p: CreateMarketSwitchboardParams,
+) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
+    // Fails early on a wrong owner or layout rather than at resolution
+    read_switchboard_feed(&ctx.accounts.switchboard_feed)?;
+    // Switchboard feeds have no EMA
+    require!(!p.rules.use_ema, ErrorCode::BadMarketType);
+
+    let m = &mut ctx.accounts.market;
+    reset_market(
+        m,
+        &ctx.accounts.config,
+        ctx.accounts.authority.key(),
+        &ctx.accounts.mint,
+        OracleKind::Switchboard as u8,
+        p.end_ts,
+    )?;
+    m.feed_id = ctx.accounts.switchboard_feed.key().to_bytes();
+    m.market_type = price_binary_type(p.market_type)?;
+    m.comparator = p.comparator;
+    m.bound_lo = p.bound_lo_usd_6;
+    m.bound_hi = p.bound_hi_usd_6;
+    check_scalar_bounds(m)?;
+    apply_stake_limits(m, &ctx.accounts.config, p.limits)?;
+    m.apply_resolution_rules(p.rules)?;
+
+    emit_market_created(m.key(), m);
+    Ok(())
+}
+
+/// Strike ladder: one Pyth threshold market per strike, sharing feed, comparator and end time.
+/// `remaining_accounts` holds (market, escrow authority yes, escrow authority no, vault yes,
+/// vault no) for each strike in order; the strike index is part of the market seeds.
+pub fn create_market_ladder<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateMarketLadder<'info>>,
+    p: CreateMarketLadderParams,
+) -> Result<()> {
+    let n = p.strikes.len();
+    require!((2..=MAX_LADDER_STRIKES).contains(&n), ErrorCode::BadLadder);
+    require!(p.strikes.windows(2).all(|w| w[0] < w[1]), ErrorCode::BadLadder);
+    require!(
+        ctx.remaining_accounts.len() == n * LADDER_ACCOUNTS_PER_STRIKE,
+        ErrorCode::BadLadder
+    );
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
+
+    let program_id = ctx.program_id;
+    let authority = ctx.accounts.authority.key();
+    let end_ts_bytes = p.end_ts.to_le_bytes();
+    let rent = Rent::get()?;
+
+    for (i, accs) in ctx.remaining_accounts.chunks(LADDER_ACCOUNTS_PER_STRIKE).enumerate() {
+        let (market_ai, auth_yes, auth_no) = (&accs[0], &accs[1], &accs[2]);
+        let idx = [i as u8];
+        let (market_key, bump) = Pubkey::find_program_address(
+            &[b"market", authority.as_ref(), &p.feed_id, &end_ts_bytes, &idx],
+            program_id,
+        );
+        require_keys_eq!(market_ai.key(), market_key, ErrorCode::BadLadder);
+        require_keys_eq!(
+            auth_yes.key(),
+            pda_escrow_auth(&market_key, SIDE_YES, program_id).0,
+            ErrorCode::BadLadder
+        );
+        require_keys_eq!(
+            auth_no.key(),
+            pda_escrow_auth(&market_key, SIDE_NO, program_id).0,
+            ErrorCode::BadLadder
+        );
+
+        let bump_arr = [bump];
+        let market_seeds: &[&[u8]] =
+            &[b"market", authority.as_ref(), &p.feed_id, &end_ts_bytes, &idx, &bump_arr];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: market_ai.clone(),
+                },
+                &[market_seeds],
+            ),
+            rent.minimum_balance(Market::SPACE),
+            Market::SPACE as u64,
+            program_id,
+        )?;
+
+        // Fresh zeroed account; the discriminator is written on exit
+        let mut m: Account<'info, Market> = Account::try_from_unchecked(market_ai)?;
+        reset_market(
+            &mut m,
+            &ctx.accounts.config,
+            authority,
+            &ctx.accounts.mint,
+            OracleKind::Pyth as u8,
+            p.end_ts,
+        )?;
+        m.feed_id = p.feed_id;
+        m.market_type = MarketType::PriceThreshold as u8;
+        m.comparator = p.comparator;
+        m.bound_lo = p.strikes[i];
+        m.bound_hi = 0;
+        apply_stake_limits(&mut m, &ctx.accounts.config, p.limits)?;
+        m.apply_resolution_rules(p.rules)?;
+        m.exit(program_id)?;
+        emit_market_created(market_key, &m);
+
+        for (vault, escrow_auth) in [(&accs[3], auth_yes), (&accs[4], auth_no)] {
+            associated_token::create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: ctx.accounts.authority.to_account_info(),
+                    associated_token: vault.clone(),
+                    authority: escrow_auth.clone(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Every market field at its fresh-market default, with fee and exit snapshots taken from the
+/// config. Defaults to a binary market; callers then set the feed, market type, bounds,
+/// outcomes and whatever else is specific to them, plus limits and rules.
+/// Fields added to `Market` get their default here and nowhere else.
+pub(crate) fn reset_market(
+    m: &mut Market,
+    cfg: &Config,
+    authority: Pubkey,
+    mint: &InterfaceAccount<Mint>,
+    oracle_kind: u8,
+    end_ts: i64,
+) -> Result<()> {
+    m.authority = authority;
+    m.oracle_kind = oracle_kind;
+    m.ai_oracle_authority = Pubkey::default();
+    m.end_ts = end_ts;
+    m.settled = false;
+
     m.fee_bps_snapshot = cfg.fee_bps;
     m.resolver_bps_snapshot = cfg.resolver_bps;
     m.creator_bps_snapshot = cfg.creator_bps;
-    m.resolver_tip_cap_snapshot = 0;
+    m.referral_bps_snapshot = cfg.referral_bps;
+    m.lp_fee_share_bps_snapshot = cfg.lp_fee_share_bps;
+    m.resolver_tip_cap_snapshot = cfg.resolver_tip_cap;
+    m.tip_curve_snapshot = cfg.tip_curve;
     m.treasury_wallet_snapshot = cfg.treasury_wallet;
 
-    // Pyth legacy clean
+    // Disputes apply to AI markets only; create_market_multi snapshots them
+    m.proposed_at = 0;
+    m.dispute_window_secs = 0;
+    m.dispute_bond_snapshot = 0;
+    m.dispute_reward_bps_snapshot = 0;
+    m.dispute_status = DISPUTE_NONE;
+    m.disputer = Pubkey::default();
+
+    m.exit_fee_bps_snapshot = cfg.exit_fee_bps;
+    m.exit_lock_ts = end_ts.checked_sub(cfg.exit_lock_secs).ok_or(ErrorCode::Overflow)?;
+    m.exit_fee_pool = 0;
+
     m.feed_id = [0u8; 32];
     m.market_type = 0;
     m.comparator = 0;
@@ -95,19 +334,105 @@ pub fn create_market_multi(
     m.bound_hi = 0;
     m.resolved_price_1e6 = 0;
 
-    // Multi
-    m.num_outcomes = p.num_outcomes;
+    // Multi-outcome defaults
+    m.num_outcomes = 2;
     m.tvl_per_outcome = [0; MAX_OUTCOMES];
     m.outcome_idx = u8::MAX;
     m.payout_pool = 0;
     m.winners_mask = 0;
     m.tentative = false;
 
-    // Legacy bin
+    // Legacy binary totals
     m.yes_total = 0;
     m.no_total = 0;
     m.winning_side = 0;
 
+    // Parimutuel until the creator seeds a CPMM pool
+    m.pricing_kind = PricingKind::Parimutuel as u8;
+    m.pool_yes = 0;
+    m.pool_no = 0;
+    m.yes_shares = 0;
+    m.no_shares = 0;
+    m.lp_residual = 0;
+    m.bucket_bounds = [0; MAX_OUTCOMES - 1];
+    m.open_positions = 0;
+    m.referred_volume = 0;
+    m.referral_pool = 0;
+    m.mint = mint.key();
+    m.mint_decimals = mint.decimals;
+    m.oracle_committee = Pubkey::default();
+    m.question_hash = [0u8; 32];
+    m.metadata_uri = [0u8; MAX_METADATA_URI_LEN];
+    m.metadata_uri_len = 0;
+    m.scalar_long_bps = 0;
+    m.scalar_long_pool = 0;
+    m.scalar_short_pool = 0;
+    m.lp_yes = 0;
+    m.lp_no = 0;
+    m.lp_shares = 0;
+    m.lp_fee_pool = 0;
+    m.version = MARKET_VERSION;
+    m.payout_per_outcome = [0; MAX_OUTCOMES];
+    m.rent_payer = authority;
+    m.positions_untracked = false;
+
+    Ok(())
+}
+
+pub(crate) fn emit_market_created(market: Pubkey, m: &Market) {
+    emit!(MarketCreated {
+        market,
+        authority: m.authority,
+        mint: m.mint,
+        oracle_kind: m.oracle_kind,
+        market_type: m.market_type,
+        num_outcomes: m.num_outcomes,
+        feed_id: m.feed_id,
+        question_hash: m.question_hash,
+        comparator: m.comparator,
+        bound_lo: m.bound_lo,
+        bound_hi: m.bound_hi,
+        end_ts: m.end_ts,
+    });
+}
+
+/// On-chain market_type of a binary price market; bucket markets go through create_market_buckets
+fn price_binary_type(market_type: MarketType) -> Result<u8> {
+    match market_type {
+        MarketType::PriceThreshold => Ok(0),
+        MarketType::PriceRange => Ok(1),
+        MarketType::PriceBuckets => Err(error!(ErrorCode::BadMarketType)),
+        MarketType::PriceScalar => Ok(MarketType::PriceScalar as u8),
+    }
+}
+
+/// Scalar payouts interpolate between the bounds, so the range can't be empty
+fn check_scalar_bounds(m: &Market) -> Result<()> {
+    require!(!m.is_scalar() || m.bound_lo < m.bound_hi, ErrorCode::BadScalarBounds);
+    Ok(())
+}
+
+/// Validate creator limits against the config caps; an unset limit falls back to the cap
+pub(crate) fn apply_stake_limits(m: &mut Market, cfg: &Config, l: StakeLimits) -> Result<()> {
+    let capped = |v: u64, cap: u64| -> Result<u64> {
+        match (v, cap) {
+            (0, cap) => Ok(cap),
+            (v, 0) => Ok(v),
+            (v, cap) => {
+                require!(v <= cap, ErrorCode::BadStakeLimits);
+                Ok(v)
+            }
+        }
+    };
+    let max_bet = capped(l.max_bet, cfg.max_bet_cap)?;
+    let max_per_wallet = capped(l.max_per_wallet, cfg.max_per_wallet_cap)?;
+
+    require!(max_bet == 0 || l.min_bet <= max_bet, ErrorCode::BadStakeLimits);
+    require!(max_per_wallet == 0 || l.min_bet <= max_per_wallet, ErrorCode::BadStakeLimits);
+
+    m.min_bet = l.min_bet;
+    m.max_bet = max_bet;
+    m.max_per_wallet = max_per_wallet;
     Ok(())
 }
 
@@ -135,9 +460,13 @@ pub struct CreateMarket<'info> {
     )]
     pub market: Account<'info, Market>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
-    #[account(seeds = [b"config"], bump)]
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
     pub config: Account<'info, Config>,
 
     /// CHECK: PDA
@@ -152,19 +481,21 @@ pub struct CreateMarket<'info> {
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = escrow_authority_yes
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault_yes: Account<'info, TokenAccount>,
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = escrow_authority_no
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault_no: Account<'info, TokenAccount>,
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -176,6 +507,9 @@ pub struct CreateMarketMultiParams {
     pub end_ts: i64,
     pub ai_oracle_authority: Pubkey,
     pub salt: [u8; 8],  // Unique salt to prevent PDA collisions
+    pub limits: StakeLimits,
+    pub question_hash: [u8; 32], // sha256 of the canonical question spec
+    pub metadata_uri: String,    // where that spec is served; may be empty
 }
 
 #[derive(Accounts)]
@@ -193,8 +527,151 @@ pub struct CreateMarketMulti<'info> {
     )]
     pub market: Account<'info, Market>,
 
-    #[account(seeds = [b"config"], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub oracle_committee: Option<Account<'info, OracleCommittee>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketBucketsParams {
+    pub feed_id: [u8; 32],
+    pub end_ts: i64,
+    pub bounds: Vec<i64>,   // 1..MAX_OUTCOMES-1 ascending boundaries, USD 1e6
+    pub salt: [u8; 8],
+    pub limits: StakeLimits,
+    pub rules: ResolutionRules,
+}
+
+#[derive(Accounts)]
+#[instruction(p: CreateMarketBucketsParams)]
+pub struct CreateMarketBuckets<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Market::SPACE,
+        seeds = [b"market", authority.key().as_ref(), &p.end_ts.to_le_bytes(), &p.feed_id, &p.salt],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketSwitchboardParams {
+    pub market_type: MarketType,
+    pub comparator: u8,
+    pub bound_lo_usd_6: i64,
+    pub bound_hi_usd_6: i64,
+    pub end_ts: i64,
+    pub limits: StakeLimits,
+    pub rules: ResolutionRules,
+}
+
+#[derive(Accounts)]
+#[instruction(p: CreateMarketSwitchboardParams)]
+pub struct CreateMarketSwitchboard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Market::SPACE,
+        seeds = [b"market", authority.key().as_ref(), switchboard_feed.key().as_ref(), &p.end_ts.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: owner and layout checked by read_switchboard_feed
+    pub switchboard_feed: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketLadderParams {
+    pub feed_id: [u8; 32],
+    pub comparator: u8,
+    pub end_ts: i64,
+    pub strikes: Vec<i64>,  // 2..=MAX_LADDER_STRIKES ascending thresholds, USD 1e6
+    pub limits: StakeLimits,
+    pub rules: ResolutionRules,
+}
+
+#[derive(Accounts)]
+pub struct CreateMarketLadder<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
     pub config: Account<'info, Config>,
 
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,178 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::{
+    constants::{CONFIG_VERSION, MARKET_VERSION, POSITION_VERSION},
+    errors::ErrorCode,
+    events::{ConfigMigrated, MarketMigrated},
+    state::{Config, LpPosition, Market, PositionBin, PositionMulti},
+};
+
+/// Bring a market, and any of its positions passed in `remaining_accounts`, up to the current
+/// account layouts. Short accounts are grown with the new fields zeroed (the payer covers the
+/// extra rent), legacy defaults are filled in and the version is stamped. Permissionless;
+/// accounts already at the current version are left untouched.
+pub fn migrate_market<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateMarket<'info>>,
+) -> Result<()> {
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    let market_ai = ctx.accounts.market.to_account_info();
+    let market_key = market_ai.key();
+
+    require_keys_eq!(*market_ai.owner, crate::ID, ErrorCode::NotMigratable);
+    let from_version = {
+        let data = market_ai.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && &data[..8] == Market::DISCRIMINATOR,
+            ErrorCode::NotMigratable
+        );
+        Market::data_version(&data)
+    };
+
+    if from_version < MARKET_VERSION {
+        grow(&market_ai, Market::SPACE, &payer, &system)?;
+
+        let mut data = market_ai.try_borrow_mut_data()?;
+        let mut m = Market::decode_any_version(&data)?;
+        m.version = MARKET_VERSION;
+        let mut w: &mut [u8] = &mut data[..];
+        m.try_serialize(&mut w)?;
+    }
+
+    let mut positions: u16 = 0;
+    for ai in ctx.remaining_accounts {
+        require_keys_eq!(*ai.owner, crate::ID, ErrorCode::NotMigratable);
+        require!(ai.is_writable, ErrorCode::NotMigratable);
+
+        // Every position kind starts with its market
+        let (space, version_offset) = {
+            let data = ai.try_borrow_data()?;
+            require!(data.len() >= 8 + 32, ErrorCode::NotMigratable);
+            let disc = &data[..8];
+            let layout = if disc == PositionBin::DISCRIMINATOR {
+                (PositionBin::SPACE, PositionBin::VERSION_OFFSET)
+            } else if disc == PositionMulti::DISCRIMINATOR {
+                (PositionMulti::SPACE, PositionMulti::VERSION_OFFSET)
+            } else if disc == LpPosition::DISCRIMINATOR {
+                (LpPosition::SPACE, LpPosition::VERSION_OFFSET)
+            } else {
+                return err!(ErrorCode::NotMigratable);
+            };
+            let owner_market = Pubkey::try_from(&data[8..40])
+                .map_err(|_| error!(ErrorCode::NotMigratable))?;
+            require_keys_eq!(owner_market, market_key, ErrorCode::NotMigratable);
+
+            if data.get(layout.1).copied().unwrap_or(0) >= POSITION_VERSION {
+                continue;
+            }
+            layout
+        };
+
+        grow(ai, space, &payer, &system)?;
+        ai.try_borrow_mut_data()?[version_offset] = POSITION_VERSION;
+        positions = positions.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    }
+
+    emit!(MarketMigrated {
+        market: market_key,
+        from_version,
+        version: MARKET_VERSION,
+        positions,
+    });
+
+    Ok(())
+}
+
+/// Bring the global config up to the current layout. Every `Account<Config>` fails to load
+/// until this runs after an upgrade from the pre-versioning layout. Permissionless, like
+/// migrate_market: the legacy defaults are fixed and the admin tunes them afterwards.
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    let config_ai = ctx.accounts.config.to_account_info();
+
+    require_keys_eq!(*config_ai.owner, crate::ID, ErrorCode::NotMigratable);
+    let from_version = {
+        let data = config_ai.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && &data[..8] == Config::DISCRIMINATOR,
+            ErrorCode::NotMigratable
+        );
+        Config::data_version(&data)
+    };
+    if from_version >= CONFIG_VERSION {
+        return Ok(());
+    }
+
+    grow(&config_ai, Config::SPACE, &payer, &system)?;
+    let mut data = config_ai.try_borrow_mut_data()?;
+    let mut cfg = Config::decode_any_version(&data)?;
+    cfg.version = CONFIG_VERSION;
+    let mut w: &mut [u8] = &mut data[..];
+    cfg.try_serialize(&mut w)?;
+
+    emit!(ConfigMigrated {
+        from_version,
+        version: CONFIG_VERSION,
+    });
+    Ok(())
+}
+
+/// Resize `ai` up to `space` bytes, zero-filling the tail; the payer tops up rent exemption
+fn grow<'info>(
+    ai: &AccountInfo<'info>,
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+) -> Result<()> {
+    if ai.data_len() >= space {
+        return Ok(());
+    }
+
+    let needed = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(ai.lamports());
+    if needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: ai.clone(),
+                },
+            ),
+            needed,
+        )?;
+    }
+
+    ai.resize(space)?;
+    Ok(())
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: may not deserialize under the current layout; owner and discriminator are
+    /// checked in the handler
+    #[account(mut)]
+    pub market: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: may not deserialize under the current layout; owner and discriminator are
+    /// checked in the handler
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -2,15 +2,31 @@ pub mod admin;
 pub mod airdrop;
 pub mod betting;
 pub mod claims;
+pub mod close;
+pub mod committee;
+pub mod dispute;
+pub mod liquidity;
 pub mod market_create;
+pub mod migrate;
+pub mod referral;
 pub mod resolve_ai;
 pub mod resolve_pyth;
+pub mod resolve_switchboard;
+pub mod series;
 
 // Re export all account structs
 pub use admin::*;
 pub use airdrop::*;
 pub use betting::*;
 pub use claims::*;
+pub use close::*;
+pub use committee::*;
+pub use dispute::*;
+pub use liquidity::*;
 pub use market_create::*;
+pub use migrate::*;
+pub use referral::*;
 pub use resolve_ai::*;
-pub use resolve_pyth::*;
\ No newline at end of file
+pub use resolve_pyth::*;
+pub use resolve_switchboard::*;
+pub use series::*;
\ No newline at end of file
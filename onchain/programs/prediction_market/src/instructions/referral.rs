@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{ESCROW_SEED, PAUSE_CLAIMS, REFERRAL_SEED, SIDE_REFERRAL},
+    errors::ErrorCode,
+    events::ReferralClaimed,
+    state::{Config, Market, ReferralStake},
+    utils::{escrow_signer_seeds, mul_div_u64, pda_escrow_auth},
+};
+
+/// Pay out a referrer's pro-rata share of the market's referral pool
+pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+    let m = &ctx.accounts.market;
+    require!(m.settled, ErrorCode::MarketNotResolved);
+
+    let stake = &mut ctx.accounts.referral_stake;
+    require!(!stake.claimed, ErrorCode::AlreadyClaimed);
+    require!(
+        stake.volume > 0 && m.referred_volume > 0 && m.referral_pool > 0,
+        ErrorCode::NoReferralVolume
+    );
+
+    let share = mul_div_u64(m.referral_pool, stake.volume, m.referred_volume)?;
+    let payout = core::cmp::min(share, ctx.accounts.referral_vault.amount);
+    stake.claimed = true;
+
+    if payout > 0 {
+        let market_key = m.key();
+        let bump_arr = [ctx.bumps.referral_authority];
+        let seeds = escrow_signer_seeds(&market_key, SIDE_REFERRAL, &bump_arr);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.referral_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.referrer_ata.to_account_info(),
+                    authority: ctx.accounts.referral_authority.to_account_info(),
+                },
+                &[&seeds],
+            ),
+            payout,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    emit!(ReferralClaimed {
+        market: m.key(),
+        referrer: stake.referrer,
+        volume: stake.volume,
+        amount: payout,
+    });
+    Ok(())
+}
+
+/// Credit a bet to the position's referrer. A fresh position takes the referrer passed
+/// with its first bet; later bets must pass the same referrer and stake account.
+pub(crate) fn accrue_referral<'info>(
+    position_referrer: &mut Pubkey,
+    fresh: bool,
+    market: &mut Account<'info, Market>,
+    user: &Signer<'info>,
+    referrer: Option<&UncheckedAccount<'info>>,
+    referral_stake: Option<&UncheckedAccount<'info>>,
+    system_program: &Program<'info, System>,
+    program_id: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if fresh {
+        if let Some(r) = referrer {
+            require_keys_neq!(r.key(), user.key(), ErrorCode::BadReferrer);
+            *position_referrer = r.key();
+        }
+    }
+    if *position_referrer == Pubkey::default() {
+        return Ok(());
+    }
+
+    let referrer = referrer.ok_or(ErrorCode::BadReferrer)?;
+    require_keys_eq!(referrer.key(), *position_referrer, ErrorCode::BadReferrer);
+    let stake_ai = referral_stake.ok_or(ErrorCode::BadReferrer)?.to_account_info();
+
+    let market_key = market.key();
+    let (pda, bump) = Pubkey::find_program_address(
+        &[REFERRAL_SEED, market_key.as_ref(), referrer.key.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(stake_ai.key(), pda, ErrorCode::BadReferrer);
+
+    // First referred bet in this market creates the referrer's stake, paid by the bettor
+    let mut stake = if stake_ai.data_is_empty() {
+        let bump_arr = [bump];
+        let seeds: [&[u8]; 4] = [REFERRAL_SEED, market_key.as_ref(), referrer.key.as_ref(), &bump_arr];
+        create_stake_account(&stake_ai, user, system_program, program_id, &seeds)?;
+        ReferralStake {
+            market: market_key,
+            referrer: referrer.key(),
+            volume: 0,
+            claimed: false,
+        }
+    } else {
+        load_stake(&stake_ai, program_id)?
+    };
+    stake.volume = stake.volume.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    stake.try_serialize(&mut &mut stake_ai.try_borrow_mut_data()?[..])?;
+
+    market.referred_volume = market.referred_volume
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+/// Take an exited amount back off the position's referrer, if it has one
+pub(crate) fn release_referral<'info>(
+    position_referrer: Pubkey,
+    market: &mut Account<'info, Market>,
+    referral_stake: Option<&UncheckedAccount<'info>>,
+    program_id: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if position_referrer == Pubkey::default() {
+        return Ok(());
+    }
+
+    let stake_ai = referral_stake.ok_or(ErrorCode::BadReferrer)?.to_account_info();
+    let market_key = market.key();
+    let (pda, _) = Pubkey::find_program_address(
+        &[REFERRAL_SEED, market_key.as_ref(), position_referrer.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(stake_ai.key(), pda, ErrorCode::BadReferrer);
+
+    let mut stake = load_stake(&stake_ai, program_id)?;
+    stake.volume = stake.volume.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+    stake.try_serialize(&mut &mut stake_ai.try_borrow_mut_data()?[..])?;
+
+    market.referred_volume = market.referred_volume
+        .checked_sub(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+fn load_stake(stake_ai: &AccountInfo, program_id: &Pubkey) -> Result<ReferralStake> {
+    require_keys_eq!(*stake_ai.owner, *program_id, ErrorCode::BadReferrer);
+    let data = stake_ai.try_borrow_data()?;
+    ReferralStake::try_deserialize(&mut &data[..])
+}
+
+/// Create the stake PDA; an address already holding lamports is topped up and assigned instead
+fn create_stake_account<'info>(
+    stake_ai: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(ReferralStake::SPACE);
+    let sys = system_program.to_account_info();
+
+    if stake_ai.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                sys,
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: stake_ai.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            ReferralStake::SPACE as u64,
+            program_id,
+        );
+    }
+
+    let top_up = rent.saturating_sub(stake_ai.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                sys.clone(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: stake_ai.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            sys.clone(),
+            Allocate { account_to_allocate: stake_ai.clone() },
+            &[seeds],
+        ),
+        ReferralStake::SPACE as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            sys,
+            Assign { account_to_assign: stake_ai.clone() },
+            &[seeds],
+        ),
+        program_id,
+    )
+}
+
+/// Settlement-side check that a passed referral vault is the market's referral escrow ATA
+pub(crate) fn referral_vault<'a, 'info>(
+    vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    market_key: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    let vault = vault.ok_or(ErrorCode::ReferralVaultRequired)?;
+    let (auth, _) = pda_escrow_auth(market_key, SIDE_REFERRAL, program_id);
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address_with_program_id(&auth, mint, token_program),
+        ErrorCode::ReferralVaultRequired
+    );
+    Ok(vault)
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = mint,
+        associated_token::authority = referrer,
+        associated_token::token_program = token_program
+    )]
+    pub referrer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, market.key().as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_stake: Account<'info, ReferralStake>,
+
+    /// CHECK: PDA derived in constraint
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_REFERRAL], bump)]
+    pub referral_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = referral_authority,
+        associated_token::token_program = token_program
+    )]
+    pub referral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CLAIMS) @ ErrorCode::ClaimsPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
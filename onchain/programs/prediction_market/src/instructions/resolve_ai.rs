@@ -1,43 +1,51 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
     ed25519_program,
-	program_pack::Pack,
     sysvar::instructions as sysvar_instructions,
     sysvar::instructions::ID as SYSVAR_INSTRUCTIONS_ID,
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use spl_token::state::Account as SplTokenAccount;
 
 use crate::{
     constants::{
-        ATTESTATION_FUTURE_TOLERANCE, ATTESTATION_TIME_TOLERANCE, OUTCOME_NONE,
+        ATTESTATION_FUTURE_TOLERANCE, ATTESTATION_TIME_TOLERANCE, DISPUTE_NONE, DISPUTE_OPEN,
+        DISPUTE_OVERTURNED, OUTCOME_NONE, RESOLVE_HORIZON_SECS, TIP_CURVE_FLAT,
+        WINNING_SIDE_VOID,
     },
     errors::ErrorCode,
-    events::{MarketProposedAi, MarketResolvedAi},
-    state::Market,
+    events::{MarketProposedAi, MarketResolvedAi, MarketVoidedAi},
+    instructions::referral::referral_vault,
+    state::{Market, OracleCommittee, UsedNonce},
     types::OracleKind,
     utils::{
-        attestation::{parse_attestation_message, parse_ed25519, ParsedMode},
+        attestation::{count_attesters, parse_attestation_message, parse_ed25519, ParsedMode},
         fees::FeeBreakdown,
         pda::{outcome_signer_seeds, pda_escrow_auth_outcome},
         transfers::{
             categorize_accounts, distribute_fees_from_winners, distribute_loser_funds_to_winners,
-            transfer_losers_to_winner,
+            read_token_account, transfer_losers_to_winner,
         },
     },
 };
 
 pub fn propose<'info>(
     ctx: Context<'_, '_, 'info, 'info, ResolveAiPropose<'info>>,
+    nonce: u64,
 ) -> Result<()> {
     require!(
         ctx.accounts.market.oracle_kind == OracleKind::Ai as u8,
         ErrorCode::BadMarketType
     );
     require!(!ctx.accounts.market.settled, ErrorCode::AlreadySettled);
+    // A second attestation must not replace the outcome or restart the challenge window
+    require!(!ctx.accounts.market.tentative, ErrorCode::AlreadyProposed);
+    require!(
+        ctx.accounts.market.dispute_status == DISPUTE_NONE,
+        ErrorCode::AlreadyDisputed
+    );
     
     let now = Clock::get()?.unix_timestamp;
     require!(now >= ctx.accounts.market.end_ts, ErrorCode::TooEarly);
@@ -53,25 +61,25 @@ pub fn propose<'info>(
     
     require_keys_eq!(ed_ix.program_id, ed25519_program::id(), ErrorCode::Unauthorized);
     
-    let (pk_bytes, msg_bytes) = parse_ed25519(&ed_ix.data)
+    // The last attestation fixes the payload; signer checks happen once it is validated
+    let (_, msg_bytes) = parse_ed25519(&ed_ix.data)
         .map_err(|_| error!(ErrorCode::Unauthorized))?;
-    
-    require!(
-        pk_bytes == &ctx.accounts.market.ai_oracle_authority.to_bytes(),
-        ErrorCode::Unauthorized
-    );
 
-let program_id = ctx.program_id;
+    let program_id = ctx.program_id;
     let market_key = ctx.accounts.market.key();
     let num_outcomes = ctx.accounts.market.num_outcomes;
     let tvl = ctx.accounts.market.tvl_per_outcome;
+    let expected_question = ctx.accounts.market.question_hash;
 
     enum Mode {
         Single { win: u8 },
         Multi { mask: u8 },
+        Void,
     }
 
     let parsed = parse_attestation_message(msg_bytes)?;
+    // The nonce PDA is seeded from the argument, so it must be the signed one
+    require!(parsed.nonce() == nonce, ErrorCode::Unauthorized);
     let (expected, mode) = match parsed {
         ParsedMode::Single {
             outcome_idx,
@@ -80,8 +88,10 @@ let program_id = ctx.program_id;
             nonce,
             program,
             market,
+            question_hash,
         } => {
             require_keys_eq!(market, market_key, ErrorCode::Unauthorized);
+            require!(question_hash == expected_question, ErrorCode::Unauthorized);
             require!(outcome_idx < num_outcomes, ErrorCode::BadMarketType);
             require!(end_ts == ctx.accounts.market.end_ts, ErrorCode::Unauthorized);
             require!(
@@ -101,6 +111,7 @@ let program_id = ctx.program_id;
                 attest_ts,
                 nonce,
                 program: *program_id,
+                question_hash,
             };
             (
                 crate::utils::attestation::build_message_single(&att),
@@ -114,8 +125,10 @@ let program_id = ctx.program_id;
             nonce,
             program,
             market,
+            question_hash,
         } => {
             require_keys_eq!(market, market_key, ErrorCode::Unauthorized);
+            require!(question_hash == expected_question, ErrorCode::Unauthorized);
             require!(end_ts == ctx.accounts.market.end_ts, ErrorCode::Unauthorized);
             require!(
                 attest_ts <= now + ATTESTATION_FUTURE_TOLERANCE,
@@ -150,16 +163,68 @@ let program_id = ctx.program_id;
                 attest_ts,
                 nonce,
                 program: *program_id,
+                question_hash,
             };
             (
                 crate::utils::attestation::build_message_multi(&att),
                 Mode::Multi { mask },
             )
         }
+        ParsedMode::Void {
+            end_ts,
+            attest_ts,
+            nonce,
+            program,
+            market,
+            question_hash,
+        } => {
+            require_keys_eq!(market, market_key, ErrorCode::Unauthorized);
+            require!(question_hash == expected_question, ErrorCode::Unauthorized);
+            require!(end_ts == ctx.accounts.market.end_ts, ErrorCode::Unauthorized);
+            require!(
+                attest_ts <= now + ATTESTATION_FUTURE_TOLERANCE,
+                ErrorCode::Unauthorized
+            );
+            require!(
+                now - attest_ts <= ATTESTATION_TIME_TOLERANCE,
+                ErrorCode::Unauthorized
+            );
+            require_keys_eq!(program, *program_id, ErrorCode::Unauthorized);
+
+            let att = crate::utils::attestation::AttestationVoid {
+                market,
+                end_ts,
+                attest_ts,
+                nonce,
+                program: *program_id,
+                question_hash,
+            };
+            (
+                crate::utils::attestation::build_message_void(&att),
+                Mode::Void,
+            )
+        }
     };
 
     require!(msg_bytes == expected.as_slice(), ErrorCode::Unauthorized);
 
+    // Committee markets need `threshold` distinct member signatures over this payload;
+    // legacy markets accept ai_oracle_authority alone
+    let legacy_signer = [ctx.accounts.market.ai_oracle_authority];
+    let (members, threshold) = match &ctx.accounts.oracle_committee {
+        Some(c) => (c.members(), c.threshold as usize),
+        None => {
+            require_keys_eq!(
+                ctx.accounts.market.oracle_committee,
+                Pubkey::default(),
+                ErrorCode::BadCommittee
+            );
+            (&legacy_signer[..], 1)
+        }
+    };
+    let signed = count_attesters(&ctx.accounts.ix_sysvar, cur_ix, msg_bytes, members)?;
+    require!(signed >= threshold, ErrorCode::NotEnoughAttestations);
+
     // Update market state
     let pot_u128: u128 = tvl.iter().map(|&x| x as u128).sum();
     let m = &mut ctx.accounts.market;
@@ -167,13 +232,25 @@ let program_id = ctx.program_id;
         Mode::Single { win } => {
             m.outcome_idx = win;
             m.winners_mask = 0;
+            m.winning_side = 0;
         }
         Mode::Multi { mask } => {
             m.outcome_idx = u8::MAX;
             m.winners_mask = mask;
+            m.winning_side = 0;
+        }
+        Mode::Void => {
+            m.outcome_idx = OUTCOME_NONE;
+            m.winners_mask = 0;
+            m.winning_side = WINNING_SIDE_VOID;
         }
     }
     m.tentative = true;
+    m.proposed_at = now;
+
+    let used = &mut ctx.accounts.used_nonce;
+    used.market = market_key;
+    used.used_at = now;
 
     emit!(MarketProposedAi {
         market: market_key,
@@ -195,20 +272,72 @@ pub fn finalize<'info>(
     require!(m.tentative, ErrorCode::Unauthorized);
     require!(!m.settled, ErrorCode::AlreadySettled);
 
+    // Challenge window: finalization is frozen while a dispute is open,
+    // and a ruled dispute lets finalization proceed immediately
+    require!(m.dispute_status != DISPUTE_OPEN, ErrorCode::DisputeOpen);
+    if m.dispute_status == DISPUTE_NONE {
+        let now = Clock::get()?.unix_timestamp;
+        let deadline = m
+            .proposed_at
+            .checked_add(m.dispute_window_secs)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(now >= deadline, ErrorCode::DisputeWindowOpen);
+    }
+
     let market_key = m.key();
+
+    // Void proposal: no funds move, bettors use refund_multi
+    if m.winning_side == WINNING_SIDE_VOID {
+        let pot_u128: u128 = m.tvl_per_outcome.iter().map(|&x| x as u128).sum();
+        m.settled = true;
+        m.tentative = false;
+        m.payout_pool = 0;
+
+        emit!(MarketVoidedAi {
+            market: market_key,
+            pot: pot_u128,
+            by_attestation: true,
+        });
+        return Ok(());
+    }
+
     let program_id = ctx.program_id;
     let mint_key = ctx.accounts.mint.key();
     let num_outcomes = m.num_outcomes;
     let tvl = m.tvl_per_outcome;
 
-    // Calculate fees
-    let pot_u128: u128 = tvl.iter().map(|&x| x as u128).sum();
+    // Calculate fees (exit fees left in the vaults belong to the pot)
+    let pot_u128: u128 = tvl.iter().map(|&x| x as u128).sum::<u128>() + m.exit_fee_pool as u128;
     let fees = FeeBreakdown::calculate(
         pot_u128,
         m.fee_bps_snapshot,
         m.resolver_bps_snapshot,
         m.creator_bps_snapshot,
-    )?;
+    )?
+    // AI markets settle on the oracle's schedule, so only the cap applies
+    .with_resolver_tip_policy(m.resolver_tip_cap_snapshot, TIP_CURVE_FLAT, m.end_ts, 0)?
+    .with_referrals(pot_u128, m.referred_volume, m.referral_bps_snapshot)?;
+    if fees.referral_fee > 0 {
+        referral_vault(
+            ctx.accounts.referral_vault.as_ref(),
+            &market_key,
+            &mint_key,
+            &ctx.accounts.token_program.key(),
+            program_id,
+        )?;
+    }
+    let fees = if m.dispute_status == DISPUTE_OVERTURNED {
+        let disputer_ata = ctx
+            .accounts
+            .disputer_ata
+            .as_ref()
+            .ok_or(ErrorCode::Unauthorized)?;
+        require_keys_eq!(disputer_ata.owner, m.disputer, ErrorCode::Unauthorized);
+        require_keys_eq!(disputer_ata.mint, ctx.accounts.mint.key(), ErrorCode::WrongMint);
+        fees.with_disputer_reward(pot_u128, m.dispute_reward_bps_snapshot)?
+    } else {
+        fees
+    };
 
     // Determine resolution mode
     let is_single_winner = m.winners_mask == 0 && m.outcome_idx != u8::MAX;
@@ -222,6 +351,35 @@ pub fn finalize<'info>(
     Ok(())
 }
 
+/// Permissionless void once the resolution horizon passes without a proposal
+pub fn void_ai_market(ctx: Context<VoidAiMarket>) -> Result<()> {
+    let m = &mut ctx.accounts.market;
+    require!(m.oracle_kind == OracleKind::Ai as u8, ErrorCode::BadMarketType);
+    require!(!m.settled, ErrorCode::AlreadySettled);
+    require!(!m.tentative, ErrorCode::AlreadyProposed);
+
+    let now = Clock::get()?.unix_timestamp;
+    let horizon = m
+        .end_ts
+        .checked_add(RESOLVE_HORIZON_SECS)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(now >= horizon, ErrorCode::TooEarly);
+
+    m.outcome_idx = OUTCOME_NONE;
+    m.winners_mask = 0;
+    m.winning_side = WINNING_SIDE_VOID;
+    m.payout_pool = 0;
+    m.settled = true;
+
+    emit!(MarketVoidedAi {
+        market: m.key(),
+        pot: m.tvl_per_outcome.iter().map(|&x| x as u128).sum(),
+        by_attestation: false,
+    });
+
+    Ok(())
+}
+
 // ============ Single Winner Logic ============
 
 fn finalize_single_winner<'info>(
@@ -250,7 +408,7 @@ fn finalize_single_winner<'info>(
         &ctx.accounts.win_vault,
         market_key,
         program_id,
-        mint_key,
+        &ctx.accounts.mint,
         num_outcomes,
         win,
     )?;
@@ -264,51 +422,106 @@ fn finalize_single_winner<'info>(
 
     // Pay resolver tip
     if resolver_u64 > 0 {
-        anchor_spl::token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                anchor_spl::token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.resolver_ata.to_account_info(),
                     authority: ctx.accounts.win_authority.to_account_info(),
                 },
                 &[&win_signer],
             ),
             resolver_u64,
+            ctx.accounts.mint.decimals,
         )?;
     }
 
     // Pay creator tip
     if creator_u64 > 0 {
-        anchor_spl::token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                anchor_spl::token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.creator_ata.to_account_info(),
                     authority: ctx.accounts.win_authority.to_account_info(),
                 },
                 &[&win_signer],
             ),
             creator_u64,
+            ctx.accounts.mint.decimals,
         )?;
     }
 
     // Pay protocol fee
     if fee_u64 > 0 {
-        anchor_spl::token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                anchor_spl::token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.treasury_ata.to_account_info(),
                     authority: ctx.accounts.win_authority.to_account_info(),
                 },
                 &[&win_signer],
             ),
             fee_u64,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    // Pay disputer reward
+    let reward_u64 = fees.disputer_reward_u64()?;
+    if reward_u64 > 0 {
+        let disputer_ata = ctx
+            .accounts
+            .disputer_ata
+            .as_ref()
+            .ok_or(ErrorCode::Unauthorized)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: disputer_ata.to_account_info(),
+                    authority: ctx.accounts.win_authority.to_account_info(),
+                },
+                &[&win_signer],
+            ),
+            reward_u64,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    // Set aside the referral slice (vault checked in finalize)
+    let referral_u64 = fees.referral_fee_u64()?;
+    if referral_u64 > 0 {
+        let vault = ctx
+            .accounts
+            .referral_vault
+            .as_ref()
+            .ok_or(ErrorCode::ReferralVaultRequired)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.win_authority.to_account_info(),
+                },
+                &[&win_signer],
+            ),
+            referral_u64,
+            ctx.accounts.mint.decimals,
         )?;
     }
+    m.referral_pool = referral_u64;
 
     // Calculate final payout pool
     ctx.accounts.win_vault.reload()?;
@@ -388,6 +601,7 @@ fn finalize_multi_winner<'info>(
         &losers,
         &winners_pairs,
         &ctx.accounts.token_program,
+        &ctx.accounts.mint,
         market_key,
         tvl,
         total_winners_stake,
@@ -396,8 +610,7 @@ fn finalize_multi_winner<'info>(
     // Calculate total in winner vaults after redistribution
     let mut total_after: u128 = 0;
     for (_, _, _, vault_ai, _) in winners_pairs.iter() {
-        let st = SplTokenAccount::unpack(&vault_ai.try_borrow_data()?)
-            .map_err(|_| error!(ErrorCode::WrongMint))?;
+        let st = read_token_account(vault_ai)?;
         total_after = total_after
             .checked_add(st.amount as u128)
             .ok_or(error!(ErrorCode::Overflow))?;
@@ -409,16 +622,27 @@ fn finalize_multi_winner<'info>(
         fees,
         total_after,
         &ctx.accounts.token_program,
+        &ctx.accounts.mint,
         &ctx.accounts.resolver_ata,
         &ctx.accounts.creator_ata,
         &ctx.accounts.treasury_ata,
+        ctx.accounts.disputer_ata.as_ref(),
+        ctx.accounts.referral_vault.as_ref(),
         market_key,
     )?;
 
+    // Snapshot each winning vault; claims split it by stake
+    let mut payout_per_outcome = [0u64; crate::constants::MAX_OUTCOMES];
+    for (idx, _, _, vault_ai, _) in winners_pairs.iter() {
+        payout_per_outcome[*idx as usize] = read_token_account(vault_ai)?.amount;
+    }
+
     // Finalize market state
+    m.referral_pool = fees.referral_fee_u64()?;
     m.settled = true;
     m.tentative = false;
     m.payout_pool = 0;
+    m.payout_per_outcome = payout_per_outcome;
 
     emit!(MarketResolvedAi {
         market: market_key,
@@ -436,13 +660,38 @@ fn finalize_multi_winner<'info>(
 // ============ Accounts ============
 
 #[derive(Accounts)]
+#[instruction(nonce: u64)]
 pub struct ResolveAiPropose<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
     
     /// CHECK: Sysvar instructions
     #[account(address = SYSVAR_INSTRUCTIONS_ID)]
     pub ix_sysvar: UncheckedAccount<'info>,
+
+    #[account(address = market.oracle_committee @ ErrorCode::BadCommittee)]
+    pub oracle_committee: Option<Account<'info, OracleCommittee>>,
+
+    // init fails if this attester already used the nonce
+    #[account(
+        init,
+        payer = payer,
+        space = UsedNonce::SPACE,
+        seeds = [b"nonce", market.attester().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub used_nonce: Account<'info, UsedNonce>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoidAiMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
 }
 
 #[derive(Accounts)]
@@ -451,31 +700,40 @@ pub struct FinalizeAi<'info> {
     pub market: Account<'info, Market>,
 
     #[account(mut)]
-    pub resolver_ata: Account<'info, TokenAccount>,
+    pub resolver_ata: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         constraint = creator_ata.owner == market.authority @ ErrorCode::Unauthorized,
         constraint = creator_ata.mint == mint.key() @ ErrorCode::WrongMint
     )]
-    pub creator_ata: Account<'info, TokenAccount>,
+    pub creator_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = treasury_ata.mint == mint.key(),
         constraint = treasury_ata.owner == market.treasury_wallet_snapshot
     )]
-    pub treasury_ata: Account<'info, TokenAccount>,
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required only when a dispute overturned the proposal
+    #[account(mut)]
+    pub disputer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: PDA verified in instruction
     pub win_authority: UncheckedAccount<'info>,
     
     #[account(mut)]
-    pub win_vault: Account<'info, TokenAccount>,
+    pub win_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+
+    /// Market's referral escrow ATA; required when the market has referred volume
+    #[account(mut)]
+    pub referral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 }
\ No newline at end of file
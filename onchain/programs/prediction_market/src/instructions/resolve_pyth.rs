@@ -1,27 +1,35 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{
     constants::{
-        ESCROW_SEED, MAX_PRICE_STALENESS, PRICE_FETCH_GRACE_PERIOD,
-        RESOLVE_HORIZON_SECS, SIDE_NO, SIDE_YES,
+        BPS_DENOM, ESCROW_SEED, MAX_PRICE_STALENESS, OUTCOME_NONE, PRICE_FETCH_GRACE_PERIOD,
+        RESOLVE_HORIZON_SECS, SIDE_NO, SIDE_YES, WINNING_SIDE_SCALAR, WINNING_SIDE_VOID,
     },
     errors::ErrorCode,
-    events::MarketResolved,
+    events::{MarketResolved, MarketResolvedBuckets, MarketResolvedScalar},
+    instructions::referral::referral_vault,
     state::Market,
-    types::OracleKind,
-    utils::{cmp_check, escrow_signer_seeds, mul_div_bps_u128, price_to_usd_1e6_from_pyth},
+    types::{OracleKind, PricingKind},
+    utils::{
+        bucket_for_price, cmp_check, escrow_signer_seeds, mul_div_bps_u128,
+        outcome_signer_seeds, pda_escrow_auth_outcome, price_to_usd_1e6_from_pyth,
+        resolver_tip_with_policy, transfer_losers_to_winner, FeeBreakdown,
+    },
 };
 
 pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
     let market_key = ctx.accounts.market.key();
     let m = &mut ctx.accounts.market;
     
-    require!(m.oracle_kind == OracleKind::Pyth as u8, ErrorCode::BadMarketType);
+    require!(
+        m.oracle_kind == OracleKind::Pyth as u8 && !m.uses_outcome_vaults(),
+        ErrorCode::BadMarketType
+    );
     require!(!m.settled, ErrorCode::AlreadySettled);
     
     let now = Clock::get()?.unix_timestamp;
@@ -42,21 +50,67 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
     }
 
     // Fetch Pyth price
-    let max_age_i64 = (now - m.end_ts) + PRICE_FETCH_GRACE_PERIOD;
-    let max_age: u64 = max_age_i64.try_into().unwrap_or(u64::MAX);
+    if !record_settlement_price(&ctx.accounts.price_update, m, now)? {
+        return resolve_as_void(m, market_key);
+    }
 
-    let price = ctx
-        .accounts
-        .price_update
-        .get_price_no_older_than(&Clock::get()?, max_age, &m.feed_id)
-        .map_err(|_| error!(ErrorCode::InvalidPriceFeed))?;
+    settle_binary(
+        m,
+        market_key,
+        now,
+        BinarySettlement {
+            mint: &ctx.accounts.mint,
+            resolver_ata: &ctx.accounts.resolver_ata,
+            treasury_ata: &ctx.accounts.treasury_ata,
+            escrow_authority_yes: &ctx.accounts.escrow_authority_yes,
+            escrow_authority_no: &ctx.accounts.escrow_authority_no,
+            escrow_vault_yes: &mut ctx.accounts.escrow_vault_yes,
+            escrow_vault_no: &mut ctx.accounts.escrow_vault_no,
+            token_program: &ctx.accounts.token_program,
+            bump_yes: ctx.bumps.escrow_authority_yes,
+            bump_no: ctx.bumps.escrow_authority_no,
+            referral_vault: ctx.accounts.referral_vault.as_ref(),
+            program_id: ctx.program_id,
+        },
+    )
+}
 
-    let pt = price.publish_time;
-    require!(pt >= m.end_ts, ErrorCode::StalePrice);
-    require!(pt - m.end_ts <= MAX_PRICE_STALENESS, ErrorCode::StalePrice);
+/// Accounts a binary price market settles through
+pub(crate) struct BinarySettlement<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub resolver_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub treasury_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub escrow_authority_yes: &'a UncheckedAccount<'info>,
+    pub escrow_authority_no: &'a UncheckedAccount<'info>,
+    pub escrow_vault_yes: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub escrow_vault_no: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub bump_yes: u8,
+    pub bump_no: u8,
+    pub referral_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub program_id: &'a Pubkey,
+}
 
-    let price_1e6 = price_to_usd_1e6_from_pyth(price.price, price.exponent)?;
-    m.resolved_price_1e6 = price_1e6;
+/// Settle a binary price market once `resolved_price_1e6` is recorded: compare against
+/// the market's bounds, sweep the losing vault into the winner and pay tip, fee and
+/// the referral slice. When the market has LPs, their losing-side stake stays in the
+/// losing vault for claim_lp and is left out of the pot, and `lp_fee_share_bps` of the fee
+/// is held back in the winning vault as `lp_fee_pool`.
+/// Callers void empty or expired markets first; this voids when nobody backed the winner.
+/// Scalar markets branch off to `settle_scalar`.
+pub(crate) fn settle_binary(
+    m: &mut Market,
+    market_key: Pubkey,
+    now: i64,
+    s: BinarySettlement<'_, '_>,
+) -> Result<()> {
+    if m.is_scalar() {
+        return settle_scalar(m, market_key, now, s);
+    }
+
+    let yes_amt_u64 = s.escrow_vault_yes.amount;
+    let no_amt_u64 = s.escrow_vault_no.amount;
+    let price_1e6 = m.resolved_price_1e6;
 
     // Determine winner
     let yes_is_true = match m.market_type {
@@ -68,27 +122,63 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
     let winner_is_yes = yes_is_true;
     m.winning_side = if winner_is_yes { 1 } else { 2 };
 
+    // With CPMM the creator's pool is the counterparty, so no winners is not a void
+    let is_cpmm = m.pricing_kind == PricingKind::Cpmm as u8;
     let total_winners = if winner_is_yes { m.yes_total } else { m.no_total };
-    if total_winners == 0 {
+    if total_winners == 0 && !is_cpmm {
         return resolve_as_void(m, market_key);
     }
 
+    // LPs get their losing-side stake back regardless of outcome, so it is not swept
+    let lp_held = match (is_cpmm, winner_is_yes) {
+        (true, _) => 0,
+        (false, true) => m.lp_no.min(no_amt_u64),
+        (false, false) => m.lp_yes.min(yes_amt_u64),
+    };
+    let (yes_amt_u64, no_amt_u64) = if winner_is_yes {
+        (yes_amt_u64, no_amt_u64 - lp_held)
+    } else {
+        (yes_amt_u64 - lp_held, no_amt_u64)
+    };
+
     // Calculate fees
     let yes_amt = yes_amt_u64 as u128;
     let no_amt = no_amt_u64 as u128;
     let pot_u128 = yes_amt.checked_add(no_amt).ok_or(error!(ErrorCode::Overflow))?;
     
-    let fee_u128 = mul_div_bps_u128(pot_u128, m.fee_bps_snapshot as u128)?;
-    let tip_u128 = mul_div_bps_u128(pot_u128, m.resolver_bps_snapshot as u128)?;
-    let payout_pool_u128 = pot_u128
-        .checked_sub(fee_u128)
-        .ok_or(error!(ErrorCode::Overflow))?
-        .checked_sub(tip_u128)
-        .ok_or(error!(ErrorCode::Overflow))?;
+    let (fee_full, tip_full, referral_full) = settlement_cuts(m, pot_u128, now)?;
+    let (fee_u128, tip_u128, referral_u128, payout_pool_u128) = if is_cpmm {
+        // Winning shares redeem at 1 USDC; fee, tip and referrals come out of the LP residual
+        let owed = if winner_is_yes { m.yes_shares } else { m.no_shares } as u128;
+        let residual = pot_u128.checked_sub(owed).ok_or(error!(ErrorCode::Overflow))?;
+        let fee = core::cmp::min(fee_full, residual);
+        let tip = core::cmp::min(tip_full, residual - fee);
+        let referral = core::cmp::min(referral_full, residual - fee - tip);
+        m.lp_residual = (residual - fee - tip - referral)
+            .try_into()
+            .map_err(|_| error!(ErrorCode::Overflow))?;
+        (fee, tip, referral, owed)
+    } else {
+        let pp = pot_u128
+            .checked_sub(fee_full)
+            .ok_or(error!(ErrorCode::Overflow))?
+            .checked_sub(tip_full)
+            .ok_or(error!(ErrorCode::Overflow))?
+            .checked_sub(referral_full)
+            .ok_or(error!(ErrorCode::Overflow))?;
+        (fee_full, tip_full, referral_full, pp)
+    };
 
-    let bump_yes = ctx.bumps.escrow_authority_yes;
-    let bump_no = ctx.bumps.escrow_authority_no;
+    // LPs' slice of the protocol fee stays in the winning vault for claim_lp
+    let lp_fee_u128 = if m.lp_shares > 0 {
+        mul_div_bps_u128(fee_u128, m.lp_fee_share_bps_snapshot as u128)?
+    } else {
+        0
+    };
+    let fee_u128 = fee_u128 - lp_fee_u128;
+    m.lp_fee_pool = lp_fee_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
 
+    let (bump_yes, bump_no) = (s.bump_yes, s.bump_no);
     let bump_yes_arr = [bump_yes];
     let bump_no_arr = [bump_no];
 
@@ -97,44 +187,48 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
 
     // Transfer losing side to winning side
     if winner_is_yes && no_amt_u64 > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow_vault_no.to_account_info(),
-                    to: ctx.accounts.escrow_vault_yes.to_account_info(),
-                    authority: ctx.accounts.escrow_authority_no.to_account_info(),
+                s.token_program.to_account_info(),
+                TransferChecked {
+                    from: s.escrow_vault_no.to_account_info(),
+                    mint: s.mint.to_account_info(),
+                    to: s.escrow_vault_yes.to_account_info(),
+                    authority: s.escrow_authority_no.to_account_info(),
                 },
                 &[&seeds_no],
             ),
             no_amt_u64,
+            s.mint.decimals,
         )?;
     } else if !winner_is_yes && yes_amt_u64 > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow_vault_yes.to_account_info(),
-                    to: ctx.accounts.escrow_vault_no.to_account_info(),
-                    authority: ctx.accounts.escrow_authority_yes.to_account_info(),
+                s.token_program.to_account_info(),
+                TransferChecked {
+                    from: s.escrow_vault_yes.to_account_info(),
+                    mint: s.mint.to_account_info(),
+                    to: s.escrow_vault_no.to_account_info(),
+                    authority: s.escrow_authority_yes.to_account_info(),
                 },
                 &[&seeds_yes],
             ),
             yes_amt_u64,
+            s.mint.decimals,
         )?;
     }
 
     let (win_vault, win_auth, win_side, win_bump) = if winner_is_yes {
         (
-            &ctx.accounts.escrow_vault_yes,
-            &ctx.accounts.escrow_authority_yes,
+            &*s.escrow_vault_yes,
+            &s.escrow_authority_yes,
             SIDE_YES,
             bump_yes,
         )
     } else {
         (
-            &ctx.accounts.escrow_vault_no,
-            &ctx.accounts.escrow_authority_no,
+            &*s.escrow_vault_no,
+            &s.escrow_authority_no,
             SIDE_NO,
             bump_no,
         )
@@ -142,54 +236,85 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
 
     let tip_u64: u64 = tip_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
     let fee_u64: u64 = fee_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+    let referral_u64: u64 = referral_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
 
-    require_keys_eq!(ctx.accounts.resolver_ata.mint, ctx.accounts.mint.key());
-    require_keys_eq!(ctx.accounts.treasury_ata.mint, ctx.accounts.mint.key());
+    require_keys_eq!(s.resolver_ata.mint, s.mint.key());
+    require_keys_eq!(s.treasury_ata.mint, s.mint.key());
 
     let win_bump_arr = [win_bump];
     let win_seeds = escrow_signer_seeds(&market_key, win_side, &win_bump_arr);
 
     // Pay resolver tip
     if tip_u64 > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                s.token_program.to_account_info(),
+                TransferChecked {
                     from: win_vault.to_account_info(),
-                    to: ctx.accounts.resolver_ata.to_account_info(),
+                    mint: s.mint.to_account_info(),
+                    to: s.resolver_ata.to_account_info(),
                     authority: win_auth.to_account_info(),
                 },
                 &[&win_seeds],
             ),
             tip_u64,
+            s.mint.decimals,
         )?;
     }
     
     // Pay protocol fee
     if fee_u64 > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                s.token_program.to_account_info(),
+                TransferChecked {
                     from: win_vault.to_account_info(),
-                    to: ctx.accounts.treasury_ata.to_account_info(),
+                    mint: s.mint.to_account_info(),
+                    to: s.treasury_ata.to_account_info(),
                     authority: win_auth.to_account_info(),
                 },
                 &[&win_seeds],
             ),
             fee_u64,
+            s.mint.decimals,
+        )?;
+    }
+
+    // Set aside the referral slice for claim_referral
+    if referral_u64 > 0 {
+        let vault = referral_vault(
+            s.referral_vault,
+            &market_key,
+            &s.mint.key(),
+            &s.token_program.key(),
+            s.program_id,
+        )?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                s.token_program.to_account_info(),
+                TransferChecked {
+                    from: win_vault.to_account_info(),
+                    mint: s.mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: win_auth.to_account_info(),
+                },
+                &[&win_seeds],
+            ),
+            referral_u64,
+            s.mint.decimals,
         )?;
     }
+    m.referral_pool = referral_u64;
 
     // Calculate final payout pool
     if winner_is_yes {
-        ctx.accounts.escrow_vault_yes.reload()?;
-        let remain = ctx.accounts.escrow_vault_yes.amount;
+        s.escrow_vault_yes.reload()?;
+        let remain = s.escrow_vault_yes.amount;
         let pp_u64: u64 = payout_pool_u128.try_into().unwrap_or(u64::MAX);
         m.payout_pool = core::cmp::min(pp_u64, remain);
     } else {
-        ctx.accounts.escrow_vault_no.reload()?;
-        let remain = ctx.accounts.escrow_vault_no.amount;
+        s.escrow_vault_no.reload()?;
+        let remain = s.escrow_vault_no.amount;
         let pp_u64: u64 = payout_pool_u128.try_into().unwrap_or(u64::MAX);
         m.payout_pool = core::cmp::min(pp_u64, remain);
     }
@@ -200,6 +325,8 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
         market: market_key,
         winning_side: m.winning_side,
         resolved_price_1e6: m.resolved_price_1e6,
+        conf_1e6: m.resolved_conf_1e6,
+        publish_time: m.resolved_publish_time,
         pot: pot_u128,
         fee: fee_u128,
         tip: tip_u128,
@@ -209,7 +336,142 @@ pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
     Ok(())
 }
 
-fn resolve_as_void(m: &mut Market, market_key: Pubkey) -> Result<()> {
+/// Protocol fee, resolver tip and referral slice owed out of a binary market's pot
+fn settlement_cuts(m: &Market, pot: u128, now: i64) -> Result<(u128, u128, u128)> {
+    let fee = mul_div_bps_u128(pot, m.fee_bps_snapshot as u128)?;
+    let tip = resolver_tip_with_policy(
+        mul_div_bps_u128(pot, m.resolver_bps_snapshot as u128)?,
+        m.resolver_tip_cap_snapshot,
+        m.tip_curve_snapshot,
+        m.end_ts,
+        now,
+    )?;
+    let referral = mul_div_bps_u128(
+        (m.referred_volume as u128).min(pot),
+        m.referral_bps_snapshot as u128,
+    )?;
+    Ok((fee, tip, referral))
+}
+
+/// Settle a scalar market once `resolved_price_1e6` is recorded. LONG (yes vault) holders
+/// share `scalar_long_bps` of the payout pool and SHORT (no vault) holders the rest.
+/// The no vault is swept into the yes vault, tip, fee and referral slice are paid from
+/// there, and the SHORT pool is moved back so each vault holds its side's pool.
+/// Voids when either side has no bettors.
+fn settle_scalar(
+    m: &mut Market,
+    market_key: Pubkey,
+    now: i64,
+    s: BinarySettlement<'_, '_>,
+) -> Result<()> {
+    if m.yes_total == 0 || m.no_total == 0 {
+        return resolve_as_void(m, market_key);
+    }
+    let long_bps = m.scalar_long_bps_at(m.resolved_price_1e6)?;
+
+    let yes_amt_u64 = s.escrow_vault_yes.amount;
+    let no_amt_u64 = s.escrow_vault_no.amount;
+    let pot_u128 = (yes_amt_u64 as u128)
+        .checked_add(no_amt_u64 as u128)
+        .ok_or(error!(ErrorCode::Overflow))?;
+
+    let (fee_u128, tip_u128, referral_u128) = settlement_cuts(m, pot_u128, now)?;
+    let payout_pool_u128 = pot_u128
+        .checked_sub(fee_u128)
+        .ok_or(error!(ErrorCode::Overflow))?
+        .checked_sub(tip_u128)
+        .ok_or(error!(ErrorCode::Overflow))?
+        .checked_sub(referral_u128)
+        .ok_or(error!(ErrorCode::Overflow))?;
+    let long_pool_u128 = mul_div_bps_u128(payout_pool_u128, long_bps as u128)?;
+    let short_pool_u128 = payout_pool_u128 - long_pool_u128;
+
+    let tip_u64: u64 = tip_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+    let fee_u64: u64 = fee_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+    let referral_u64: u64 = referral_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+    let long_pool_u64: u64 = long_pool_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+    let short_pool_u64: u64 = short_pool_u128.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+
+    require_keys_eq!(s.resolver_ata.mint, s.mint.key());
+    require_keys_eq!(s.treasury_ata.mint, s.mint.key());
+
+    let bump_yes_arr = [s.bump_yes];
+    let bump_no_arr = [s.bump_no];
+    let seeds_yes = escrow_signer_seeds(&market_key, SIDE_YES, &bump_yes_arr);
+    let seeds_no = escrow_signer_seeds(&market_key, SIDE_NO, &bump_no_arr);
+
+    let yes_vault = s.escrow_vault_yes.to_account_info();
+    let no_vault = s.escrow_vault_no.to_account_info();
+    let yes_auth = s.escrow_authority_yes.to_account_info();
+    let no_auth = s.escrow_authority_no.to_account_info();
+
+    // Pool everything in the yes vault, take the cuts, then hand SHORT its share back
+    escrow_transfer(&s, no_vault.clone(), no_auth, yes_vault.clone(), &seeds_no, no_amt_u64)?;
+    let resolver = s.resolver_ata.to_account_info();
+    escrow_transfer(&s, yes_vault.clone(), yes_auth.clone(), resolver, &seeds_yes, tip_u64)?;
+    let treasury = s.treasury_ata.to_account_info();
+    escrow_transfer(&s, yes_vault.clone(), yes_auth.clone(), treasury, &seeds_yes, fee_u64)?;
+    if referral_u64 > 0 {
+        let vault = referral_vault(
+            s.referral_vault,
+            &market_key,
+            &s.mint.key(),
+            &s.token_program.key(),
+            s.program_id,
+        )?;
+        let vault = vault.to_account_info();
+        escrow_transfer(&s, yes_vault.clone(), yes_auth.clone(), vault, &seeds_yes, referral_u64)?;
+    }
+    escrow_transfer(&s, yes_vault, yes_auth, no_vault, &seeds_yes, short_pool_u64)?;
+
+    m.referral_pool = referral_u64;
+    m.scalar_long_bps = long_bps;
+    m.scalar_long_pool = long_pool_u64;
+    m.scalar_short_pool = short_pool_u64;
+    m.payout_pool = long_pool_u64 + short_pool_u64;
+    m.winning_side = WINNING_SIDE_SCALAR;
+    m.settled = true;
+
+    emit!(MarketResolvedScalar {
+        market: market_key,
+        resolved_price_1e6: m.resolved_price_1e6,
+        conf_1e6: m.resolved_conf_1e6,
+        publish_time: m.resolved_publish_time,
+        long_bps,
+        pot: pot_u128,
+        fee: fee_u128,
+        tip: tip_u128,
+        long_pool: long_pool_u64,
+        short_pool: short_pool_u64,
+    });
+
+    Ok(())
+}
+
+/// Escrow-signed transfer between settlement accounts; zero amounts are skipped
+fn escrow_transfer<'info>(
+    s: &BinarySettlement<'_, 'info>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            s.token_program.to_account_info(),
+            TransferChecked { from, mint: s.mint.to_account_info(), to, authority },
+            &[seeds],
+        ),
+        amount,
+        s.mint.decimals,
+    )
+}
+
+pub(crate) fn resolve_as_void(m: &mut Market, market_key: Pubkey) -> Result<()> {
     m.winning_side = 3; // VOID
     m.payout_pool = 0;
     m.settled = true;
@@ -218,6 +480,8 @@ fn resolve_as_void(m: &mut Market, market_key: Pubkey) -> Result<()> {
         market: market_key,
         winning_side: m.winning_side,
         resolved_price_1e6: m.resolved_price_1e6,
+        conf_1e6: m.resolved_conf_1e6,
+        publish_time: m.resolved_publish_time,
         pot: 0,
         fee: 0,
         tip: 0,
@@ -227,6 +491,242 @@ fn resolve_as_void(m: &mut Market, market_key: Pubkey) -> Result<()> {
     Ok(())
 }
 
+/// Pyth bucket market: the settlement price picks the winning outcome,
+/// losing outcome vaults are swept into it and claims go through `claim_multi`.
+/// `remaining_accounts` holds (escrow authority, vault) pairs for every outcome with stake.
+pub fn resolve_market_buckets<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMarketBuckets<'info>>,
+) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let program_id = ctx.program_id;
+    let mint_key = ctx.accounts.mint.key();
+    let m = &mut ctx.accounts.market;
+
+    require!(
+        m.oracle_kind == OracleKind::Pyth as u8 && m.uses_outcome_vaults(),
+        ErrorCode::BadMarketType
+    );
+    require!(!m.settled, ErrorCode::AlreadySettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= m.end_ts, ErrorCode::TooEarly);
+
+    if now - m.end_ts > RESOLVE_HORIZON_SECS {
+        return resolve_buckets_as_void(m, market_key);
+    }
+
+    if !record_settlement_price(&ctx.accounts.price_update, m, now)? {
+        return resolve_buckets_as_void(m, market_key);
+    }
+    let price_1e6 = m.resolved_price_1e6;
+
+    let num_outcomes = m.num_outcomes;
+    let tvl = m.tvl_per_outcome;
+    let win = bucket_for_price(&m.bucket_bounds[..(num_outcomes - 1) as usize], price_1e6);
+    if tvl[win as usize] == 0 {
+        return resolve_buckets_as_void(m, market_key);
+    }
+
+    // Split remaining accounts into the winner pair and the losers;
+    // every outcome with stake must be present exactly once
+    require!(ctx.remaining_accounts.len() % 2 == 0, ErrorCode::Unauthorized);
+    let mut seen: u8 = 0;
+    let mut win_pair: Option<(usize, u8)> = None;
+    let mut losers: Vec<AccountInfo<'info>> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for (pair_idx, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+        let (idx, bump) = (0..num_outcomes)
+            .find_map(|i| {
+                let (pda, bump) = pda_escrow_auth_outcome(&market_key, i, program_id);
+                (pda == pair[0].key()).then_some((i, bump))
+            })
+            .ok_or(error!(ErrorCode::Unauthorized))?;
+        require!(seen & (1 << idx) == 0, ErrorCode::Unauthorized);
+        seen |= 1 << idx;
+
+        if idx == win {
+            win_pair = Some((pair_idx * 2, bump));
+        } else {
+            losers.extend_from_slice(pair);
+        }
+    }
+    for i in 0..num_outcomes {
+        require!(tvl[i as usize] == 0 || seen & (1 << i) != 0, ErrorCode::Unauthorized);
+    }
+
+    let (win_pos, win_bump) = win_pair.ok_or(error!(ErrorCode::Unauthorized))?;
+    let win_auth = ctx.remaining_accounts[win_pos].clone();
+    let mut win_vault: InterfaceAccount<'info, TokenAccount> =
+        InterfaceAccount::try_from(&ctx.remaining_accounts[win_pos + 1])?;
+    require_keys_eq!(win_vault.owner, win_auth.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(win_vault.mint, mint_key, ErrorCode::WrongMint);
+    require_keys_eq!(ctx.accounts.treasury_ata.mint, mint_key, ErrorCode::WrongMint);
+
+    transfer_losers_to_winner(
+        &losers,
+        &ctx.accounts.token_program,
+        &win_vault,
+        market_key,
+        program_id,
+        &ctx.accounts.mint,
+        num_outcomes,
+        win,
+    )?;
+
+    // Exit fees left in the vaults belong to the pot
+    let pot_u128: u128 = tvl.iter().map(|&x| x as u128).sum::<u128>() + m.exit_fee_pool as u128;
+    let fees = FeeBreakdown::calculate(
+        pot_u128,
+        m.fee_bps_snapshot,
+        m.resolver_bps_snapshot,
+        m.creator_bps_snapshot,
+    )?
+    .with_resolver_tip_policy(m.resolver_tip_cap_snapshot, m.tip_curve_snapshot, m.end_ts, now)?
+    .with_referrals(pot_u128, m.referred_volume, m.referral_bps_snapshot)?;
+    let (fee_u64, resolver_u64, creator_u64) = fees.to_u64_parts()?;
+    let referral_u64 = fees.referral_fee_u64()?;
+
+    let win_idx = [win];
+    let win_barr = [win_bump];
+    let win_signer = outcome_signer_seeds(&market_key, &win_idx, &win_barr);
+
+    let mut payees = vec![
+        (ctx.accounts.resolver_ata.to_account_info(), resolver_u64),
+        (ctx.accounts.creator_ata.to_account_info(), creator_u64),
+        (ctx.accounts.treasury_ata.to_account_info(), fee_u64),
+    ];
+    if referral_u64 > 0 {
+        let vault = referral_vault(
+            ctx.accounts.referral_vault.as_ref(),
+            &market_key,
+            &mint_key,
+            &ctx.accounts.token_program.key(),
+            program_id,
+        )?;
+        payees.push((vault.to_account_info(), referral_u64));
+    }
+
+    for (dest, amount) in payees {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: win_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: dest,
+                    authority: win_auth.clone(),
+                },
+                &[&win_signer],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    win_vault.reload()?;
+    let payout_u64: u64 = pot_u128
+        .checked_sub(fees.total_deductions)
+        .ok_or(error!(ErrorCode::Overflow))?
+        .try_into()
+        .map_err(|_| error!(ErrorCode::Overflow))?;
+
+    m.outcome_idx = win;
+    m.winners_mask = 0;
+    m.referral_pool = referral_u64;
+    m.payout_pool = core::cmp::min(payout_u64, win_vault.amount);
+    m.settled = true;
+
+    emit!(MarketResolvedBuckets {
+        market: market_key,
+        outcome_idx: win,
+        resolved_price_1e6: price_1e6,
+        conf_1e6: m.resolved_conf_1e6,
+        publish_time: m.resolved_publish_time,
+        pot: pot_u128,
+        fee: fees.protocol_fee,
+        tip: fees.resolver_tip.checked_add(fees.creator_tip).unwrap(),
+        payout_pool: m.payout_pool as u128,
+    });
+
+    Ok(())
+}
+
+fn resolve_buckets_as_void(m: &mut Market, market_key: Pubkey) -> Result<()> {
+    m.outcome_idx = OUTCOME_NONE;
+    m.winners_mask = 0;
+    m.winning_side = WINNING_SIDE_VOID;
+    m.payout_pool = 0;
+    m.settled = true;
+
+    emit!(MarketResolvedBuckets {
+        market: market_key,
+        outcome_idx: OUTCOME_NONE,
+        resolved_price_1e6: m.resolved_price_1e6,
+        conf_1e6: m.resolved_conf_1e6,
+        publish_time: m.resolved_publish_time,
+        pot: 0,
+        fee: 0,
+        tip: 0,
+        payout_pool: 0,
+    });
+
+    Ok(())
+}
+
+/// Record the settlement price published within MAX_PRICE_STALENESS after end_ts,
+/// spot or EMA per the market's rules, with its confidence and publish time.
+/// Returns false when the confidence band is too wide and the market voids on that.
+fn record_settlement_price(
+    price_update: &Account<PriceUpdateV2>,
+    m: &mut Market,
+    now: i64,
+) -> Result<bool> {
+    let max_age_i64 = (now - m.end_ts) + PRICE_FETCH_GRACE_PERIOD;
+    let max_age: u64 = max_age_i64.try_into().unwrap_or(u64::MAX);
+
+    let price = price_update
+        .get_price_no_older_than(&Clock::get()?, max_age, &m.feed_id)
+        .map_err(|_| error!(ErrorCode::InvalidPriceFeed))?;
+
+    let pt = price.publish_time;
+    require!(pt >= m.end_ts, ErrorCode::StalePrice);
+    require!(pt - m.end_ts <= MAX_PRICE_STALENESS, ErrorCode::StalePrice);
+
+    // EMA fields come from the same (already validated) message
+    let (raw_price, raw_conf) = if m.use_ema {
+        (price_update.price_message.ema_price, price_update.price_message.ema_conf)
+    } else {
+        (price.price, price.conf)
+    };
+    let conf_i64: i64 = raw_conf.try_into().map_err(|_| error!(ErrorCode::Overflow))?;
+
+    m.resolved_price_1e6 = price_to_usd_1e6_from_pyth(raw_price, price.exponent)?;
+    m.resolved_conf_1e6 = price_to_usd_1e6_from_pyth(conf_i64, price.exponent)? as u64;
+    m.resolved_publish_time = pt;
+
+    check_confidence(m, raw_conf as u128, raw_price.unsigned_abs() as u128)
+}
+
+/// Apply the market's confidence rule to a settlement price. Returns false when the
+/// band is too wide and the market voids on that; errors when it should not settle yet.
+pub(crate) fn check_confidence(m: &Market, conf: u128, price_abs: u128) -> Result<bool> {
+    if m.max_conf_bps == 0 {
+        return Ok(true);
+    }
+    // conf / |price| <= max_conf_bps / BPS_DENOM, compared in raw units
+    let lhs = conf.checked_mul(BPS_DENOM as u128).ok_or(error!(ErrorCode::Overflow))?;
+    let rhs = price_abs
+        .checked_mul(m.max_conf_bps as u128)
+        .ok_or(error!(ErrorCode::Overflow))?;
+    if lhs <= rhs {
+        return Ok(true);
+    }
+    require!(m.void_on_wide_conf, ErrorCode::ConfidenceTooWide);
+    Ok(false)
+}
+
 // ============ Accounts ============
 
 #[derive(Accounts)]
@@ -243,18 +743,20 @@ pub struct ResolveMarket<'info> {
         init_if_needed,
         payer = resolver,
         associated_token::mint = mint,
-        associated_token::authority = resolver
+        associated_token::authority = resolver,
+        associated_token::token_program = token_program
     )]
-    pub resolver_ata: Account<'info, TokenAccount>,
+    pub resolver_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = treasury_ata.mint == mint.key(),
         constraint = treasury_ata.owner == market.treasury_wallet_snapshot
     )]
-    pub treasury_ata: Account<'info, TokenAccount>,
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA
     #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
@@ -265,12 +767,61 @@ pub struct ResolveMarket<'info> {
     pub escrow_authority_no: UncheckedAccount<'info>,
 
     #[account(mut, constraint = escrow_vault_yes.mint == mint.key())]
-    pub escrow_vault_yes: Account<'info, TokenAccount>,
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut, constraint = escrow_vault_no.mint == mint.key())]
-    pub escrow_vault_no: Account<'info, TokenAccount>,
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+
+    /// Market's referral escrow ATA; required when the market has referred volume
+    #[account(mut)]
+    pub referral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarketBuckets<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub price_update: Account<'info, PriceUpdateV2>,
+
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = resolver,
+        associated_token::mint = mint,
+        associated_token::authority = resolver,
+        associated_token::token_program = token_program
+    )]
+    pub resolver_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_ata.owner == market.authority @ ErrorCode::Unauthorized,
+        constraint = creator_ata.mint == mint.key() @ ErrorCode::WrongMint
+    )]
+    pub creator_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = treasury_ata.mint == mint.key(),
+        constraint = treasury_ata.owner == market.treasury_wallet_snapshot
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Market's referral escrow ATA; required when the market has referred volume
+    #[account(mut)]
+    pub referral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
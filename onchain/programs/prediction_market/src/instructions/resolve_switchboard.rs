@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{ESCROW_SEED, MAX_PRICE_STALENESS, RESOLVE_HORIZON_SECS, SIDE_NO, SIDE_YES},
+    errors::ErrorCode,
+    instructions::resolve_pyth::{check_confidence, resolve_as_void, settle_binary, BinarySettlement},
+    state::Market,
+    types::OracleKind,
+    utils::{price_to_usd_1e6_from_switchboard, read_switchboard_feed},
+};
+
+/// Resolve a Switchboard binary market from its pull feed; the feed must have been
+/// updated within MAX_PRICE_STALENESS after end_ts. Settlement matches `resolve_market`.
+pub fn resolve_market_switchboard(ctx: Context<ResolveMarketSwitchboard>) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let m = &mut ctx.accounts.market;
+
+    require!(
+        m.oracle_kind == OracleKind::Switchboard as u8 && !m.uses_outcome_vaults(),
+        ErrorCode::BadMarketType
+    );
+    require!(!m.settled, ErrorCode::AlreadySettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= m.end_ts, ErrorCode::TooEarly);
+
+    ctx.accounts.escrow_vault_yes.reload()?;
+    ctx.accounts.escrow_vault_no.reload()?;
+
+    // Check for void conditions
+    if ctx.accounts.escrow_vault_yes.amount == 0 || ctx.accounts.escrow_vault_no.amount == 0 {
+        return resolve_as_void(m, market_key);
+    }
+
+    if now - m.end_ts > RESOLVE_HORIZON_SECS {
+        return resolve_as_void(m, market_key);
+    }
+
+    if !record_switchboard_price(&ctx.accounts.switchboard_feed, m)? {
+        return resolve_as_void(m, market_key);
+    }
+
+    settle_binary(
+        m,
+        market_key,
+        now,
+        BinarySettlement {
+            mint: &ctx.accounts.mint,
+            resolver_ata: &ctx.accounts.resolver_ata,
+            treasury_ata: &ctx.accounts.treasury_ata,
+            escrow_authority_yes: &ctx.accounts.escrow_authority_yes,
+            escrow_authority_no: &ctx.accounts.escrow_authority_no,
+            escrow_vault_yes: &mut ctx.accounts.escrow_vault_yes,
+            escrow_vault_no: &mut ctx.accounts.escrow_vault_no,
+            token_program: &ctx.accounts.token_program,
+            bump_yes: ctx.bumps.escrow_authority_yes,
+            bump_no: ctx.bumps.escrow_authority_no,
+            referral_vault: ctx.accounts.referral_vault.as_ref(),
+            program_id: ctx.program_id,
+        },
+    )
+}
+
+/// Record the feed's current value as the settlement price, with its std deviation
+/// as the confidence band. Returns false when the market voids on a wide band.
+fn record_switchboard_price(feed: &AccountInfo, m: &mut Market) -> Result<bool> {
+    let r = read_switchboard_feed(feed)?;
+    require!(r.updated_at >= m.end_ts, ErrorCode::StalePrice);
+    require!(r.updated_at - m.end_ts <= MAX_PRICE_STALENESS, ErrorCode::StalePrice);
+
+    m.resolved_price_1e6 = price_to_usd_1e6_from_switchboard(r.value)?;
+    m.resolved_conf_1e6 = price_to_usd_1e6_from_switchboard(r.std_dev)?.unsigned_abs();
+    m.resolved_publish_time = r.updated_at;
+
+    check_confidence(m, r.std_dev.unsigned_abs(), r.value.unsigned_abs())
+}
+
+// ============ Accounts ============
+
+#[derive(Accounts)]
+pub struct ResolveMarketSwitchboard<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: must be the feed the market was created with; owner and layout checked on read
+    #[account(address = Pubkey::new_from_array(market.feed_id) @ ErrorCode::InvalidSwitchboardFeed)]
+    pub switchboard_feed: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = resolver,
+        associated_token::mint = mint,
+        associated_token::authority = resolver,
+        associated_token::token_program = token_program
+    )]
+    pub resolver_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = treasury_ata.mint == mint.key(),
+        constraint = treasury_ata.owner == market.treasury_wallet_snapshot
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Market's referral escrow ATA; required when the market has referred volume
+    #[account(mut)]
+    pub referral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+    constants::{
+        BPS_DENOM, ESCROW_SEED, MIN_SERIES_CADENCE_SECS, MIN_SERIES_ROUND_SECS, PAUSE_CREATION,
+        SERIES_STRIKE_MAX_AGE, SIDE_NO, SIDE_YES, STRIKE_CEIL,
+    },
+    errors::ErrorCode,
+    events::{SeriesCreated, SeriesRolled},
+    instructions::market_create::{apply_stake_limits, emit_market_created, reset_market},
+    state::{Config, Market, Series},
+    types::{MarketType, OracleKind, ResolutionRules, StakeLimits},
+    utils::{price_to_usd_1e6_from_pyth, round_strike},
+};
+
+pub fn create_series(ctx: Context<CreateSeries>, p: CreateSeriesParams) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.mint.key()),
+        ErrorCode::MintNotAllowed
+    );
+    require!(p.cadence_secs >= MIN_SERIES_CADENCE_SECS, ErrorCode::BadSeries);
+    require!(p.strike_rule <= STRIKE_CEIL && p.strike_step > 0, ErrorCode::BadSeries);
+    require!((p.rules.max_conf_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+
+    let s = &mut ctx.accounts.series;
+    s.id = p.id;
+    s.authority = ctx.accounts.authority.key();
+    s.mint = ctx.accounts.mint.key();
+    s.feed_id = p.feed_id;
+    s.comparator = p.comparator;
+    s.cadence_secs = p.cadence_secs;
+    s.strike_rule = p.strike_rule;
+    s.strike_step = p.strike_step;
+    s.limits = p.limits;
+    s.rules = p.rules;
+    s.active = true;
+    s.next_round = 0;
+    s.current_market = Pubkey::default();
+    s.current_end_ts = 0;
+
+    emit!(SeriesCreated {
+        series: s.key(),
+        authority: s.authority,
+        id: s.id,
+        feed_id: s.feed_id,
+        comparator: s.comparator,
+        cadence_secs: s.cadence_secs,
+        strike_rule: s.strike_rule,
+        strike_step: s.strike_step,
+    });
+    Ok(())
+}
+
+/// Pause or resume rolling; the open round is unaffected
+pub fn set_series_active(ctx: Context<UpdateSeries>, active: bool) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.series.authority,
+        ctx.accounts.authority.key(),
+        ErrorCode::Unauthorized
+    );
+    ctx.accounts.series.active = active;
+    Ok(())
+}
+
+/// Permissionless: open the series' next round once the current one has ended. The strike
+/// is the live Pyth price rounded per the series rule and the round ends on the next
+/// cadence boundary at least MIN_SERIES_ROUND_SECS away. The caller pays rent for the
+/// market and its vaults and gets it back when the market is closed.
+pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+    let s = &ctx.accounts.series;
+    require!(s.active, ErrorCode::SeriesInactive);
+    require!(
+        ctx.accounts.config.is_mint_allowed(&s.mint),
+        ErrorCode::MintNotAllowed
+    );
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(now >= s.current_end_ts, ErrorCode::SeriesRoundOpen);
+
+    let price = ctx
+        .accounts
+        .price_update
+        .get_price_no_older_than(&clock, SERIES_STRIKE_MAX_AGE, &s.feed_id)
+        .map_err(|_| error!(ErrorCode::InvalidPriceFeed))?;
+    let price_1e6 = price_to_usd_1e6_from_pyth(price.price, price.exponent)?;
+    let strike = round_strike(price_1e6, s.strike_step, s.strike_rule)?;
+
+    // Rounds end on cadence boundaries, e.g. the top of every hour
+    let mut end_ts = (now / s.cadence_secs)
+        .checked_add(1)
+        .and_then(|n| n.checked_mul(s.cadence_secs))
+        .ok_or(ErrorCode::Overflow)?;
+    if end_ts - now < MIN_SERIES_ROUND_SECS {
+        end_ts = end_ts.checked_add(s.cadence_secs).ok_or(ErrorCode::Overflow)?;
+    }
+
+    let series_key = s.key();
+    let round = s.next_round;
+    let (authority, feed_id, comparator, limits, rules) =
+        (s.authority, s.feed_id, s.comparator, s.limits, s.rules);
+
+    let market_key = ctx.accounts.market.key();
+    let m = &mut ctx.accounts.market;
+    reset_market(
+        m,
+        &ctx.accounts.config,
+        authority,
+        &ctx.accounts.mint,
+        OracleKind::Pyth as u8,
+        end_ts,
+    )?;
+    m.feed_id = feed_id;
+    m.market_type = MarketType::PriceThreshold as u8;
+    m.comparator = comparator;
+    m.bound_lo = strike;
+    m.bound_hi = 0;
+    m.rent_payer = ctx.accounts.payer.key();
+    apply_stake_limits(m, &ctx.accounts.config, limits)?;
+    m.apply_resolution_rules(rules)?;
+    emit_market_created(market_key, m);
+
+    let s = &mut ctx.accounts.series;
+    s.next_round = round.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    s.current_market = market_key;
+    s.current_end_ts = end_ts;
+
+    emit!(SeriesRolled {
+        series: series_key,
+        market: market_key,
+        round,
+        strike,
+        end_ts,
+    });
+    Ok(())
+}
+
+// ============ Accounts ============
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateSeriesParams {
+    pub id: u64,
+    pub feed_id: [u8; 32],
+    pub comparator: u8,
+    pub cadence_secs: i64,
+    pub strike_rule: u8,    // STRIKE_*
+    pub strike_step: i64,   // USD 1e6, e.g. 100_000_000 rounds to $100
+    pub limits: StakeLimits,
+    pub rules: ResolutionRules,
+}
+
+#[derive(Accounts)]
+#[instruction(p: CreateSeriesParams)]
+pub struct CreateSeries<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Series::SPACE,
+        seeds = [b"series", authority.key().as_ref(), &p.id.to_le_bytes()],
+        bump
+    )]
+    pub series: Account<'info, Series>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSeries<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"series", series.authority.as_ref(), &series.id.to_le_bytes()],
+        bump
+    )]
+    pub series: Account<'info, Series>,
+}
+
+#[derive(Accounts)]
+pub struct RollSeries<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"series", series.authority.as_ref(), &series.id.to_le_bytes()],
+        bump
+    )]
+    pub series: Account<'info, Series>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Market::SPACE,
+        seeds = [b"market", series.key().as_ref(), &series.next_round.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(address = series.mint @ ErrorCode::WrongMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.is_paused(PAUSE_CREATION) @ ErrorCode::CreationPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub price_update: Account<'info, PriceUpdateV2>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_YES], bump)]
+    pub escrow_authority_yes: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(seeds = [ESCROW_SEED, market.key().as_ref(), SIDE_NO], bump)]
+    pub escrow_authority_no: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_yes,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_yes: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority_no,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault_no: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -17,34 +17,71 @@ pub mod prediction_market {
     use super::*;
 
     // ============ Admin ============
-    pub fn init_config(
-        ctx: Context<InitConfig>,
-        fee_bps: u16,
-        resolver_bps: u16,
-        creator_bps: u16,
-        resolver_tip_cap: u64,
-    ) -> Result<()> {
-        admin::init_config(ctx, fee_bps, resolver_bps, creator_bps, resolver_tip_cap)
+    pub fn init_config(ctx: Context<InitConfig>, p: InitConfigParams) -> Result<()> {
+        admin::init_config(ctx, p)
     }
 
-    pub fn update_config(
-        ctx: Context<UpdateConfig>,
-        fee_bps: Option<u16>,
-        resolver_bps: Option<u16>,
-        creator_bps: Option<u16>,
-        resolver_tip_cap: Option<u64>,
-        new_treasury: Option<Pubkey>,
-    ) -> Result<()> {
-        admin::update_config(ctx, fee_bps, resolver_bps, creator_bps, resolver_tip_cap, new_treasury)
+    pub fn update_config(ctx: Context<UpdateConfig>, p: UpdateConfigParams) -> Result<()> {
+        admin::update_config(ctx, p)
     }
 
     pub fn close_config(ctx: Context<CloseConfig>) -> Result<()> {
         admin::close_config(ctx)
     }
 
+    pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+        admin::add_collateral_mint(ctx)
+    }
+
+    pub fn remove_collateral_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+        admin::remove_collateral_mint(ctx, mint)
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        admin::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        admin::accept_admin(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+        admin::set_paused(ctx, paused)
+    }
+
+    // ============ Oracle Committee ============
+    pub fn create_committee(
+        ctx: Context<CreateCommittee>,
+        id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        committee::create_committee(ctx, id, members, threshold)
+    }
+
+    pub fn update_committee(
+        ctx: Context<UpdateCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        committee::update_committee(ctx, members, threshold)
+    }
+
     // ============ Airdrop ============
-    pub fn airdrop_once(ctx: Context<AirdropOnce>) -> Result<()> {
-        airdrop::airdrop_once(ctx)
+    pub fn airdrop(ctx: Context<Airdrop>) -> Result<()> {
+        airdrop::airdrop(ctx)
+    }
+
+    pub fn init_faucet(ctx: Context<InitFaucet>, amount: u64, cooldown_secs: i64) -> Result<()> {
+        airdrop::init_faucet(ctx, amount, cooldown_secs)
+    }
+
+    pub fn update_faucet(
+        ctx: Context<UpdateFaucet>,
+        amount: Option<u64>,
+        cooldown_secs: Option<i64>,
+    ) -> Result<()> {
+        airdrop::update_faucet(ctx, amount, cooldown_secs)
     }
 
     pub fn set_metadata(ctx: Context<SetMetadata>, uri: String) -> Result<()> {
@@ -60,32 +97,108 @@ pub mod prediction_market {
         bound_hi_usd_6: i64,
         end_ts: i64,
         feed_id: [u8; 32],
+        limits: types::StakeLimits,
+        rules: types::ResolutionRules,
     ) -> Result<()> {
-        market_create::create_market(ctx, market_type, comparator, bound_lo_usd_6, bound_hi_usd_6, end_ts, feed_id)
+        market_create::create_market(
+            ctx, market_type, comparator, bound_lo_usd_6, bound_hi_usd_6, end_ts, feed_id, limits, rules,
+        )
     }
 
     pub fn create_market_multi(ctx: Context<CreateMarketMulti>, p: CreateMarketMultiParams) -> Result<()> {
         market_create::create_market_multi(ctx, p)
     }
 
+    pub fn create_market_buckets(ctx: Context<CreateMarketBuckets>, p: CreateMarketBucketsParams) -> Result<()> {
+        market_create::create_market_buckets(ctx, p)
+    }
+
+    pub fn create_market_switchboard(
+        ctx: Context<CreateMarketSwitchboard>,
+        p: CreateMarketSwitchboardParams,
+    ) -> Result<()> {
+        market_create::create_market_switchboard(ctx, p)
+    }
+
+    pub fn create_market_ladder<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateMarketLadder<'info>>,
+        p: CreateMarketLadderParams,
+    ) -> Result<()> {
+        market_create::create_market_ladder(ctx, p)
+    }
+
+    // ============ Series ============
+    pub fn create_series(ctx: Context<CreateSeries>, p: CreateSeriesParams) -> Result<()> {
+        series::create_series(ctx, p)
+    }
+
+    pub fn set_series_active(ctx: Context<UpdateSeries>, active: bool) -> Result<()> {
+        series::set_series_active(ctx, active)
+    }
+
+    pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+        series::roll_series(ctx)
+    }
+
     // ============ Betting ============
-    pub fn place_bet(ctx: Context<PlaceBet>, side: types::Side, amount: u64) -> Result<()> {
-        betting::place_bet(ctx, side, amount)
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        side: types::Side,
+        amount: u64,
+        min_shares_out: u64,
+    ) -> Result<()> {
+        betting::place_bet(ctx, side, amount, min_shares_out)
+    }
+
+    pub fn seed_cpmm(ctx: Context<SeedCpmm>, initial_liquidity: u64) -> Result<()> {
+        betting::seed_cpmm(ctx, initial_liquidity)
     }
 
     pub fn place_bet_multi(ctx: Context<PlaceBetMulti>, outcome_idx: u8, amount: u64) -> Result<()> {
         betting::place_bet_multi(ctx, outcome_idx, amount)
     }
 
+    pub fn exit_position(ctx: Context<ExitPosition>, side: types::Side, amount: u64) -> Result<()> {
+        betting::exit_position(ctx, side, amount)
+    }
+
+    pub fn exit_position_multi(ctx: Context<ExitPositionMulti>, outcome_idx: u8, amount: u64) -> Result<()> {
+        betting::exit_position_multi(ctx, outcome_idx, amount)
+    }
+
+    // ============ Liquidity ============
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+        liquidity::add_liquidity(ctx, amount)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64) -> Result<()> {
+        liquidity::remove_liquidity(ctx, shares)
+    }
+
+    pub fn claim_lp(ctx: Context<ClaimLp>) -> Result<()> {
+        liquidity::claim_lp(ctx)
+    }
+
     // ============ Resolution ============
     pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
         resolve_pyth::resolve_market(ctx)
     }
 
+    pub fn resolve_market_buckets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveMarketBuckets<'info>>,
+    ) -> Result<()> {
+        resolve_pyth::resolve_market_buckets(ctx)
+    }
+
+    pub fn resolve_market_switchboard(ctx: Context<ResolveMarketSwitchboard>) -> Result<()> {
+        resolve_switchboard::resolve_market_switchboard(ctx)
+    }
+
     pub fn resolve_ai_propose<'info>(
         ctx: Context<'_, '_, 'info, 'info, ResolveAiPropose<'info>>,
+        nonce: u64,
     ) -> Result<()> {
-        resolve_ai::propose(ctx)
+        resolve_ai::propose(ctx, nonce)
     }
 
     pub fn finalize_ai<'info>(
@@ -94,6 +207,27 @@ pub mod prediction_market {
         resolve_ai::finalize(ctx)
     }
 
+    pub fn void_ai_market(ctx: Context<VoidAiMarket>) -> Result<()> {
+        resolve_ai::void_ai_market(ctx)
+    }
+
+    pub fn dispute_ai(ctx: Context<DisputeAi>) -> Result<()> {
+        dispute::dispute_ai(ctx)
+    }
+
+    pub fn rule_dispute(
+        ctx: Context<RuleDispute>,
+        overturn: bool,
+        outcome_idx: u8,
+        winners_mask: u8,
+    ) -> Result<()> {
+        dispute::rule_dispute(ctx, overturn, outcome_idx, winners_mask)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        dispute::expire_dispute(ctx)
+    }
+
     // ============ Claims ============
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
         claims::claim(ctx)
@@ -102,4 +236,49 @@ pub mod prediction_market {
     pub fn claim_multi(ctx: Context<ClaimMulti>, outcome_idx: u8) -> Result<()> {
         claims::claim_multi(ctx, outcome_idx)
     }
+
+    pub fn refund_multi(ctx: Context<ClaimMulti>, outcome_idx: u8) -> Result<()> {
+        claims::refund_multi(ctx, outcome_idx)
+    }
+
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+        claims::claim_many(ctx)
+    }
+
+    pub fn crank_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankPayout<'info>>,
+        outcome_idx: u8,
+    ) -> Result<()> {
+        claims::crank_payout(ctx, outcome_idx)
+    }
+
+    pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+        referral::claim_referral(ctx)
+    }
+
+    // ============ Rent Reclamation ============
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        close::close_position(ctx)
+    }
+
+    pub fn close_position_multi(ctx: Context<ClosePositionMulti>, outcome_idx: u8) -> Result<()> {
+        close::close_position_multi(ctx, outcome_idx)
+    }
+
+    pub fn close_market<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseMarket<'info>>,
+    ) -> Result<()> {
+        close::close_market(ctx)
+    }
+
+    // ============ Migration ============
+    pub fn migrate_market<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateMarket<'info>>,
+    ) -> Result<()> {
+        migrate::migrate_market(ctx)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        migrate::migrate_config(ctx)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_COMMITTEE_MEMBERS;
+
+/// AI oracle committee: a proposal needs `threshold` distinct member signatures
+#[account]
+pub struct OracleCommittee {
+    pub id: u64,
+    pub members: [Pubkey; MAX_COMMITTEE_MEMBERS],
+    pub members_len: u8,
+    pub threshold: u8,
+}
+
+impl OracleCommittee {
+    pub const SPACE: usize = 8 + 8 + (32 * MAX_COMMITTEE_MEMBERS) + 1 + 1;
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.members[..self.members_len as usize]
+    }
+}
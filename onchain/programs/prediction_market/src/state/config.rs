@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    CONFIG_VERSION, MAX_COLLATERAL_MINTS, TIP_CURVE_FLAT, USDC_MINT,
+};
 
 #[account]
 pub struct Config {
@@ -8,8 +11,122 @@ pub struct Config {
     pub resolver_bps: u16,
     pub creator_bps: u16,
     pub resolver_tip_cap: u64,
+
+    // AI dispute parameters
+    pub dispute_window_secs: i64,
+    pub dispute_bond: u64,
+    pub dispute_reward_bps: u16,
+    pub dispute_authority: Pubkey,
+
+    // Early exit
+    pub exit_fee_bps: u16,
+    pub exit_lock_secs: i64,
+
+    // Collateral mints markets may be created with
+    pub allowed_mints: [Pubkey; MAX_COLLATERAL_MINTS],
+    pub allowed_mints_len: u8,
+
+    // Two-step admin handover and emergency stop
+    pub pending_admin: Pubkey,
+    pub paused: u8, // PAUSE_* bits
+
+    // Ceilings for creator stake limits; 0 = uncapped
+    pub max_bet_cap: u64,
+    pub max_per_wallet_cap: u64,
+
+    // Resolver tip curve snapshotted into markets (TIP_CURVE_*)
+    pub tip_curve: u8,
+
+    // Payout crank: cut of each pushed payout (cap 0 = uncapped), and wait after end_ts
+    pub crank_fee_bps: u16,
+    pub crank_fee_cap: u64,
+    pub crank_delay_secs: i64,
+
+    // Cut of referred volume paid to referrers at settlement
+    pub referral_bps: u16,
+
+    // Share of the protocol fee paid to liquidity providers at settlement
+    pub lp_fee_share_bps: u16,
+
+    // Layout version (CONFIG_VERSION); 0 = created before versioning, see migrate_config
+    pub version: u8,
 }
 
 impl Config {
-    pub const SPACE: usize = 8 + 32 + 32 + 2 + 2 + 2 + 8;
+    /// Offset of `version` in the account data; fields added from now on go after it
+    pub const VERSION_OFFSET: usize = 8 + 32 + 32 + 2 + 2 + 2 + 8
+        + 8 + 8 + 2 + 32
+        + 2 + 8
+        + (32 * MAX_COLLATERAL_MINTS) + 1
+        + 32 + 1
+        + 8 + 8
+        + 1
+        + 2 + 8 + 8
+        + 2
+        + 2;
+
+    pub const SPACE: usize = Self::VERSION_OFFSET
+        + 1
+        + 64; // padding
+
+    /// Layout version of raw config data; 0 for the pre-versioning layout
+    pub fn data_version(data: &[u8]) -> u8 {
+        data.get(Self::VERSION_OFFSET).copied().unwrap_or(0)
+    }
+
+    /// Decode config data written under any layout version, with legacy defaults applied
+    pub fn decode_any_version(data: &[u8]) -> Result<Self> {
+        let mut buf = data.to_vec();
+        if buf.len() < Self::SPACE {
+            buf.resize(Self::SPACE, 0);
+        }
+        let mut cfg = Self::try_deserialize(&mut &buf[..])?;
+        if cfg.version < CONFIG_VERSION {
+            cfg.apply_legacy_defaults();
+        }
+        Ok(cfg)
+    }
+
+    /// Version 0 ends at `resolver_tip_cap`; everything after it is set here explicitly.
+    /// The admin can change any of it with update_config once migrated.
+    pub fn apply_legacy_defaults(&mut self) {
+        if self.version == 0 {
+            // Disputes are off until the admin configures a window and bond
+            self.dispute_window_secs = 0;
+            self.dispute_bond = 0;
+            self.dispute_reward_bps = 0;
+            self.dispute_authority = self.admin;
+
+            self.exit_fee_bps = 0;
+            self.exit_lock_secs = 0;
+
+            // The faucet mint was the only collateral before the allowlist
+            self.allowed_mints = [Pubkey::default(); MAX_COLLATERAL_MINTS];
+            self.allowed_mints[0] = USDC_MINT;
+            self.allowed_mints_len = 1;
+
+            self.pending_admin = Pubkey::default();
+            self.paused = 0;
+            self.max_bet_cap = 0;
+            self.max_per_wallet_cap = 0;
+            self.tip_curve = TIP_CURVE_FLAT;
+            self.crank_fee_bps = 0;
+            self.crank_fee_cap = 0;
+            self.crank_delay_secs = 0;
+            self.referral_bps = 0;
+            self.lp_fee_share_bps = 0;
+        }
+    }
+
+    pub fn allowed_mints(&self) -> &[Pubkey] {
+        &self.allowed_mints[..self.allowed_mints_len as usize]
+    }
+
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints().contains(mint)
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Devnet faucet settings; a wallet can draw `amount` again once `cooldown_secs` has passed
+#[account]
+pub struct FaucetConfig {
+    pub amount: u64,        // 0 = faucet disabled
+    pub cooldown_secs: i64,
+}
+
+impl FaucetConfig {
+    pub const SPACE: usize = 8 + 8 + 8;
+}
@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
-use crate::constants::MAX_OUTCOMES;
+use crate::{
+    constants::{
+        BPS_DENOM, DISPUTE_NONE, MARKET_VERSION, MAX_METADATA_URI_LEN, MAX_OUTCOMES,
+        TIP_CURVE_FLAT, USDC_DECIMALS, USDC_MINT,
+    },
+    errors::ErrorCode,
+    types::{MarketType, OracleKind, PricingKind, ResolutionRules, StakeLimits},
+};
 
 #[account]
 pub struct Market {
@@ -17,7 +24,7 @@ pub struct Market {
     pub resolver_tip_cap_snapshot: u64,
     pub treasury_wallet_snapshot: Pubkey,
 
-    // Pyth oracle fields
+    // Price oracle fields; feed_id is the Pyth feed id or the Switchboard feed account
     pub feed_id: [u8; 32],
     pub market_type: u8,
     pub comparator: u8,
@@ -36,15 +43,304 @@ pub struct Market {
     // Legacy binary (Pyth) fields
     pub yes_total: u64,
     pub no_total: u64,
-    pub winning_side: u8,       // 0=undef, 1=YES, 2=NO, 3=VOID
+    pub winning_side: u8,       // 0=undef, 1=YES, 2=NO, 3=VOID, 4=SCALAR
+
+    // AI dispute fields
+    pub proposed_at: i64,
+    pub dispute_window_secs: i64,
+    pub dispute_bond_snapshot: u64,
+    pub dispute_reward_bps_snapshot: u16,
+    pub dispute_status: u8,     // 0=none, 1=open, 2=overturned, 3=confirmed, 4=expired
+    pub disputer: Pubkey,
+
+    // Early exit
+    pub exit_fee_bps_snapshot: u16,
+    pub exit_lock_ts: i64,
+    pub exit_fee_pool: u64,     // exit fees left in the vaults for remaining bettors
+
+    // Pricing (Pyth binary)
+    pub pricing_kind: u8,       // 0=parimutuel, 1=CPMM
+    pub pool_yes: u64,          // CPMM curve reserves, in shares
+    pub pool_no: u64,
+    pub yes_shares: u64,        // CPMM shares held by bettors
+    pub no_shares: u64,
+    pub lp_residual: u64,       // CPMM collateral left to the creator after settlement
+
+    // Pyth bucket markets: ordered boundaries, outcome i covers [bounds[i-1], bounds[i])
+    pub bucket_bounds: [i64; MAX_OUTCOMES - 1],
+
+    // Rent reclamation: close_market needs every position closed first
+    pub open_positions: u32,
+
+    // Collateral token, checked against the config allowlist at creation
+    pub mint: Pubkey,
+    pub mint_decimals: u8,
+
+    // AI oracle committee; default = ai_oracle_authority signs alone
+    pub oracle_committee: Pubkey,
+
+    // Stake limits in base units; 0 = no limit
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_per_wallet: u64,
+
+    // Resolver tip curve (TIP_CURVE_*); the cap is resolver_tip_cap_snapshot
+    pub tip_curve_snapshot: u8,
+
+    // Pyth resolution rules and the settlement price's confidence and publish time
+    pub max_conf_bps: u16,
+    pub void_on_wide_conf: bool,
+    pub use_ema: bool,
+    pub resolved_conf_1e6: u64,
+    pub resolved_publish_time: i64,
+
+    // Referrals: stake placed through a referrer, and the slice set aside at settlement
+    pub referral_bps_snapshot: u16,
+    pub referred_volume: u64,
+    pub referral_pool: u64,
+
+    // AI markets: sha256 of the canonical question spec the oracle attests against,
+    // and an optional URI serving that spec; zero/empty for price markets
+    pub question_hash: [u8; 32],
+    pub metadata_uri: [u8; MAX_METADATA_URI_LEN],
+    pub metadata_uri_len: u8,
+
+    // Scalar markets: LONG (yes vault) share of the payout in bps, and each side's pool
+    pub scalar_long_bps: u16,
+    pub scalar_long_pool: u64,
+    pub scalar_short_pool: u64,
+
+    // Two-sided liquidity: LP stake on each side (included in yes_total/no_total), LP shares
+    // outstanding, and the protocol fee slice set aside for LPs at settlement
+    pub lp_fee_share_bps_snapshot: u16,
+    pub lp_yes: u64,
+    pub lp_no: u64,
+    pub lp_shares: u64,
+    pub lp_fee_pool: u64,
+
+    // Layout version (MARKET_VERSION); 0 = created before versioning, see migrate_market
+    pub version: u8,
+
+    // Split-winner AI markets: each winning outcome's vault balance once finalize has moved
+    // the losers' funds in and taken fees. Claims share this, not the shrinking live balance.
+    pub payout_per_outcome: [u64; MAX_OUTCOMES],
+
+    // Who paid rent for the market and its vaults; close_market refunds them
+    pub rent_payer: Pubkey,
+
+    // Migrated from before open_positions was counted: positions opened back then were never
+    // counted, so the zero in open_positions proves nothing and close_market is refused
+    pub positions_untracked: bool,
 }
 
 impl Market {
-    pub const SPACE: usize = 8
+    /// Offset of `version` in the account data: every field before it is fixed-size.
+    /// Fields added from now on go after `version` so this stays put.
+    pub const VERSION_OFFSET: usize = 8
         + 32 + 1 + 32 + 8 + 1
         + 2 + 2 + 2 + 8 + 32
         + 32 + 1 + 1 + 8 + 8 + 8
         + 1 + (8 * MAX_OUTCOMES) + 1 + 8 + 1 + 1
         + 8 + 8 + 1
+        + 8 + 8 + 8 + 2 + 1 + 32
+        + 2 + 8 + 8
+        + 1 + 8 + 8 + 8 + 8 + 8
+        + (8 * (MAX_OUTCOMES - 1))
+        + 4
+        + 32 + 1
+        + 32
+        + 8 + 8 + 8
+        + 1
+        + 2 + 1 + 1 + 8 + 8
+        + 2 + 8 + 8
+        + 32 + MAX_METADATA_URI_LEN + 1
+        + 2 + 8 + 8
+        + 2 + 8 + 8 + 8 + 8;
+
+    pub const SPACE: usize = Self::VERSION_OFFSET
+        + 1
+        + (8 * MAX_OUTCOMES)
+        + 32
+        + 1
         + 32; // padding
+
+    /// Layout version of raw market data. Older layouts are prefixes of the current one, so
+    /// the byte is either old padding or past the end of the account: both read as 0.
+    pub fn data_version(data: &[u8]) -> u8 {
+        data.get(Self::VERSION_OFFSET).copied().unwrap_or(0)
+    }
+
+    /// Decode market data written under any layout version. Fields appended after the
+    /// account was created read as zero, then get their legacy defaults.
+    pub fn decode_any_version(data: &[u8]) -> Result<Self> {
+        let mut buf = data.to_vec();
+        if buf.len() < Self::SPACE {
+            buf.resize(Self::SPACE, 0);
+        }
+        let mut m = Self::try_deserialize(&mut &buf[..])?;
+        if m.version < MARKET_VERSION {
+            m.apply_legacy_defaults();
+        }
+        Ok(m)
+    }
+
+    /// Defaults for fields older layouts lacked. Version 0 is the layout from before
+    /// versioning, which ends at `winning_side`: every field after it is set here explicitly
+    /// rather than left to whatever its zero happens to mean.
+    pub fn apply_legacy_defaults(&mut self) {
+        if self.version == 0 {
+            // No challenge window: legacy AI markets finalize on the attestation, as they did
+            self.proposed_at = 0;
+            self.dispute_window_secs = 0;
+            self.dispute_bond_snapshot = 0;
+            self.dispute_reward_bps_snapshot = 0;
+            self.dispute_status = DISPUTE_NONE;
+            self.disputer = Pubkey::default();
+
+            // No early exit or liquidity: bettors never agreed to an exit fee
+            self.exit_fee_bps_snapshot = 0;
+            self.exit_lock_ts = 0;
+            self.exit_fee_pool = 0;
+
+            self.pricing_kind = PricingKind::Parimutuel as u8;
+            self.pool_yes = 0;
+            self.pool_no = 0;
+            self.yes_shares = 0;
+            self.no_shares = 0;
+            self.lp_residual = 0;
+            self.bucket_bounds = [0; MAX_OUTCOMES - 1];
+
+            // Markets from before per-market collateral all used the faucet mint
+            if self.mint == Pubkey::default() {
+                self.mint = USDC_MINT;
+                self.mint_decimals = USDC_DECIMALS;
+            }
+            self.oracle_committee = Pubkey::default();
+            self.min_bet = 0;
+            self.max_bet = 0;
+            self.max_per_wallet = 0;
+            self.tip_curve_snapshot = TIP_CURVE_FLAT;
+
+            // Settle on the spot price with no confidence check, as before resolution rules
+            self.max_conf_bps = 0;
+            self.void_on_wide_conf = false;
+            self.use_ema = false;
+            self.resolved_conf_1e6 = 0;
+            self.resolved_publish_time = 0;
+
+            self.referral_bps_snapshot = 0;
+            self.referred_volume = 0;
+            self.referral_pool = 0;
+            self.question_hash = [0u8; 32];
+            self.metadata_uri = [0u8; MAX_METADATA_URI_LEN];
+            self.metadata_uri_len = 0;
+            self.scalar_long_bps = 0;
+            self.scalar_long_pool = 0;
+            self.scalar_short_pool = 0;
+            self.lp_fee_share_bps_snapshot = 0;
+            self.lp_yes = 0;
+            self.lp_no = 0;
+            self.lp_shares = 0;
+            self.lp_fee_pool = 0;
+
+            // Split-winner claims fall back to the live vault balance until finalize snapshots
+            self.payout_per_outcome = [0; MAX_OUTCOMES];
+            self.rent_payer = self.authority;
+
+            // Positions opened before counting started are unknown, so the market never closes
+            self.open_positions = 0;
+            self.positions_untracked = true;
+        }
+        // Versions 1 and 2 counted positions, so positions_untracked reading false is right
+    }
+
+    /// Where close_market sends the rent; markets from before `rent_payer` were paid by
+    /// their authority
+    pub fn rent_refund(&self) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            self.authority
+        } else {
+            self.rent_payer
+        }
+    }
+
+    pub fn metadata_uri(&self) -> &[u8] {
+        &self.metadata_uri[..self.metadata_uri_len as usize]
+    }
+
+    /// Key AI attestations are bound to: the committee if set, else the single oracle
+    pub fn attester(&self) -> Pubkey {
+        if self.oracle_committee != Pubkey::default() {
+            self.oracle_committee
+        } else {
+            self.ai_oracle_authority
+        }
+    }
+
+    pub fn apply_resolution_rules(&mut self, r: ResolutionRules) -> Result<()> {
+        require!((r.max_conf_bps as u64) <= BPS_DENOM, ErrorCode::BadBps);
+        self.max_conf_bps = r.max_conf_bps;
+        self.void_on_wide_conf = r.void_on_wide_conf;
+        self.use_ema = r.use_ema;
+        self.resolved_conf_1e6 = 0;
+        self.resolved_publish_time = 0;
+        Ok(())
+    }
+
+    pub fn stake_limits(&self) -> StakeLimits {
+        StakeLimits {
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+            max_per_wallet: self.max_per_wallet,
+        }
+    }
+
+    /// Check one bet of `amount` that brings the position to `position_total`
+    pub fn check_stake_limits(&self, amount: u64, position_total: u64) -> Result<()> {
+        require!(amount >= self.min_bet, ErrorCode::BetTooSmall);
+        require!(self.max_bet == 0 || amount <= self.max_bet, ErrorCode::BetTooLarge);
+        require!(
+            self.max_per_wallet == 0 || position_total <= self.max_per_wallet,
+            ErrorCode::WalletCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Stakes live in per-outcome vaults (AI markets and Pyth bucket markets)
+    pub fn uses_outcome_vaults(&self) -> bool {
+        self.oracle_kind == OracleKind::Ai as u8
+            || (self.oracle_kind == OracleKind::Pyth as u8
+                && self.market_type == MarketType::PriceBuckets as u8)
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        self.market_type == MarketType::PriceScalar as u8
+    }
+
+    /// LONG share of the payout, linear in the price between the bounds and clamped at the ends
+    pub fn scalar_long_bps_at(&self, price_1e6: i64) -> Result<u16> {
+        require!(self.bound_lo < self.bound_hi, ErrorCode::BadScalarBounds);
+        let price = price_1e6.clamp(self.bound_lo, self.bound_hi);
+        let num = (price as i128 - self.bound_lo as i128) as u128;
+        let den = (self.bound_hi as i128 - self.bound_lo as i128) as u128;
+        let bps = num
+            .checked_mul(BPS_DENOM as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / den;
+        Ok(bps as u16)
+    }
+
+    /// Parimutuel yes/no price market, the only kind LPs can provide to
+    pub fn accepts_liquidity(&self) -> bool {
+        self.is_price_binary()
+            && !self.is_scalar()
+            && self.pricing_kind == PricingKind::Parimutuel as u8
+    }
+
+    /// Binary yes/no market settled from a price feed (Pyth or Switchboard)
+    pub fn is_price_binary(&self) -> bool {
+        (self.oracle_kind == OracleKind::Pyth as u8
+            || self.oracle_kind == OracleKind::Switchboard as u8)
+            && !self.uses_outcome_vaults()
+    }
 }
\ No newline at end of file
@@ -1,7 +1,17 @@
+pub mod committee;
 pub mod config;
+pub mod faucet;
 pub mod market;
+pub mod nonce;
 pub mod position;
+pub mod referral;
+pub mod series;
 
+pub use committee::*;
 pub use config::*;
+pub use faucet::*;
 pub use market::*;
-pub use position::*;
\ No newline at end of file
+pub use nonce::*;
+pub use position::*;
+pub use referral::*;
+pub use series::*;
\ No newline at end of file
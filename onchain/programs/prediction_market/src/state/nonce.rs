@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Consumed attestation nonce; the PDA existing is what blocks a replay
+#[account]
+pub struct UsedNonce {
+    pub market: Pubkey,
+    pub used_at: i64,
+}
+
+impl UsedNonce {
+    pub const SPACE: usize = 8 + 32 + 8;
+}
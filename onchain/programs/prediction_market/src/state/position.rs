@@ -7,10 +7,16 @@ pub struct PositionBin {
     pub yes_bet: u64,
     pub no_bet: u64,
     pub claimed: bool,
+    pub yes_shares: u64,    // CPMM only
+    pub no_shares: u64,
+    pub referrer: Pubkey,   // default = not referred
+    pub version: u8,        // POSITION_VERSION; 0 = created before versioning
 }
 
 impl PositionBin {
-    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+    // Position fields added from now on go after `version`
+    pub const VERSION_OFFSET: usize = 8 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 32;
+    pub const SPACE: usize = Self::VERSION_OFFSET + 1;
 }
 
 #[account]
@@ -20,18 +26,41 @@ pub struct PositionMulti {
     pub outcome_idx: u8,
     pub stake: u64,
     pub claimed: bool,
+    pub referrer: Pubkey,   // default = not referred
+    pub version: u8,
 }
 
 impl PositionMulti {
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1;
+    pub const VERSION_OFFSET: usize = 8 + 32 + 32 + 1 + 8 + 1 + 32;
+    /// Offset of `stake`, the same in every layout version
+    pub const STAKE_OFFSET: usize = 8 + 32 + 32 + 1;
+    pub const SPACE: usize = Self::VERSION_OFFSET + 1;
 }
 
+/// Two-sided liquidity in a binary market; shares are minted 1:1 with the deposit
+#[account]
+pub struct LpPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub yes_amount: u64,
+    pub no_amount: u64,
+    pub shares: u64,
+    pub version: u8,
+}
+
+impl LpPosition {
+    pub const VERSION_OFFSET: usize = 8 + 32 + 32 + 8 + 8 + 8;
+    pub const SPACE: usize = Self::VERSION_OFFSET + 1;
+}
+
+/// Per-wallet faucet record: last draw time and everything minted to the wallet so far
 #[account]
 pub struct AirdropClaim {
-    pub claimed: bool,
-    pub when: i64,
+    pub claimed: bool,  // drawn at least once
+    pub when: i64,      // last draw
+    pub total: u64,
 }
 
 impl AirdropClaim {
-    pub const SIZE: usize = 1 + 8;
+    pub const SIZE: usize = 1 + 8 + 8;
 }
\ No newline at end of file
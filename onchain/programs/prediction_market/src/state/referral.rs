@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Volume a referrer brought into one market; their cut of `referral_pool` is pro rata
+#[account]
+pub struct ReferralStake {
+    pub market: Pubkey,
+    pub referrer: Pubkey,
+    pub volume: u64,
+    pub claimed: bool,
+}
+
+impl ReferralStake {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::types::{ResolutionRules, StakeLimits};
+
+/// Recurring Pyth threshold market, e.g. "BTC above X at the top of every hour".
+/// `roll_series` opens round `next_round` once `current_end_ts` has passed.
+#[account]
+pub struct Series {
+    pub id: u64,
+    pub authority: Pubkey,      // authority of every market the series opens
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub comparator: u8,
+    pub cadence_secs: i64,      // rounds end on multiples of this
+    pub strike_rule: u8,        // STRIKE_*
+    pub strike_step: i64,       // USD 1e6
+    pub limits: StakeLimits,
+    pub rules: ResolutionRules,
+    pub active: bool,
+
+    pub next_round: u64,
+    pub current_market: Pubkey,
+    pub current_end_ts: i64,
+}
+
+impl Series {
+    pub const SPACE: usize = 8
+        + 8 + 32 + 32 + 32 + 1
+        + 8 + 1 + 8
+        + (8 + 8 + 8) + (2 + 1 + 1)
+        + 1
+        + 8 + 32 + 8;
+}
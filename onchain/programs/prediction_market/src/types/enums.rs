@@ -4,12 +4,21 @@ use anchor_lang::prelude::*;
 pub enum OracleKind {
     Pyth = 0,
     Ai = 1,
+    Switchboard = 2,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PricingKind {
+    Parimutuel = 0,
+    Cpmm = 1,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum MarketType {
     PriceThreshold,
     PriceRange,
+    PriceBuckets,
+    PriceScalar,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
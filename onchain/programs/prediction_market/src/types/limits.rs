@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Creator-chosen stake limits in collateral base units; 0 leaves a limit off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakeLimits {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_per_wallet: u64,    // yes_bet + no_bet on binary, stake summed over outcomes on multi
+}
@@ -1,3 +1,7 @@
 pub mod enums;
+pub mod limits;
+pub mod rules;
 
-pub use enums::*;
\ No newline at end of file
+pub use enums::*;
+pub use limits::*;
+pub use rules::*;
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Pyth settlement rules chosen at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ResolutionRules {
+    pub max_conf_bps: u16,          // max conf / price; 0 = not checked
+    pub void_on_wide_conf: bool,    // void instead of rejecting the price update
+    pub use_ema: bool,              // settle on the EMA price instead of spot
+}
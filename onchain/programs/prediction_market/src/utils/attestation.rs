@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::{
+    ed25519_program, program_error::ProgramError, sysvar::instructions as sysvar_instructions,
+};
 
-use crate::{constants::DOMAIN, errors::ErrorCode};
+use crate::{
+    constants::{DOMAIN, OUTCOME_NONE},
+    errors::ErrorCode,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AttestationSingle {
@@ -11,6 +16,7 @@ pub struct AttestationSingle {
     pub attest_ts: i64,
     pub nonce: u64,
     pub program: Pubkey,
+    pub question_hash: [u8; 32],
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -21,6 +27,18 @@ pub struct AttestationMultiWinners {
     pub attest_ts: i64,
     pub nonce: u64,
     pub program: Pubkey,
+    pub question_hash: [u8; 32],
+}
+
+/// Void attestation: single-winner layout with outcome byte set to OUTCOME_NONE
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AttestationVoid {
+    pub market: Pubkey,
+    pub end_ts: i64,
+    pub attest_ts: i64,
+    pub nonce: u64,
+    pub program: Pubkey,
+    pub question_hash: [u8; 32],
 }
 
 pub enum ParsedMode {
@@ -31,6 +49,7 @@ pub enum ParsedMode {
         nonce: u64,
         program: Pubkey,
         market: Pubkey,
+        question_hash: [u8; 32],
     },
     Multi {
         winners: Vec<u8>,
@@ -39,11 +58,30 @@ pub enum ParsedMode {
         nonce: u64,
         program: Pubkey,
         market: Pubkey,
+        question_hash: [u8; 32],
+    },
+    Void {
+        end_ts: i64,
+        attest_ts: i64,
+        nonce: u64,
+        program: Pubkey,
+        market: Pubkey,
+        question_hash: [u8; 32],
     },
 }
 
+impl ParsedMode {
+    pub fn nonce(&self) -> u64 {
+        match self {
+            ParsedMode::Single { nonce, .. }
+            | ParsedMode::Multi { nonce, .. }
+            | ParsedMode::Void { nonce, .. } => *nonce,
+        }
+    }
+}
+
 pub fn build_message_single(a: &AttestationSingle) -> Vec<u8> {
-    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32);
+    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32 + 32);
     v.extend_from_slice(DOMAIN);
     v.extend_from_slice(a.market.as_ref());
     v.push(a.outcome_idx);
@@ -51,15 +89,28 @@ pub fn build_message_single(a: &AttestationSingle) -> Vec<u8> {
     v.extend_from_slice(&a.attest_ts.to_le_bytes());
     v.extend_from_slice(&a.nonce.to_le_bytes());
     v.extend_from_slice(a.program.as_ref());
+    v.extend_from_slice(&a.question_hash);
     v
 }
 
+pub fn build_message_void(a: &AttestationVoid) -> Vec<u8> {
+    build_message_single(&AttestationSingle {
+        market: a.market,
+        outcome_idx: OUTCOME_NONE,
+        end_ts: a.end_ts,
+        attest_ts: a.attest_ts,
+        nonce: a.nonce,
+        program: a.program,
+        question_hash: a.question_hash,
+    })
+}
+
 pub fn build_message_multi(a: &AttestationMultiWinners) -> Vec<u8> {
     let mut winners = a.winners.clone();
     winners.sort_unstable();
     winners.dedup();
     
-    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + winners.len() + 8 + 8 + 8 + 32);
+    let mut v = Vec::with_capacity(DOMAIN.len() + 32 + 1 + winners.len() + 8 + 8 + 8 + 32 + 32);
     v.extend_from_slice(DOMAIN);
     v.extend_from_slice(a.market.as_ref());
     v.push(winners.len() as u8);
@@ -68,13 +119,14 @@ pub fn build_message_multi(a: &AttestationMultiWinners) -> Vec<u8> {
     v.extend_from_slice(&a.attest_ts.to_le_bytes());
     v.extend_from_slice(&a.nonce.to_le_bytes());
     v.extend_from_slice(a.program.as_ref());
+    v.extend_from_slice(&a.question_hash);
     v
 }
 
 pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
     let mut i = 0usize;
     require!(
-        msg.len() >= DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32,
+        msg.len() >= DOMAIN.len() + 32 + 1 + 8 + 8 + 8 + 32 + 32,
         ErrorCode::Unauthorized
     );
     require!(&msg[0..DOMAIN.len()] == DOMAIN, ErrorCode::Unauthorized);
@@ -87,7 +139,7 @@ pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
 
     let first = msg[i];
     i += 1;
-    let tail = 8 + 8 + 8 + 32;
+    let tail = 8 + 8 + 8 + 32 + 32;
 
     // Single winner: exactly tail bytes remain
     if msg.len() == i + tail {
@@ -100,7 +152,19 @@ pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
         p.copy_from_slice(&msg[i..i + 32]);
         i += 32;
         let program = Pubkey::new_from_array(p);
+        let question_hash = read_hash(msg, &mut i)?;
         let outcome_idx = first;
+
+        if outcome_idx == OUTCOME_NONE {
+            return Ok(ParsedMode::Void {
+                end_ts,
+                attest_ts,
+                nonce,
+                program,
+                market,
+                question_hash,
+            });
+        }
         
         return Ok(ParsedMode::Single {
             outcome_idx,
@@ -109,6 +173,7 @@ pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
             nonce,
             program,
             market,
+            question_hash,
         });
     }
 
@@ -130,6 +195,7 @@ pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
     p.copy_from_slice(&msg[i..i + 32]);
     i += 32;
     let program = Pubkey::new_from_array(p);
+    let question_hash = read_hash(msg, &mut i)?;
 
     Ok(ParsedMode::Multi {
         winners,
@@ -138,6 +204,7 @@ pub fn parse_attestation_message(msg: &[u8]) -> Result<ParsedMode> {
         nonce,
         program,
         market,
+        question_hash,
     })
 }
 
@@ -159,6 +226,15 @@ fn read_le_u64(input: &[u8], at: &mut usize) -> Result<u64> {
     Ok(u64::from_le_bytes(buf))
 }
 
+#[inline]
+fn read_hash(input: &[u8], at: &mut usize) -> Result<[u8; 32]> {
+    require!(*at + 32 <= input.len(), ErrorCode::Unauthorized);
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&input[*at..*at + 32]);
+    *at += 32;
+    Ok(buf)
+}
+
 /// Parse Ed25519 instruction data
 pub fn parse_ed25519<'a>(data: &'a [u8]) -> core::result::Result<(&'a [u8], &'a [u8]), ProgramError> {
     #[repr(C)]
@@ -212,4 +288,32 @@ pub fn parse_ed25519<'a>(data: &'a [u8]) -> core::result::Result<(&'a [u8], &'a
     let message = &data[msg_off as usize..msg_off as usize + msg_len as usize];
     
     Ok((pubkey, message))
-}
\ No newline at end of file
+}
+
+/// Count distinct `members` that signed exactly `msg` in the ed25519 instructions
+/// preceding `cur_ix`; other signers and payloads are ignored
+pub fn count_attesters(
+    ix_sysvar: &AccountInfo,
+    cur_ix: u16,
+    msg: &[u8],
+    members: &[Pubkey],
+) -> Result<usize> {
+    let mut signed: Vec<Pubkey> = Vec::with_capacity(members.len());
+    for i in 0..cur_ix {
+        let ix = sysvar_instructions::load_instruction_at_checked(i as usize, ix_sysvar)?;
+        if ix.program_id != ed25519_program::id() {
+            continue;
+        }
+        let Ok((pk_bytes, m)) = parse_ed25519(&ix.data) else {
+            continue;
+        };
+        if m != msg {
+            continue;
+        }
+        let pk = Pubkey::try_from(pk_bytes).map_err(|_| error!(ErrorCode::Unauthorized))?;
+        if members.contains(&pk) && !signed.contains(&pk) {
+            signed.push(pk);
+        }
+    }
+    Ok(signed.len())
+}
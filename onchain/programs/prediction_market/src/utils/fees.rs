@@ -1,12 +1,42 @@
 use anchor_lang::prelude::*;
+use crate::constants::{RESOLVE_HORIZON_SECS, TIP_CURVE_LINEAR, TIP_FULL_WINDOW_SECS};
 use crate::errors::ErrorCode;
 use crate::utils::math::mul_div_bps_u128;
 
+/// Resolver tip after the market's cap (0 = uncapped) and lateness curve. With the
+/// linear curve the full tip is paid for TIP_FULL_WINDOW_SECS after `end_ts`, then
+/// falls to zero at the resolve horizon.
+pub fn resolver_tip_with_policy(
+    tip: u128,
+    cap: u64,
+    curve: u8,
+    end_ts: i64,
+    now: i64,
+) -> Result<u128> {
+    let tip = if cap > 0 { tip.min(cap as u128) } else { tip };
+    if curve != TIP_CURVE_LINEAR {
+        return Ok(tip);
+    }
+
+    let span = RESOLVE_HORIZON_SECS - TIP_FULL_WINDOW_SECS;
+    let late = now
+        .saturating_sub(end_ts)
+        .saturating_sub(TIP_FULL_WINDOW_SECS)
+        .clamp(0, span);
+    let scaled = tip
+        .checked_mul((span - late) as u128)
+        .ok_or(error!(ErrorCode::Overflow))?
+        / span as u128;
+    Ok(scaled)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct FeeBreakdown {
     pub protocol_fee: u128,
     pub resolver_tip: u128,
     pub creator_tip: u128,
+    pub disputer_reward: u128,
+    pub referral_fee: u128,
     pub total_deductions: u128,
 }
 
@@ -31,10 +61,45 @@ impl FeeBreakdown {
             protocol_fee,
             resolver_tip,
             creator_tip,
+            disputer_reward: 0,
+            referral_fee: 0,
             total_deductions,
         })
     }
+
+    /// Apply the resolver tip cap and curve; whatever is cut stays in the payout pool
+    pub fn with_resolver_tip_policy(
+        mut self,
+        cap: u64,
+        curve: u8,
+        end_ts: i64,
+        now: i64,
+    ) -> Result<Self> {
+        let tip = resolver_tip_with_policy(self.resolver_tip, cap, curve, end_ts, now)?;
+        self.total_deductions -= self.resolver_tip - tip;
+        self.resolver_tip = tip;
+        Ok(self)
+    }
+
+    /// Carve a successful disputer's reward out of the protocol fee
+    pub fn with_disputer_reward(mut self, pot: u128, reward_bps: u16) -> Result<Self> {
+        let reward = mul_div_bps_u128(pot, reward_bps as u128)?.min(self.protocol_fee);
+        self.protocol_fee -= reward;
+        self.disputer_reward = reward;
+        Ok(self)
+    }
     
+    /// Set aside the referrers' cut of the volume placed through them (capped at the pot)
+    pub fn with_referrals(mut self, pot: u128, referred_volume: u64, referral_bps: u16) -> Result<Self> {
+        let referred = (referred_volume as u128).min(pot);
+        let fee = mul_div_bps_u128(referred, referral_bps as u128)?;
+        self.total_deductions = self.total_deductions
+            .checked_add(fee)
+            .ok_or(error!(ErrorCode::Overflow))?;
+        self.referral_fee = fee;
+        Ok(self)
+    }
+
     pub fn to_u64_parts(&self) -> Result<(u64, u64, u64)> {
         let fee_u64 = self.protocol_fee
             .try_into()
@@ -47,4 +112,16 @@ impl FeeBreakdown {
             .map_err(|_| error!(ErrorCode::Overflow))?;
         Ok((fee_u64, resolver_u64, creator_u64))
     }
+
+    pub fn disputer_reward_u64(&self) -> Result<u64> {
+        self.disputer_reward
+            .try_into()
+            .map_err(|_| error!(ErrorCode::Overflow))
+    }
+
+    pub fn referral_fee_u64(&self) -> Result<u64> {
+        self.referral_fee
+            .try_into()
+            .map_err(|_| error!(ErrorCode::Overflow))
+    }
 }
\ No newline at end of file
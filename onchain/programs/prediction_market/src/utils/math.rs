@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::errors::ErrorCode;
+use crate::{
+    constants::{STRIKE_CEIL, STRIKE_FLOOR, STRIKE_NEAREST},
+    errors::ErrorCode,
+};
 
 /// Multiply and divide for u64: (a * b) / d
 pub fn mul_div_u64(a: u64, b: u64, d: u64) -> Result<u64> {
@@ -15,6 +18,40 @@ pub fn mul_div_bps_u128(x: u128, bps: u128) -> Result<u128> {
     Ok(num / 10_000)
 }
 
+/// CPMM buy: `amount` mints complete sets into the pool, then the opposite
+/// side is swapped back along x*y=k. Returns (shares_out, new_pool_side, new_pool_other).
+pub fn cpmm_buy(pool_side: u64, pool_other: u64, amount: u64) -> Result<(u64, u64, u64)> {
+    require!(pool_side > 0 && pool_other > 0, ErrorCode::EmptyPool);
+
+    let k = (pool_side as u128)
+        .checked_mul(pool_other as u128)
+        .ok_or(error!(ErrorCode::Overflow))?;
+    let other_after = (pool_other as u128)
+        .checked_add(amount as u128)
+        .ok_or(error!(ErrorCode::Overflow))?;
+    // Round up so the pool never gives out more than the curve allows
+    let side_after = k.div_ceil(other_after);
+    let shares_out = (pool_side as u128)
+        .checked_add(amount as u128)
+        .and_then(|v| v.checked_sub(side_after))
+        .ok_or(error!(ErrorCode::Overflow))?;
+
+    Ok((
+        u64::try_from(shares_out).map_err(|_| error!(ErrorCode::Overflow))?,
+        u64::try_from(side_after).map_err(|_| error!(ErrorCode::Overflow))?,
+        u64::try_from(other_after).map_err(|_| error!(ErrorCode::Overflow))?,
+    ))
+}
+
+/// Marginal CPMM price of YES in basis points
+pub fn cpmm_price_yes_bps(pool_yes: u64, pool_no: u64) -> u64 {
+    let sum = pool_yes as u128 + pool_no as u128;
+    if sum == 0 {
+        return 5_000;
+    }
+    (pool_no as u128 * 10_000 / sum) as u64
+}
+
 /// Convert Pyth price to USD with 6 decimals
 pub fn price_to_usd_1e6_from_pyth(price: i64, exponent: i32) -> Result<i64> {
     let mut v = price as i128;
@@ -33,6 +70,33 @@ pub fn price_to_usd_1e6_from_pyth(price: i64, exponent: i32) -> Result<i64> {
     Ok(v as i64)
 }
 
+/// Convert a Switchboard on-demand value (fixed point, 18 decimals) to USD with 6 decimals
+pub fn price_to_usd_1e6_from_switchboard(value: i128) -> Result<i64> {
+    let v = value / 1_000_000_000_000;
+    i64::try_from(v).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// Bucket index for a price given ordered boundaries:
+/// 0 below bounds[0], i for bounds[i-1] <= price < bounds[i], last at or above the top
+pub fn bucket_for_price(bounds: &[i64], price: i64) -> u8 {
+    bounds.iter().take_while(|&&b| price >= b).count() as u8
+}
+
+/// Round a USD 1e6 price to a multiple of `step` per a STRIKE_* rule
+pub fn round_strike(price: i64, step: i64, rule: u8) -> Result<i64> {
+    require!(step > 0, ErrorCode::BadSeries);
+    let floor = price.div_euclid(step);
+    let units = match rule {
+        STRIKE_FLOOR => floor,
+        STRIKE_CEIL if price.rem_euclid(step) == 0 => floor,
+        STRIKE_CEIL => floor + 1,
+        STRIKE_NEAREST if price.rem_euclid(step) * 2 >= step => floor + 1,
+        STRIKE_NEAREST => floor,
+        _ => return Err(error!(ErrorCode::BadSeries)),
+    };
+    units.checked_mul(step).ok_or(error!(ErrorCode::Overflow))
+}
+
 /// Compare two values based on comparator
 /// 0 = GT, 1 = LT, 2 = GTE, 3 = LTE
 pub fn cmp_check(comparator: u8, lhs: i64, rhs: i64) -> Result<bool> {
@@ -2,10 +2,12 @@ pub mod attestation;
 pub mod fees;
 pub mod math;
 pub mod pda;
+pub mod switchboard;
 pub mod transfers;
 
 pub use attestation::*;
 pub use fees::*;
 pub use math::*;
 pub use pda::*;
+pub use switchboard::*;
 pub use transfers::*;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
+
+use crate::{
+    constants::{SWITCHBOARD_ON_DEMAND_DEVNET_PID, SWITCHBOARD_ON_DEMAND_PID},
+    errors::ErrorCode,
+};
+
+/// Latest result of a Switchboard pull feed, in the feed's 18-decimal fixed point
+pub struct SwitchboardReading {
+    pub value: i128,
+    pub std_dev: i128,
+    pub updated_at: i64,
+}
+
+/// Read the current result of a Switchboard on-demand pull feed. Only the account owner
+/// and layout are checked, so a feed account written directly into a local validator works.
+pub fn read_switchboard_feed(feed: &AccountInfo) -> Result<SwitchboardReading> {
+    require!(
+        *feed.owner == SWITCHBOARD_ON_DEMAND_PID || *feed.owner == SWITCHBOARD_ON_DEMAND_DEVNET_PID,
+        ErrorCode::InvalidSwitchboardFeed
+    );
+    let data = feed.try_borrow_data()?;
+    let parsed = PullFeedAccountData::parse(data)
+        .map_err(|_| error!(ErrorCode::InvalidSwitchboardFeed))?;
+    // A feed that never received a sample has an all-zero result
+    require!(parsed.result.num_samples > 0, ErrorCode::InvalidSwitchboardFeed);
+
+    Ok(SwitchboardReading {
+        value: parsed.result.value,
+        std_dev: parsed.result.std_dev,
+        updated_at: parsed.last_update_timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    const FEED_LEN: usize = 8 + std::mem::size_of::<PullFeedAccountData>();
+
+    /// A feed account as the on-demand program lays it out: Anchor discriminator + raw struct.
+    /// The struct holds i128s, so the buffer is u128-backed and the account data starts 8 bytes
+    /// in, putting the struct on a 16-byte boundary as on-chain account data would.
+    fn feed_data(value: i128, num_samples: u8, updated_at: i64) -> Vec<u128> {
+        let mut feed: PullFeedAccountData = bytemuck::Zeroable::zeroed();
+        feed.result.value = value;
+        feed.result.std_dev = 1_000;
+        feed.result.num_samples = num_samples;
+        feed.last_update_timestamp = updated_at;
+
+        let mut buf = vec![0u128; (8 + FEED_LEN).div_ceil(16)];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+        bytes[8..16].copy_from_slice(&hash(b"account:PullFeedAccountData").to_bytes()[..8]);
+        bytes[16..8 + FEED_LEN].copy_from_slice(bytemuck::bytes_of(&feed));
+        buf
+    }
+
+    fn read(owner: Pubkey, mut buf: Vec<u128>) -> Result<SwitchboardReading> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000_000u64;
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+        let data = &mut bytes[8..8 + FEED_LEN];
+        let feed = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+        read_switchboard_feed(&feed)
+    }
+
+    #[test]
+    fn reads_result_from_feed_account() {
+        let value = 64_250_500_000_000_000_000_000i128; // 64_250.5 at 18 decimals
+        let r = read(SWITCHBOARD_ON_DEMAND_PID, feed_data(value, 3, 1_760_000_000)).unwrap();
+        assert_eq!(r.value, value);
+        assert_eq!(r.std_dev, 1_000);
+        assert_eq!(r.updated_at, 1_760_000_000);
+
+        let r = read(SWITCHBOARD_ON_DEMAND_DEVNET_PID, feed_data(value, 1, 1)).unwrap();
+        assert_eq!(r.value, value);
+    }
+
+    #[test]
+    fn rejects_foreign_owner() {
+        let data = feed_data(1, 3, 1);
+        assert!(read(Pubkey::new_unique(), data).is_err());
+    }
+
+    #[test]
+    fn rejects_feed_without_samples() {
+        assert!(read(SWITCHBOARD_ON_DEMAND_PID, feed_data(1, 0, 1)).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_discriminator() {
+        let mut buf = feed_data(1, 3, 1);
+        bytemuck::cast_slice_mut::<u128, u8>(&mut buf)[8] ^= 0xff;
+        assert!(read(SWITCHBOARD_ON_DEMAND_PID, buf).is_err());
+    }
+}
@@ -1,22 +1,26 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use spl_token::state::Account as SplTokenAccount;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use std::collections::BTreeMap;
-use anchor_lang::solana_program::{program_pack::Pack};
 use crate::{
     constants::{ESCROW_SEED, MAX_OUTCOMES, OUTCOME_PREFIX},
     errors::ErrorCode,
     utils::{fees::FeeBreakdown, pda::outcome_signer_seeds},
 };
 
+/// Read a vault passed via remaining accounts (SPL Token or Token-2022 layout)
+pub fn read_token_account(ai: &AccountInfo) -> Result<TokenAccount> {
+    let data = ai.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::WrongMint))
+}
+
 /// Transfer all losing stakes to the winner vault (single winner)
 pub fn transfer_losers_to_winner<'info>(
     remaining_accounts: &[AccountInfo<'info>],
-    token_program: &Program<'info, Token>,
-    win_vault: &Account<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    win_vault: &InterfaceAccount<'info, TokenAccount>,
     market_key: Pubkey,
     program_id: &Pubkey,
-    mint_key: Pubkey,
+    mint: &InterfaceAccount<'info, Mint>,
     num_outcomes: u8,
     win_idx: u8,
 ) -> Result<()> {
@@ -25,9 +29,8 @@ pub fn transfer_losers_to_winner<'info>(
         let lose_auth_ai = pair[0].clone();
         let lose_vault_ai = pair[1].clone();
 
-        let lose_state = SplTokenAccount::unpack(&lose_vault_ai.try_borrow_data()?)
-            .map_err(|_| error!(ErrorCode::WrongMint))?;
-        require_keys_eq!(lose_state.mint, mint_key, ErrorCode::WrongMint);
+        let lose_state = read_token_account(&lose_vault_ai)?;
+        require_keys_eq!(lose_state.mint, mint.key(), ErrorCode::WrongMint);
         require_keys_eq!(lose_state.owner, lose_auth_ai.key(), ErrorCode::Unauthorized);
 
         // Find loser index
@@ -53,17 +56,19 @@ pub fn transfer_losers_to_winner<'info>(
             let bump_arr = [loser_bump];
             let lose_signer = outcome_signer_seeds(&market_key, &idx_arr, &bump_arr);
 
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: lose_vault_ai,
+                        mint: mint.to_account_info(),
                         to: win_vault.to_account_info(),
                         authority: lose_auth_ai,
                     },
                     &[&lose_signer],
                 ),
                 amount,
+                mint.decimals,
             )?;
         }
     }
@@ -93,8 +98,7 @@ pub fn categorize_accounts<'info>(
     let mut losers = Vec::new();
 
     for [auth_ai, vault_ai] in pairs.into_iter() {
-        let st = SplTokenAccount::unpack(&vault_ai.try_borrow_data()?)
-            .map_err(|_| error!(ErrorCode::WrongMint))?;
+        let st = read_token_account(&vault_ai)?;
         require_keys_eq!(st.mint, mint_key, ErrorCode::WrongMint);
         require_keys_eq!(st.owner, auth_ai.key(), ErrorCode::Unauthorized);
 
@@ -128,7 +132,8 @@ pub fn categorize_accounts<'info>(
 pub fn distribute_loser_funds_to_winners<'info>(
     losers: &[(AccountInfo<'info>, AccountInfo<'info>, u8, u8, u64)],
     winners_pairs: &[(u8, u8, AccountInfo<'info>, AccountInfo<'info>, u64)],
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
     market_key: Pubkey,
     tvl: [u64; MAX_OUTCOMES],
     total_winners_stake: u128,
@@ -163,17 +168,19 @@ pub fn distribute_loser_funds_to_winners<'info>(
 
             let share_u64: u64 = share_u128.try_into().unwrap_or(u64::MAX);
             if share_u64 > 0 {
-                token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new_with_signer(
                         token_program.to_account_info(),
-                        Transfer {
+                        TransferChecked {
                             from: lose_vault_ai.to_account_info(),
+                            mint: mint.to_account_info(),
                             to: wvault_ai.to_account_info(),
                             authority: lose_auth_ai.to_account_info(),
                         },
                         &[&lose_signer],
                     ),
                     share_u64,
+                    mint.decimals,
                 )?;
             }
         }
@@ -187,22 +194,28 @@ pub fn distribute_fees_from_winners<'info>(
     winners_pairs: &[(u8, u8, AccountInfo<'info>, AccountInfo<'info>, u64)],
     fees: &FeeBreakdown,
     total_after: u128,
-    token_program: &Program<'info, Token>,
-    resolver_ata: &Account<'info, TokenAccount>,
-    creator_ata: &Account<'info, TokenAccount>,
-    treasury_ata: &Account<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    resolver_ata: &InterfaceAccount<'info, TokenAccount>,
+    creator_ata: &InterfaceAccount<'info, TokenAccount>,
+    treasury_ata: &InterfaceAccount<'info, TokenAccount>,
+    disputer_ata: Option<&InterfaceAccount<'info, TokenAccount>>,
+    referral_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
     market_key: Pubkey,
 ) -> Result<()> {
     let (fee_total_u64, resolver_total_u64, creator_total_u64) = fees.to_u64_parts()?;
+    let reward_total_u64 = fees.disputer_reward_u64()?;
+    let referral_total_u64 = fees.referral_fee_u64()?;
 
     let mut resolver_left = resolver_total_u64;
     let mut creator_left = creator_total_u64;
     let mut fee_left = fee_total_u64;
+    let mut reward_left = reward_total_u64;
+    let mut referral_left = referral_total_u64;
 
     for i in 0..winners_pairs.len() {
         let (idx, bump, wauth_ai, wvault_ai, _) = &winners_pairs[i];
-        let st = SplTokenAccount::unpack(&wvault_ai.try_borrow_data()?)
-            .map_err(|_| error!(ErrorCode::WrongMint))?;
+        let st = read_token_account(wvault_ai)?;
         let cur = st.amount;
 
         if cur == 0 {
@@ -248,55 +261,123 @@ pub fn distribute_fees_from_winners<'info>(
             s_u64
         };
 
+        let share_reward = if is_last {
+            reward_left
+        } else {
+            let s = (reward_total_u64 as u128)
+                .checked_mul(cur as u128)
+                .ok_or(error!(ErrorCode::Overflow))?
+                / total_after;
+            let s_u64: u64 = s.try_into().unwrap_or(u64::MAX);
+            reward_left = reward_left.saturating_sub(s_u64);
+            s_u64
+        };
+
+        let share_referral = if is_last {
+            referral_left
+        } else {
+            let s = (referral_total_u64 as u128)
+                .checked_mul(cur as u128)
+                .ok_or(error!(ErrorCode::Overflow))?
+                / total_after;
+            let s_u64: u64 = s.try_into().unwrap_or(u64::MAX);
+            referral_left = referral_left.saturating_sub(s_u64);
+            s_u64
+        };
+
         let idx_arr = [*idx];
         let bump_arr = [*bump];
         let win_signer = outcome_signer_seeds(&market_key, &idx_arr, &bump_arr);
 
         // Transfer resolver tip
         if share_resolver > 0 {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: wvault_ai.to_account_info(),
+                        mint: mint.to_account_info(),
                         to: resolver_ata.to_account_info(),
                         authority: wauth_ai.to_account_info(),
                     },
                     &[&win_signer],
                 ),
                 share_resolver,
+                mint.decimals,
             )?;
         }
 
         // Transfer creator tip
         if share_creator > 0 {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: wvault_ai.to_account_info(),
+                        mint: mint.to_account_info(),
                         to: creator_ata.to_account_info(),
                         authority: wauth_ai.to_account_info(),
                     },
                     &[&win_signer],
                 ),
                 share_creator,
+                mint.decimals,
             )?;
         }
 
         // Transfer protocol fee
         if share_fee > 0 {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
                     token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: wvault_ai.to_account_info(),
+                        mint: mint.to_account_info(),
                         to: treasury_ata.to_account_info(),
                         authority: wauth_ai.to_account_info(),
                     },
                     &[&win_signer],
                 ),
                 share_fee,
+                mint.decimals,
+            )?;
+        }
+
+        // Transfer disputer reward
+        if share_reward > 0 {
+            let disputer_ata = disputer_ata.ok_or(error!(ErrorCode::Unauthorized))?;
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: wvault_ai.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: disputer_ata.to_account_info(),
+                        authority: wauth_ai.to_account_info(),
+                    },
+                    &[&win_signer],
+                ),
+                share_reward,
+                mint.decimals,
+            )?;
+        }
+
+        // Transfer referral slice
+        if share_referral > 0 {
+            let referral_vault = referral_vault.ok_or(error!(ErrorCode::ReferralVaultRequired))?;
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: wvault_ai.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: referral_vault.to_account_info(),
+                        authority: wauth_ai.to_account_info(),
+                    },
+                    &[&win_signer],
+                ),
+                share_referral,
+                mint.decimals,
             )?;
         }
     }